@@ -0,0 +1,133 @@
+//! Self-detection of error storms, without an external metrics stack.
+
+use crate::Fields;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// "More than `limit` errors of kind `category` within `window`".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    /// The [`Fields::kind`] that this rule counts.
+    pub category: &'static str,
+    pub limit: usize,
+    pub window: Duration,
+}
+
+#[derive(Debug)]
+struct Watch {
+    rule: Rule,
+    seen: VecDeque<Instant>,
+    tripped: bool,
+}
+
+/// Fires a callback when errors of some kind are recorded more often
+/// than a [`Rule`] allows.
+///
+/// The callback fires once when a rule is tripped, and the rule is
+/// re-armed once the rate drops back to within its limit.
+///
+/// # Examples
+/// ```rust
+/// use goof::Alarm;
+/// use std::time::Duration;
+///
+/// let mut storms = 0;
+/// let mut alarm = Alarm::new(|_rule, _count| storms += 1)
+///     .rule("mismatch", 2, Duration::from_secs(60));
+///
+/// for len in [0, 1, 2] {
+///     if let Err(error) = goof::assert_eq(&32, &len) {
+///         alarm.record(&error);
+///     }
+/// }
+/// drop(alarm);
+/// assert_eq!(storms, 1);
+/// ```
+pub struct Alarm<F: FnMut(&Rule, usize)> {
+    watches: Vec<Watch>,
+    on_trip: F,
+}
+
+impl<F: FnMut(&Rule, usize)> Alarm<F> {
+    /// An alarm with no rules, calling `on_trip` with the tripped rule
+    /// and the number of errors currently in its window.
+    pub const fn new(on_trip: F) -> Self {
+        Self {
+            watches: Vec::new(),
+            on_trip,
+        }
+    }
+
+    /// Trip when more than `limit` errors of kind `category` are
+    /// recorded within `window`.
+    pub fn rule(mut self, category: &'static str, limit: usize, window: Duration) -> Self {
+        self.watches.push(Watch {
+            rule: Rule {
+                category,
+                limit,
+                window,
+            },
+            seen: VecDeque::new(),
+            tripped: false,
+        });
+        self
+    }
+
+    /// Record that `error` happened just now.
+    pub fn record<E: Fields + ?Sized>(&mut self, error: &E) {
+        self.record_at(error, Instant::now())
+    }
+
+    /// Record that `error` happened at `at`.  Errors must be recorded
+    /// in chronological order.
+    pub fn record_at<E: Fields + ?Sized>(&mut self, error: &E, at: Instant) {
+        let kind = error.kind();
+        for watch in self.watches.iter_mut() {
+            if watch.rule.category != kind {
+                continue;
+            }
+            watch.seen.push_back(at);
+            while let Some(&oldest) = watch.seen.front() {
+                if at.saturating_duration_since(oldest) > watch.rule.window {
+                    watch.seen.pop_front();
+                } else {
+                    break;
+                }
+            }
+            if watch.seen.len() > watch.rule.limit {
+                if !watch.tripped {
+                    watch.tripped = true;
+                    (self.on_trip)(&watch.rule, watch.seen.len());
+                }
+            } else {
+                watch.tripped = false;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Alarm;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn trips_once_per_storm() {
+        let mismatch = crate::assert_eq(&1, &2).unwrap_err();
+        let outside = crate::assert_in(&0, &(1..2)).unwrap_err();
+        let mut trips = Vec::new();
+        let mut alarm = Alarm::new(|rule, count| trips.push((rule.category, count))).rule(
+            "mismatch",
+            2,
+            Duration::from_secs(10),
+        );
+
+        let start = Instant::now();
+        for second in [0, 1, 2, 3, 30, 31, 32] {
+            alarm.record_at(&mismatch, start + Duration::from_secs(second));
+            alarm.record_at(&outside, start + Duration::from_secs(second));
+        }
+        drop(alarm);
+        assert_eq!(trips, [("mismatch", 3), ("mismatch", 3)]);
+    }
+}
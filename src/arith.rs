@@ -0,0 +1,493 @@
+//! Checked arithmetic that fails with an error carrying the operands,
+//! instead of panicking or wrapping.
+//!
+//! # Examples
+//! ```rust
+//! # if cfg!(feature = "compact") { return; }
+//! use goof::arith::{checked_add, checked_div, checked_sub};
+//!
+//! assert_eq!(checked_add(200_u8, 50), Ok(250));
+//! assert_eq!(checked_add(200_u8, 100).unwrap_err().to_string(), "200 + 100 overflows u8");
+//! assert_eq!(checked_sub(0_u8, 1).unwrap_err().to_string(), "0 - 1 underflows u8");
+//! assert_eq!(checked_div(7_i32, 0).unwrap_err().to_string(), "Division of 7 by zero");
+//! ```
+
+use crate::{code, hook, ErrorCode, Fields, Interval, Outside};
+use core::fmt::{Debug, Display};
+
+/// The integers that the functions in this module work on.
+pub trait CheckedInt: Copy + PartialOrd + Display {
+    const ZERO: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! checked_int {
+    ($($t:ty),*) => {
+        $(impl CheckedInt for $t {
+            const ZERO: Self = 0;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+
+            fn checked_div(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_div(self, rhs)
+            }
+        })*
+    };
+}
+
+checked_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// The limits of an integer type, for [`try_narrow`].
+pub trait Limits: Copy + Ord {
+    const MIN: Self;
+    const MAX: Self;
+
+    /// This integer as a [`Wide`] one.
+    fn wide(self) -> Wide;
+}
+
+macro_rules! limits {
+    ($($t:ty),*) => {
+        $(impl Limits for $t {
+            const MIN: Self = <$t>::MIN;
+            const MAX: Self = <$t>::MAX;
+
+            #[allow(unused_comparisons)]
+            fn wide(self) -> Wide {
+                if self < 0 {
+                    Wide {
+                        negative: true,
+                        magnitude: (self as i128).unsigned_abs(),
+                    }
+                } else {
+                    Wide {
+                        negative: false,
+                        magnitude: self as u128,
+                    }
+                }
+            }
+        })*
+    };
+}
+
+limits!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// An integer of any type, so that [`try_narrow`] can report values
+/// and limits of different types in one [`Outside`].
+///
+/// It is packed, to keep that [`Outside`] small.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C, packed)]
+pub struct Wide {
+    negative: bool,
+    magnitude: u128,
+}
+
+impl Ord for Wide {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        let (lhs, rhs) = (*self, *other);
+        match (lhs.negative, rhs.negative) {
+            (false, false) => { lhs.magnitude }.cmp(&{ rhs.magnitude }),
+            (true, true) => { rhs.magnitude }.cmp(&{ lhs.magnitude }),
+            (negative, _) => rhs.negative.cmp(&negative),
+        }
+    }
+}
+
+impl PartialOrd for Wide {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Wide {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let Wide {
+            negative,
+            magnitude,
+        } = *self;
+        if negative {
+            f.write_str("-")?;
+        }
+        Display::fmt(&magnitude, f)
+    }
+}
+
+/// Convert `value` to a narrower integer type `T`, failing with an
+/// [`Outside`] error that shows which values `T` can hold.
+///
+/// The limits of `T` are clamped to what the type of `value` can
+/// hold, so narrowing a `u8` to an `i8` reports the range `0..=127`.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::arith::try_narrow;
+///
+/// let port: u16 = try_narrow(8080_u32).unwrap();
+/// assert_eq!(port, 8080);
+/// assert_eq!(
+///     try_narrow::<u16>(70000_u32).unwrap_err().to_string(),
+///     "Value 70000 is outside the allowed range 0..=65535 (must be at most 65535)"
+/// );
+/// assert_eq!(
+///     try_narrow::<u8>(-1_i32).unwrap_err().to_string(),
+///     "Value -1 is outside the allowed range 0..=255 (must be at least 0)"
+/// );
+/// assert_eq!(
+///     try_narrow::<i8>(200_u8).unwrap_err().to_string(),
+///     "Value 200 is outside the allowed range 0..=127 (must be at most 127)"
+/// );
+/// ```
+pub fn try_narrow<T: Limits>(value: impl Limits + TryInto<T>) -> Result<T, Outside<Wide>> {
+    fn outside<T: Limits, S: Limits>(value: S) -> Outside<Wide> {
+        hook::observe("outside", code::OUTSIDE, "try_narrow");
+        Outside {
+            range: T::MIN.wide().max(S::MIN.wide())..T::MAX.wide().min(S::MAX.wide()),
+            interval: Interval::Closed,
+            value: value.wide(),
+            provenance: "try_narrow",
+        }
+    }
+
+    value.try_into().map_err(|_| outside::<T, _>(value))
+}
+
+/// An arithmetic operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Display for Op {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Op::Add => "+",
+            Op::Sub => "-",
+            Op::Mul => "*",
+            Op::Div => "/",
+        })
+    }
+}
+
+/// `lhs + rhs`, failing instead of going past `T::MAX` or `T::MIN`.
+pub fn checked_add<T: CheckedInt>(lhs: T, rhs: T) -> Result<T, ArithGoof<T>> {
+    lhs.checked_add(rhs)
+        .ok_or_else(|| out_of_range(lhs, Op::Add, rhs, rhs >= T::ZERO, "checked_add"))
+}
+
+/// `lhs - rhs`, failing instead of going past `T::MAX` or `T::MIN`.
+pub fn checked_sub<T: CheckedInt>(lhs: T, rhs: T) -> Result<T, ArithGoof<T>> {
+    lhs.checked_sub(rhs)
+        .ok_or_else(|| out_of_range(lhs, Op::Sub, rhs, rhs < T::ZERO, "checked_sub"))
+}
+
+/// `lhs * rhs`, failing instead of going past `T::MAX` or `T::MIN`.
+pub fn checked_mul<T: CheckedInt>(lhs: T, rhs: T) -> Result<T, ArithGoof<T>> {
+    let positive = (lhs < T::ZERO) == (rhs < T::ZERO);
+    lhs.checked_mul(rhs)
+        .ok_or_else(|| out_of_range(lhs, Op::Mul, rhs, positive, "checked_mul"))
+}
+
+/// `lhs / rhs`, failing on division by zero and on `T::MIN / -1`.
+pub fn checked_div<T: CheckedInt>(lhs: T, rhs: T) -> Result<T, ArithGoof<T>> {
+    if rhs == T::ZERO {
+        hook::observe("division_by_zero", code::DIVISION_BY_ZERO, "checked_div");
+        return Err(ArithGoof::DivisionByZero(DivisionByZero {
+            dividend: lhs,
+            provenance: "checked_div",
+        }));
+    }
+    lhs.checked_div(rhs)
+        .ok_or_else(|| out_of_range(lhs, Op::Div, rhs, true, "checked_div"))
+}
+
+fn out_of_range<T>(lhs: T, op: Op, rhs: T, up: bool, provenance: &'static str) -> ArithGoof<T> {
+    if up {
+        hook::observe("overflow", code::OVERFLOW, provenance);
+        ArithGoof::Overflow(Overflow::new(lhs, op, rhs, provenance))
+    } else {
+        hook::observe("underflow", code::UNDERFLOW, provenance);
+        ArithGoof::Underflow(Underflow::new(lhs, op, rhs, provenance))
+    }
+}
+
+#[derive(Clone, Copy)]
+struct OutOfRange<T> {
+    lhs: T,
+    op: Op,
+    rhs: T,
+    provenance: &'static str,
+}
+
+macro_rules! out_of_range_error {
+    ($name:ident, $kind:literal, $code:path, $verb:literal, $doc:literal) => {
+        #[doc = $doc]
+        #[derive(Clone, Copy)]
+        pub struct $name<T>(OutOfRange<T>);
+
+        impl<T> $name<T> {
+            pub(crate) fn new(lhs: T, op: Op, rhs: T, provenance: &'static str) -> Self {
+                Self(OutOfRange {
+                    lhs,
+                    op,
+                    rhs,
+                    provenance,
+                })
+            }
+
+            pub fn lhs(&self) -> &T {
+                &self.0.lhs
+            }
+
+            pub fn op(&self) -> Op {
+                self.0.op
+            }
+
+            pub fn rhs(&self) -> &T {
+                &self.0.rhs
+            }
+
+            /// The name of the function that created this error, e.g.
+            /// `"checked_add"`.
+            pub fn provenance(&self) -> &'static str {
+                self.0.provenance
+            }
+        }
+
+        impl<T: PartialEq> PartialEq for $name<T> {
+            fn eq(&self, other: &Self) -> bool {
+                self.0.lhs == other.0.lhs && self.0.op == other.0.op && self.0.rhs == other.0.rhs
+            }
+        }
+
+        impl<T: Eq> Eq for $name<T> {}
+
+        impl<T: Debug> Debug for $name<T> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("lhs", &self.0.lhs)
+                    .field("op", &self.0.op)
+                    .field("rhs", &self.0.rhs)
+                    .finish()
+            }
+        }
+
+        impl<T: Display> Display for $name<T> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                compact!(self, f);
+                write!(
+                    f,
+                    concat!("{} {} {} ", $verb, " {}"),
+                    self.0.lhs,
+                    self.0.op,
+                    self.0.rhs,
+                    core::any::type_name::<T>()
+                )
+            }
+        }
+
+        impl<T: Debug + Display> core::error::Error for $name<T> {}
+
+        impl<T: Display> Fields for $name<T> {
+            fn kind(&self) -> &'static str {
+                $kind
+            }
+
+            fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+                visit("lhs", &self.0.lhs);
+                visit("op", &self.0.op);
+                visit("rhs", &self.0.rhs);
+            }
+        }
+
+        impl<T> ErrorCode for $name<T> {
+            fn code(&self) -> u32 {
+                $code
+            }
+        }
+    };
+}
+
+out_of_range_error!(
+    Overflow,
+    "overflow",
+    code::OVERFLOW,
+    "overflows",
+    "The result of an operation was greater than the type can hold."
+);
+out_of_range_error!(
+    Underflow,
+    "underflow",
+    code::UNDERFLOW,
+    "underflows",
+    "The result of an operation was less than the type can hold."
+);
+
+/// A division, or remainder, by zero.
+#[derive(Clone, Copy)]
+pub struct DivisionByZero<T> {
+    pub(crate) dividend: T,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl<T> DivisionByZero<T> {
+    pub fn dividend(&self) -> &T {
+        &self.dividend
+    }
+
+    /// The name of the function that created this error, e.g.
+    /// `"checked_div"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl<T: PartialEq> PartialEq for DivisionByZero<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.dividend == other.dividend
+    }
+}
+
+impl<T: Eq> Eq for DivisionByZero<T> {}
+
+impl<T: Debug> Debug for DivisionByZero<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DivisionByZero")
+            .field("dividend", &self.dividend)
+            .finish()
+    }
+}
+
+impl<T: Display> Display for DivisionByZero<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(f, "Division of {} by zero", self.dividend)
+    }
+}
+
+impl<T: Debug + Display> core::error::Error for DivisionByZero<T> {}
+
+impl<T: Display> Fields for DivisionByZero<T> {
+    fn kind(&self) -> &'static str {
+        "division_by_zero"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("dividend", &self.dividend);
+    }
+}
+
+impl<T> ErrorCode for DivisionByZero<T> {
+    fn code(&self) -> u32 {
+        code::DIVISION_BY_ZERO
+    }
+}
+
+/// Why a checked operation failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithGoof<T> {
+    Overflow(Overflow<T>),
+    Underflow(Underflow<T>),
+    DivisionByZero(DivisionByZero<T>),
+}
+
+impl<T: Display> Display for ArithGoof<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        match self {
+            ArithGoof::Overflow(error) => Display::fmt(error, f),
+            ArithGoof::Underflow(error) => Display::fmt(error, f),
+            ArithGoof::DivisionByZero(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl<T: Debug + Display> core::error::Error for ArithGoof<T> {}
+
+impl<T: Display> Fields for ArithGoof<T> {
+    fn kind(&self) -> &'static str {
+        match self {
+            ArithGoof::Overflow(error) => error.kind(),
+            ArithGoof::Underflow(error) => error.kind(),
+            ArithGoof::DivisionByZero(error) => error.kind(),
+        }
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        match self {
+            ArithGoof::Overflow(error) => error.for_each_field(visit),
+            ArithGoof::Underflow(error) => error.for_each_field(visit),
+            ArithGoof::DivisionByZero(error) => error.for_each_field(visit),
+        }
+    }
+}
+
+impl<T> ErrorCode for ArithGoof<T> {
+    fn code(&self) -> u32 {
+        match self {
+            ArithGoof::Overflow(error) => error.code(),
+            ArithGoof::Underflow(error) => error.code(),
+            ArithGoof::DivisionByZero(error) => error.code(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{checked_add, checked_div, checked_mul, checked_sub, try_narrow, ArithGoof};
+    use crate::Fields;
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn direction_of_signed_failures() {
+        let kind = |result: Result<i8, ArithGoof<i8>>| result.unwrap_err().kind();
+        assert_eq!(kind(checked_add(-100, -100)), "underflow");
+        assert_eq!(kind(checked_sub(100, -100)), "overflow");
+        assert_eq!(kind(checked_mul(-100, 2)), "underflow");
+        assert_eq!(kind(checked_mul(-100, -2)), "overflow");
+        assert_eq!(kind(checked_div(i8::MIN, -1)), "overflow");
+        assert_eq!(kind(checked_div(1, 0)), "division_by_zero");
+        assert_eq!(
+            checked_mul(-100_i8, 2).unwrap_err().to_string(),
+            "-100 * 2 underflows i8"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn narrowing_clamps_the_range_to_the_source() {
+        let error = try_narrow::<i8>(200_u8).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Value 200 is outside the allowed range 0..=127 (must be at most 127)"
+        );
+        assert_eq!(error.provenance(), "try_narrow");
+        assert_eq!(
+            try_narrow::<i8>(i128::MIN).unwrap_err().to_string(),
+            "Value -170141183460469231731687303715884105728 is outside the allowed range -128..=127 (must be at least -128)"
+        );
+        assert_eq!(
+            try_narrow::<u64>(u128::MAX).unwrap_err().to_string(),
+            "Value 340282366920938463463374607431768211455 is outside the allowed range 0..=18446744073709551615 (must be at most 18446744073709551615)"
+        );
+    }
+}
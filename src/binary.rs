@@ -0,0 +1,561 @@
+//! Errors for zero-copy parsing of binary formats.
+
+use crate::{code, hook, ErrorCode, Fields, Hex, ToHex};
+use alloc::format;
+use alloc::string::String;
+use core::fmt::{Debug, Display};
+use core::ops::{BitAnd, Not};
+
+/// Something with an address: an offset into a buffer or a pointer.
+pub trait Address {
+    fn address(&self) -> usize;
+}
+
+impl Address for usize {
+    fn address(&self) -> usize {
+        *self
+    }
+}
+
+impl<T: ?Sized> Address for *const T {
+    fn address(&self) -> usize {
+        self.cast::<()>() as usize
+    }
+}
+
+impl<T: ?Sized> Address for *mut T {
+    fn address(&self) -> usize {
+        self.cast::<()>() as usize
+    }
+}
+
+/// Assert that `at`, an offset or a pointer, is a multiple of
+/// `alignment` bytes.  An alignment of zero is taken to be one.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_aligned;
+///
+/// assert_eq!(assert_aligned(16_usize, 8), Ok(16));
+/// assert_eq!(
+///     assert_aligned(19_usize, 8).unwrap_err().to_string(),
+///     "Offset 0x13 is not aligned to 8 bytes (3 past 0x10)"
+/// );
+///
+/// let value = 0_u64;
+/// assert!(assert_aligned(&value as *const u64, align_of::<u64>()).is_ok());
+/// ```
+pub fn assert_aligned<A: Address>(at: A, alignment: usize) -> Result<A, Misaligned> {
+    let address = at.address();
+    if address.is_multiple_of(alignment.max(1)) {
+        Ok(at)
+    } else {
+        hook::observe("misaligned", code::MISALIGNED, "assert_aligned");
+        Err(Misaligned {
+            required: alignment,
+            actual_offset: address,
+            provenance: "assert_aligned",
+        })
+    }
+}
+
+/// An offset or pointer was not aligned as the data there requires.
+#[derive(Clone, Copy)]
+pub struct Misaligned {
+    /// The alignment in bytes.
+    pub(crate) required: usize,
+    pub(crate) actual_offset: usize,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl Misaligned {
+    pub fn required(&self) -> usize {
+        self.required
+    }
+
+    pub fn actual_offset(&self) -> usize {
+        self.actual_offset
+    }
+
+    /// How many bytes `actual_offset` is past the closest aligned one
+    /// before it.
+    pub fn excess(&self) -> usize {
+        self.actual_offset % self.required.max(1)
+    }
+
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_aligned"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl PartialEq for Misaligned {
+    fn eq(&self, other: &Self) -> bool {
+        self.required == other.required && self.actual_offset == other.actual_offset
+    }
+}
+
+impl Eq for Misaligned {}
+
+impl Debug for Misaligned {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Misaligned")
+            .field("required", &self.required)
+            .field("actual_offset", &self.actual_offset)
+            .finish()
+    }
+}
+
+impl Display for Misaligned {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(
+            f,
+            "Offset {:#x} is not aligned to {} bytes ({} past {:#x})",
+            self.actual_offset,
+            self.required,
+            self.excess(),
+            self.actual_offset - self.excess()
+        )
+    }
+}
+
+impl core::error::Error for Misaligned {}
+
+impl Fields for Misaligned {
+    fn kind(&self) -> &'static str {
+        "misaligned"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("required", &self.required);
+        visit("actual_offset", &self.actual_offset);
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(format!("pad by {} bytes", self.required - self.excess()))
+    }
+}
+
+impl ErrorCode for Misaligned {
+    fn code(&self) -> u32 {
+        code::MISALIGNED
+    }
+}
+
+/// The order of the bytes of a number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endian {
+    Little,
+    Big,
+}
+
+impl Endian {
+    /// The byte order of the target.
+    pub const NATIVE: Endian = if cfg!(target_endian = "little") {
+        Endian::Little
+    } else {
+        Endian::Big
+    };
+}
+
+impl Display for Endian {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Endian::Little => "little-endian",
+            Endian::Big => "big-endian",
+        })
+    }
+}
+
+/// Data was in a byte order other than the one the reader supports,
+/// e.g. according to a byte-order mark.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::{Endian, EndiannessMismatch};
+///
+/// let error = EndiannessMismatch::new(Endian::Little, Endian::Big);
+/// assert_eq!(
+///     error.to_string(),
+///     "Expected little-endian data, but got big-endian"
+/// );
+/// ```
+#[derive(Clone, Copy)]
+pub struct EndiannessMismatch {
+    pub(crate) expected: Endian,
+    pub(crate) found: Endian,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl EndiannessMismatch {
+    pub fn new(expected: Endian, found: Endian) -> Self {
+        hook::observe(
+            "endianness_mismatch",
+            code::ENDIANNESS_MISMATCH,
+            "EndiannessMismatch::new",
+        );
+        Self {
+            expected,
+            found,
+            provenance: "EndiannessMismatch::new",
+        }
+    }
+
+    pub fn expected(&self) -> Endian {
+        self.expected
+    }
+
+    pub fn found(&self) -> Endian {
+        self.found
+    }
+
+    /// The name of the function that created this error, e.g.
+    /// `"EndiannessMismatch::new"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl PartialEq for EndiannessMismatch {
+    fn eq(&self, other: &Self) -> bool {
+        self.expected == other.expected && self.found == other.found
+    }
+}
+
+impl Eq for EndiannessMismatch {}
+
+impl Debug for EndiannessMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("EndiannessMismatch")
+            .field("expected", &self.expected)
+            .field("found", &self.found)
+            .finish()
+    }
+}
+
+impl Display for EndiannessMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(f, "Expected {} data, but got {}", self.expected, self.found)
+    }
+}
+
+impl core::error::Error for EndiannessMismatch {}
+
+impl Fields for EndiannessMismatch {
+    fn kind(&self) -> &'static str {
+        "endianness_mismatch"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("expected", &self.expected);
+        visit("found", &self.found);
+    }
+}
+
+impl ErrorCode for EndiannessMismatch {
+    fn code(&self) -> u32 {
+        code::ENDIANNESS_MISMATCH
+    }
+}
+
+/// Assert that `value` has no bits set outside of `known_mask`, e.g.
+/// when decoding flags whose other bits are reserved.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_known_bits;
+///
+/// assert_eq!(assert_known_bits(0b0011_u8, 0b0111), Ok(0b0011));
+///
+/// let error = assert_known_bits(0b1010_u8, 0b0111).unwrap_err();
+/// assert_eq!(error.unknown(), &0b1000);
+/// assert_eq!(
+///     error.to_string(),
+///     "Unknown bits 0x08 set in 0x0a (known bits are 0x07)"
+/// );
+/// ```
+pub fn assert_known_bits<T>(value: T, known_mask: T) -> Result<T, UnknownBits<T>>
+where
+    T: BitAnd<Output = T> + Not<Output = T> + PartialEq + Default + Copy,
+{
+    let unknown = value & !known_mask;
+    if unknown == T::default() {
+        Ok(value)
+    } else {
+        hook::observe("unknown_bits", code::UNKNOWN_BITS, "assert_known_bits");
+        Err(UnknownBits {
+            known_mask,
+            value,
+            unknown,
+            provenance: "assert_known_bits",
+        })
+    }
+}
+
+/// Bits were set that have no known meaning.
+#[derive(Clone, Copy)]
+pub struct UnknownBits<T> {
+    pub(crate) known_mask: T,
+    pub(crate) value: T,
+    /// The bits of `value` outside of `known_mask`.
+    pub(crate) unknown: T,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl<T> UnknownBits<T> {
+    pub fn known_mask(&self) -> &T {
+        &self.known_mask
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Just the bits that were not expected.
+    pub fn unknown(&self) -> &T {
+        &self.unknown
+    }
+
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_known_bits"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl<T: PartialEq> PartialEq for UnknownBits<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.known_mask == other.known_mask && self.value == other.value
+    }
+}
+
+impl<T: Eq> Eq for UnknownBits<T> {}
+
+impl<T: Debug> Debug for UnknownBits<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("UnknownBits")
+            .field("known_mask", &self.known_mask)
+            .field("value", &self.value)
+            .field("unknown", &self.unknown)
+            .finish()
+    }
+}
+
+impl<T: ToHex> Display for UnknownBits<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(
+            f,
+            "Unknown bits {} set in {} (known bits are {})",
+            Hex(&self.unknown),
+            Hex(&self.value),
+            Hex(&self.known_mask)
+        )
+    }
+}
+
+impl<T: ToHex + Debug> core::error::Error for UnknownBits<T> {}
+
+impl<T: ToHex> Fields for UnknownBits<T> {
+    fn kind(&self) -> &'static str {
+        "unknown_bits"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("known_mask", &Hex(&self.known_mask));
+        visit("value", &Hex(&self.value));
+        visit("unknown", &Hex(&self.unknown));
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(format!("clear the bits {}", Hex(&self.unknown)))
+    }
+}
+
+impl<T> ErrorCode for UnknownBits<T> {
+    fn code(&self) -> u32 {
+        code::UNKNOWN_BITS
+    }
+}
+
+/// Assert that at least `needed` bytes are left in `input`.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_remaining;
+///
+/// assert!(assert_remaining(b"abcd", 4).is_ok());
+/// assert_eq!(
+///     assert_remaining(b"abc", 8).unwrap_err().to_string(),
+///     "Needed 8 bytes, but only 3 are left"
+/// );
+/// ```
+pub fn assert_remaining(input: &[u8], needed: usize) -> Result<&[u8], TooShort> {
+    if input.len() >= needed {
+        Ok(input)
+    } else {
+        Err(TooShort::observed(needed, input.len(), "assert_remaining"))
+    }
+}
+
+/// Split the first `n` bytes off `input`, advancing it past them.
+///
+/// On failure `input` is left as it was.
+///
+/// # Examples
+/// ```rust
+/// use goof::take_exact;
+///
+/// let mut input = &b"\x00\x2aGIF"[..];
+/// let length = u16::from_be_bytes(take_exact(&mut input, 2)?.try_into().unwrap());
+/// assert_eq!(length, 42);
+/// assert_eq!(input, b"GIF");
+///
+/// let error = take_exact(&mut input, 4).unwrap_err();
+/// assert_eq!((error.needed(), error.available()), (4, 3));
+/// assert_eq!(input, b"GIF");
+/// # Ok::<(), goof::TooShort>(())
+/// ```
+pub fn take_exact<'a>(input: &mut &'a [u8], n: usize) -> Result<&'a [u8], TooShort> {
+    match input.split_at_checked(n) {
+        Some((taken, rest)) => {
+            *input = rest;
+            Ok(taken)
+        }
+        None => Err(TooShort::observed(n, input.len(), "take_exact")),
+    }
+}
+
+/// The input ended before all the bytes that were needed.
+#[derive(Clone, Copy)]
+pub struct TooShort {
+    pub(crate) needed: usize,
+    pub(crate) available: usize,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl TooShort {
+    fn observed(needed: usize, available: usize, provenance: &'static str) -> Self {
+        hook::observe("too_short", code::TOO_SHORT, provenance);
+        Self {
+            needed,
+            available,
+            provenance,
+        }
+    }
+
+    /// For decoders that read from something other than a slice.
+    pub fn new(needed: usize, available: usize) -> Self {
+        Self::observed(needed, available, "TooShort::new")
+    }
+
+    pub fn needed(&self) -> usize {
+        self.needed
+    }
+
+    pub fn available(&self) -> usize {
+        self.available
+    }
+
+    /// The name of the function that created this error, e.g.
+    /// `"take_exact"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl PartialEq for TooShort {
+    fn eq(&self, other: &Self) -> bool {
+        self.needed == other.needed && self.available == other.available
+    }
+}
+
+impl Eq for TooShort {}
+
+impl Debug for TooShort {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TooShort")
+            .field("needed", &self.needed)
+            .field("available", &self.available)
+            .finish()
+    }
+}
+
+impl Display for TooShort {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        let bytes = if self.needed == 1 { "byte" } else { "bytes" };
+        let are = if self.available == 1 { "is" } else { "are" };
+        write!(
+            f,
+            "Needed {} {}, but only {} {} left",
+            self.needed, bytes, self.available, are
+        )
+    }
+}
+
+impl core::error::Error for TooShort {}
+
+impl Fields for TooShort {
+    fn kind(&self) -> &'static str {
+        "too_short"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("needed", &self.needed);
+        visit("available", &self.available);
+    }
+}
+
+impl ErrorCode for TooShort {
+    fn code(&self) -> u32 {
+        code::TOO_SHORT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{assert_aligned, assert_known_bits, take_exact, TooShort};
+
+    #[test]
+    fn alignment() {
+        assert_eq!(assert_aligned(7_usize, 0), Ok(7));
+        assert_eq!(assert_aligned(7_usize, 1), Ok(7));
+        let error = assert_aligned(13_usize, 4).unwrap_err();
+        assert_eq!(error.excess(), 1);
+        assert_eq!(crate::Fields::help(&error).unwrap(), "pad by 3 bytes");
+    }
+
+    #[test]
+    fn bits() {
+        assert_eq!(assert_known_bits(0_u32, 0), Ok(0));
+        assert_eq!(
+            assert_known_bits(u64::MAX, 0xff).unwrap_err().unknown(),
+            &!0xff
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn taking_bytes() {
+        let mut input = &b"ab"[..];
+        assert_eq!(take_exact(&mut input, 0), Ok(&b""[..]));
+        assert_eq!(take_exact(&mut input, 2), Ok(&b"ab"[..]));
+        assert!(input.is_empty());
+        assert_eq!(
+            TooShort::new(1, 1).to_string(),
+            "Needed 1 byte, but only 1 is left"
+        );
+    }
+}
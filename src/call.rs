@@ -0,0 +1,148 @@
+//! Errors for call-like interfaces: interpreters, plugin systems and
+//! RPC layers.
+
+use crate::{code, hook, ErrorCode, Fields};
+use core::fmt::{Debug, Display};
+
+/// Assert that `callee` was called with as many arguments as it
+/// takes.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_arity;
+///
+/// assert_eq!(assert_arity("foo", 2, 2), Ok(2));
+/// assert_eq!(
+///     assert_arity("foo", 2, 5).unwrap_err().to_string(),
+///     "function `foo` expects 2 arguments, got 5"
+/// );
+/// ```
+pub fn assert_arity(
+    callee: &'static str,
+    expected: usize,
+    actual: usize,
+) -> Result<usize, ArityMismatch> {
+    if expected == actual {
+        Ok(actual)
+    } else {
+        hook::observe("arity_mismatch", code::ARITY_MISMATCH, "assert_arity");
+        Err(ArityMismatch {
+            callee,
+            expected,
+            actual,
+            provenance: "assert_arity",
+        })
+    }
+}
+
+/// Something was called with the wrong number of arguments.
+#[derive(Clone, Copy)]
+pub struct ArityMismatch {
+    /// The name of what was called.
+    pub(crate) callee: &'static str,
+    pub(crate) expected: usize,
+    pub(crate) actual: usize,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl ArityMismatch {
+    pub fn new(callee: &'static str, expected: usize, actual: usize) -> Self {
+        hook::observe("arity_mismatch", code::ARITY_MISMATCH, "ArityMismatch::new");
+        Self {
+            callee,
+            expected,
+            actual,
+            provenance: "ArityMismatch::new",
+        }
+    }
+
+    pub fn callee(&self) -> &'static str {
+        self.callee
+    }
+
+    pub fn expected(&self) -> usize {
+        self.expected
+    }
+
+    pub fn actual(&self) -> usize {
+        self.actual
+    }
+
+    /// The name of the function that created this error, e.g.
+    /// `"assert_arity"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl PartialEq for ArityMismatch {
+    fn eq(&self, other: &Self) -> bool {
+        self.callee == other.callee
+            && self.expected == other.expected
+            && self.actual == other.actual
+    }
+}
+
+impl Eq for ArityMismatch {}
+
+impl Debug for ArityMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ArityMismatch")
+            .field("callee", &self.callee)
+            .field("expected", &self.expected)
+            .field("actual", &self.actual)
+            .finish()
+    }
+}
+
+impl Display for ArityMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        let arguments = if self.expected == 1 {
+            "argument"
+        } else {
+            "arguments"
+        };
+        write!(
+            f,
+            "function `{}` expects {} {}, got {}",
+            self.callee, self.expected, arguments, self.actual
+        )
+    }
+}
+
+impl core::error::Error for ArityMismatch {}
+
+impl Fields for ArityMismatch {
+    fn kind(&self) -> &'static str {
+        "arity_mismatch"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("callee", &self.callee);
+        visit("expected", &self.expected);
+        visit("actual", &self.actual);
+    }
+}
+
+impl ErrorCode for ArityMismatch {
+    fn code(&self) -> u32 {
+        code::ARITY_MISMATCH
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ArityMismatch;
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn singular() {
+        assert_eq!(
+            ArityMismatch::new("len", 1, 0).to_string(),
+            "function `len` expects 1 argument, got 0"
+        );
+    }
+}
@@ -0,0 +1,122 @@
+//! The error for work that was stopped on purpose.
+
+use crate::{code, hook, ErrorCode, Fields};
+use core::fmt::{Debug, Display};
+
+/// Work was cancelled before it could finish, e.g. because the user
+/// asked for it or the caller is no longer interested in the result.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::{Cancelled, Report};
+///
+/// fn work(stop: bool) -> Result<u32, Cancelled> {
+///     if stop {
+///         return Err(Cancelled::because("shutting down"));
+///     }
+///     Ok(42)
+/// }
+///
+/// let report = Report::from(work(true).unwrap_err());
+/// assert_eq!(report.to_string(), "Cancelled: shutting down");
+/// ```
+#[derive(Clone, Copy)]
+pub struct Cancelled {
+    pub(crate) reason: Option<&'static str>,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl Cancelled {
+    pub fn new() -> Self {
+        hook::observe("cancelled", code::CANCELLED, "Cancelled::new");
+        Self {
+            reason: None,
+            provenance: "Cancelled::new",
+        }
+    }
+
+    pub fn because(reason: &'static str) -> Self {
+        hook::observe("cancelled", code::CANCELLED, "Cancelled::because");
+        Self {
+            reason: Some(reason),
+            provenance: "Cancelled::because",
+        }
+    }
+
+    pub fn reason(&self) -> Option<&'static str> {
+        self.reason
+    }
+
+    /// The name of the function that created this error, e.g.
+    /// `"Cancelled::because"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl Default for Cancelled {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for Cancelled {
+    fn eq(&self, other: &Self) -> bool {
+        self.reason == other.reason
+    }
+}
+
+impl Eq for Cancelled {}
+
+impl Debug for Cancelled {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Cancelled")
+            .field("reason", &self.reason)
+            .finish()
+    }
+}
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        match self.reason {
+            Some(reason) => write!(f, "Cancelled: {}", reason),
+            None => f.write_str("Cancelled"),
+        }
+    }
+}
+
+impl core::error::Error for Cancelled {}
+
+impl Fields for Cancelled {
+    fn kind(&self) -> &'static str {
+        "cancelled"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        if let Some(reason) = &self.reason {
+            visit("reason", reason);
+        }
+    }
+}
+
+impl ErrorCode for Cancelled {
+    fn code(&self) -> u32 {
+        code::CANCELLED
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cancelled;
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn reason_is_optional() {
+        assert_eq!(Cancelled::new().to_string(), "Cancelled");
+        assert_eq!(Cancelled::because("timed out").reason(), Some("timed out"));
+        assert_ne!(Cancelled::new(), Cancelled::because("timed out"));
+    }
+}
@@ -0,0 +1,239 @@
+//! Recording which error caused another, so that cause-chain printers
+//! can show both.
+
+use crate::Fields;
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::error::Error;
+use core::fmt::{Debug, Display};
+
+/// An error `E` that may have been caused by another error.
+///
+/// The wrapper is transparent for `Display` and [`Fields`]; the cause
+/// is only reachable through [`Error::source`], so it composes with
+/// `#[source]` in `thiserror` and with cause-chain printers.
+///
+/// The cause can be of any type, unless its type `S` is given, as it
+/// is by [`CausedBy::caused_by`].
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use core::error::Error;
+/// use goof::{Caused, Mismatch, Outside};
+///
+/// let cause: Outside<u32> = goof::assert_in(&9, &(1..5)).unwrap_err();
+/// let error: Caused<Mismatch<u32>> =
+///     Caused::new(goof::assert_eq(&4, &1).unwrap_err()).with_source(cause);
+///
+/// assert_eq!(error.to_string(), "Expected 4, but got 1");
+/// assert_eq!(error.source().unwrap().to_string(), "Value 9 is outside the allowed range (1, 5] (must be at most 5)");
+/// ```
+pub struct Caused<E, S: ?Sized = dyn Error + Send + Sync + 'static> {
+    error: E,
+    source: Option<Box<S>>,
+}
+
+impl<E, S: ?Sized> Caused<E, S> {
+    /// The error that was caused, without its cause.
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// The cause of this error, if one was recorded.
+    pub fn cause(&self) -> Option<&S> {
+        self.source.as_deref()
+    }
+
+    /// The error that was caused, dropping its cause.
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+}
+
+impl<E> Caused<E> {
+    /// Wrap `error`, without a cause.
+    pub const fn new(error: E) -> Self {
+        Self {
+            error,
+            source: None,
+        }
+    }
+
+    /// Record `source` as the cause of this error.
+    pub fn with_source(mut self, source: impl Error + Send + Sync + 'static) -> Self {
+        self.source = Some(Box::new(source));
+        self
+    }
+}
+
+/// Adds [`CausedBy::caused_by`] to every error.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use core::error::Error;
+/// use goof::{Caused, CausedBy, Mismatch};
+/// use std::num::ParseIntError;
+///
+/// let cause = "v2".parse::<u8>().unwrap_err();
+/// let error: Caused<Mismatch<u8>, ParseIntError> =
+///     goof::assert_eq(&4, &0).unwrap_err().caused_by(cause.clone());
+///
+/// assert_eq!(error.to_string(), "Expected 4, but got 0");
+/// assert_eq!(error.source().unwrap().to_string(), "invalid digit found in string");
+/// assert_eq!(error.cause(), Some(&cause));
+/// ```
+pub trait CausedBy: Sized {
+    /// Record `source` as the cause of this error, keeping its type.
+    fn caused_by<S: Error + 'static>(self, source: S) -> Caused<Self, S> {
+        Caused {
+            error: self,
+            source: Some(Box::new(source)),
+        }
+    }
+}
+
+impl<E: Error> CausedBy for E {}
+
+impl<E, S: ?Sized> From<E> for Caused<E, S> {
+    fn from(error: E) -> Self {
+        Self {
+            error,
+            source: None,
+        }
+    }
+}
+
+/// The error behind the cause of a [`Caused`].
+pub trait AsSource {
+    fn as_source(&self) -> &(dyn Error + 'static);
+}
+
+impl<S: Error + 'static> AsSource for S {
+    fn as_source(&self) -> &(dyn Error + 'static) {
+        self
+    }
+}
+
+impl AsSource for dyn Error + Send + Sync + 'static {
+    fn as_source(&self) -> &(dyn Error + 'static) {
+        self
+    }
+}
+
+impl<E: Debug, S: Debug + ?Sized> Debug for Caused<E, S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Caused")
+            .field("error", &self.error)
+            .field("source", &self.source)
+            .finish()
+    }
+}
+
+impl<E: Display, S: ?Sized> Display for Caused<E, S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.error, f)
+    }
+}
+
+impl<E: Error, S: AsSource + Debug + ?Sized> Error for Caused<E, S> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.source {
+            Some(source) => Some(source.as_source()),
+            None => self.error.source(),
+        }
+    }
+}
+
+impl<E: Fields, S: ?Sized> Fields for Caused<E, S> {
+    fn kind(&self) -> &'static str {
+        self.error.kind()
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        self.error.for_each_field(visit)
+    }
+
+    fn help(&self) -> Option<String> {
+        self.error.help()
+    }
+
+    fn severity(&self) -> crate::Severity {
+        self.error.severity()
+    }
+
+    fn as_explain(&self) -> Option<&dyn crate::Explain> {
+        self.error.as_explain()
+    }
+}
+
+/// Walk the chain of causes of `error`, starting with `error` itself,
+/// and return the first one that is an `E`, or that is a [`Caused`]
+/// wrapping an `E`.
+///
+/// This works with anything that dereferences to `dyn Error`, which
+/// includes `anyhow::Error` and `Box<dyn Error>`, so a mixed codebase
+/// can recover typed errors from wherever they got wrapped.
+///
+/// # Examples
+/// ```rust
+/// use goof::{Caused, Mismatch, Outside, find_cause};
+///
+/// let error: Box<dyn core::error::Error> = Box::new(
+///     Caused::new(goof::assert_eq(&4, &1).unwrap_err())
+///         .with_source(goof::assert_in(&9_u8, &(1..5)).unwrap_err()),
+/// );
+///
+/// let outside = find_cause::<Outside<u8>>(&*error).unwrap();
+/// assert_eq!(outside, &goof::assert_in(&9, &(1..5)).unwrap_err());
+/// assert_eq!(find_cause::<Mismatch<i32>>(&*error), Some(&goof::assert_eq(&4, &1).unwrap_err()));
+/// assert!(find_cause::<Mismatch<u8>>(&*error).is_none());
+/// ```
+pub fn find_cause<'a, E: Error + 'static>(error: &'a (dyn Error + 'static)) -> Option<&'a E> {
+    let mut link = Some(error);
+    while let Some(error) = link {
+        if let Some(found) = error.downcast_ref::<E>() {
+            return Some(found);
+        }
+        if let Some(caused) = error.downcast_ref::<Caused<E>>() {
+            return Some(caused.error());
+        }
+        link = error.source();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_cause, Caused};
+    use crate::{Mismatch, Outside};
+    use core::error::Error;
+
+    #[test]
+    fn source_chain() {
+        let outside = crate::assert_in(&9, &(1..5)).unwrap_err();
+        let caused =
+            Caused::new(crate::assert_eq(&1, &2).unwrap_err()).with_source(outside.clone());
+        let source = caused.source().unwrap();
+        assert_eq!(source.to_string(), outside.to_string());
+        assert!(Caused::new(outside).source().is_none());
+    }
+
+    #[test]
+    fn finds_cause_by_type() {
+        let caused = Caused::new(crate::assert_eq(&1_u8, &2).unwrap_err())
+            .with_source(crate::assert_in(&9_u8, &(1..5)).unwrap_err());
+        let error: &(dyn Error + 'static) = &caused;
+        assert!(find_cause::<Caused<Mismatch<u8>>>(error).is_some());
+        assert_eq!(
+            find_cause::<Mismatch<u8>>(error),
+            Some(&crate::assert_eq(&1, &2).unwrap_err())
+        );
+        assert_eq!(
+            find_cause::<Outside<u8>>(error).unwrap().provenance(),
+            "assert_in"
+        );
+        assert!(find_cause::<Outside<u16>>(error).is_none());
+    }
+}
@@ -0,0 +1,337 @@
+//! Stable numeric codes for errors, for matching in logs and
+//! dashboards without parsing messages.
+//!
+//! The built-in errors only ever use codes below [`FIRST_FREE`], so
+//! codes from there upwards are free for applications.
+
+use crate::{At, Caused, Mismatch, Outside, Spanned, Unknown};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+/// The first code that applications can use for their own errors.
+/// The ones below it are reserved for the built-in errors.
+pub const FIRST_FREE: u32 = 1000;
+
+/// The code of [`Mismatch`].
+pub const MISMATCH: u32 = 1;
+/// The code of [`Outside`].
+pub const OUTSIDE: u32 = 2;
+/// The code of [`Unknown`].
+pub const UNKNOWN: u32 = 3;
+/// The code of `Panicked`.
+pub const PANICKED: u32 = 4;
+/// The code of [`crate::PatternMismatch`].
+pub const PATTERN_MISMATCH: u32 = 5;
+/// The code of [`crate::FailedPredicate`].
+pub const FAILED_PREDICATE: u32 = 6;
+/// The code of [`crate::LengthMismatch`].
+pub const LENGTH_MISMATCH: u32 = 7;
+/// The code of [`crate::LengthOutside`].
+pub const LENGTH_OUTSIDE: u32 = 8;
+/// The code of [`crate::UnsortedAt`].
+pub const UNSORTED: u32 = 9;
+/// The code of [`crate::Duplicate`].
+pub const DUPLICATE: u32 = 10;
+/// The code of [`crate::NotSubset`].
+pub const NOT_SUBSET: u32 = 11;
+/// The code of [`crate::NotDisjoint`].
+pub const NOT_DISJOINT: u32 = 12;
+/// The code of [`crate::SliceMismatch`].
+pub const SLICE_MISMATCH: u32 = 13;
+/// The code of [`crate::MagicMismatch`].
+pub const MAGIC_MISMATCH: u32 = 14;
+/// The code of [`crate::ExpectedOneOf`].
+pub const EXPECTED_ONE_OF: u32 = 15;
+/// The code of [`crate::schema::MissingField`].
+pub const MISSING_FIELD: u32 = 16;
+/// The code of [`crate::schema::UnknownField`].
+pub const UNKNOWN_FIELD: u32 = 17;
+/// The code of [`crate::schema::DuplicateKey`].
+pub const DUPLICATE_KEY: u32 = 18;
+/// The code of [`crate::config::ConfigGoof`].
+pub const CONFIG: u32 = 19;
+/// The code of `env::MissingEnv`.
+pub const MISSING_ENV: u32 = 20;
+/// The code of `env::InvalidEnv`.
+pub const INVALID_ENV: u32 = 21;
+/// The code of `IoGoof`.
+pub const IO: u32 = 22;
+/// The code of [`crate::arith::Overflow`].
+pub const OVERFLOW: u32 = 23;
+/// The code of [`crate::arith::Underflow`].
+pub const UNDERFLOW: u32 = 24;
+/// The code of [`crate::arith::DivisionByZero`].
+pub const DIVISION_BY_ZERO: u32 = 25;
+/// The code of [`crate::NotPositive`].
+pub const NOT_POSITIVE: u32 = 26;
+/// The code of [`crate::Zero`].
+pub const ZERO: u32 = 27;
+/// The code of [`crate::NotMultipleOf`].
+pub const NOT_MULTIPLE_OF: u32 = 28;
+/// The code of [`crate::Timeout`].
+pub const TIMEOUT: u32 = 29;
+/// The code of [`crate::retry::RetriesExhausted`].
+pub const RETRIES_EXHAUSTED: u32 = 30;
+/// The code of [`crate::Cancelled`].
+pub const CANCELLED: u32 = 31;
+/// The code of [`crate::InvalidTransition`].
+pub const INVALID_TRANSITION: u32 = 32;
+/// The code of [`crate::InsufficientPermission`].
+pub const INSUFFICIENT_PERMISSION: u32 = 33;
+/// The code of [`crate::Exceeded`].
+pub const EXCEEDED: u32 = 34;
+/// The code of [`crate::RateLimited`].
+pub const RATE_LIMITED: u32 = 35;
+/// The code of [`crate::ArityMismatch`].
+pub const ARITY_MISMATCH: u32 = 36;
+/// The code of `goof::semver::VersionOutside`, with the `semver` feature.
+pub const VERSION_OUTSIDE: u32 = 37;
+/// The code of [`crate::Misaligned`].
+pub const MISALIGNED: u32 = 38;
+/// The code of [`crate::EndiannessMismatch`].
+pub const ENDIANNESS_MISMATCH: u32 = 39;
+/// The code of [`crate::UnknownBits`].
+pub const UNKNOWN_BITS: u32 = 40;
+/// The code of [`crate::TooShort`].
+pub const TOO_SHORT: u32 = 41;
+/// The code of [`crate::BadText`].
+pub const BAD_TEXT: u32 = 42;
+/// The code of [`crate::Goof`] and [`crate::InlineGoof`].
+pub const GOOF: u32 = 43;
+/// The code of [`crate::MultiError`].
+pub const MULTI: u32 = 44;
+/// The code of [`crate::EmptyRange`].
+pub const EMPTY_RANGE: u32 = 45;
+/// The code of [`crate::NotComparable`].
+pub const NOT_COMPARABLE: u32 = 46;
+/// The code of [`crate::TooLarge`].
+pub const TOO_LARGE: u32 = 47;
+/// The code of [`crate::TooSmall`].
+pub const TOO_SMALL: u32 = 48;
+/// The code of [`crate::ParseGoof`].
+pub const PARSE: u32 = 49;
+/// The code of [`crate::schema::InvalidData`].
+pub const INVALID_DATA: u32 = 50;
+
+/// An error identified by a stable number.
+///
+/// # Examples
+/// ```rust
+/// use goof::ErrorCode;
+///
+/// assert_eq!(goof::assert_eq(&1, &2).unwrap_err().code(), goof::code::MISMATCH);
+/// ```
+pub trait ErrorCode {
+    /// The number identifying this kind of error.  It never changes
+    /// between releases.
+    fn code(&self) -> u32;
+}
+
+impl<T: Copy + Eq> ErrorCode for Mismatch<T> {
+    fn code(&self) -> u32 {
+        MISMATCH
+    }
+}
+
+impl<T: PartialOrd + Copy> ErrorCode for Outside<T> {
+    fn code(&self) -> u32 {
+        OUTSIDE
+    }
+}
+
+impl<T: Eq> ErrorCode for Unknown<'_, T> {
+    fn code(&self) -> u32 {
+        UNKNOWN
+    }
+}
+
+impl<E: ErrorCode, S: ?Sized> ErrorCode for Caused<E, S> {
+    fn code(&self) -> u32 {
+        self.error().code()
+    }
+}
+
+impl<E: ErrorCode> ErrorCode for Spanned<E> {
+    fn code(&self) -> u32 {
+        self.error().code()
+    }
+}
+
+impl<E: ErrorCode> ErrorCode for At<E> {
+    fn code(&self) -> u32 {
+        self.error().code()
+    }
+}
+
+impl<E: ErrorCode> ErrorCode for crate::http::WithStatus<E> {
+    fn code(&self) -> u32 {
+        self.error().code()
+    }
+}
+
+/// Two errors were registered under the same code, or an application
+/// tried to use a code reserved for the built-in errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodeTaken {
+    pub code: u32,
+    /// The name already registered under `code`, or `"goof"` if the
+    /// code is reserved but not in use yet.
+    pub taken_by: &'static str,
+    /// The name that could not be registered.
+    pub name: &'static str,
+}
+
+impl Display for CodeTaken {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.code < FIRST_FREE {
+            return write!(
+                f,
+                "Code {} is reserved for the built-in errors, so {} cannot use it",
+                self.code, self.name
+            );
+        }
+        write!(
+            f,
+            "Code {} is already taken by {}, so {} cannot use it",
+            self.code, self.taken_by, self.name
+        )
+    }
+}
+
+impl core::error::Error for CodeTaken {}
+
+/// The names of every known error code, including the ones an
+/// application defines for its own errors.
+///
+/// # Examples
+/// ```rust
+/// use goof::code::Registry;
+///
+/// let mut registry = Registry::new();
+/// registry.register(1000, "config_missing").unwrap();
+/// assert_eq!(registry.name(1000), Some("config_missing"));
+/// assert_eq!(registry.name(goof::code::OUTSIDE), Some("outside"));
+/// assert_eq!(
+///     registry.register(999, "mine").unwrap_err().to_string(),
+///     "Code 999 is reserved for the built-in errors, so mine cannot use it"
+/// );
+/// assert!(registry.register(1000, "mine").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Registry {
+    entries: Vec<(u32, &'static str)>,
+}
+
+impl Registry {
+    /// A registry of the built-in codes.
+    pub fn new() -> Self {
+        Self {
+            entries: vec![
+                (MISMATCH, "mismatch"),
+                (OUTSIDE, "outside"),
+                (UNKNOWN, "unknown"),
+                (PANICKED, "panicked"),
+                (PATTERN_MISMATCH, "pattern_mismatch"),
+                (FAILED_PREDICATE, "failed_predicate"),
+                (LENGTH_MISMATCH, "length_mismatch"),
+                (LENGTH_OUTSIDE, "length_outside"),
+                (UNSORTED, "unsorted"),
+                (DUPLICATE, "duplicate"),
+                (NOT_SUBSET, "not_subset"),
+                (NOT_DISJOINT, "not_disjoint"),
+                (SLICE_MISMATCH, "slice_mismatch"),
+                (MAGIC_MISMATCH, "magic_mismatch"),
+                (EXPECTED_ONE_OF, "expected_one_of"),
+                (MISSING_FIELD, "missing_field"),
+                (UNKNOWN_FIELD, "unknown_field"),
+                (DUPLICATE_KEY, "duplicate_key"),
+                (CONFIG, "config"),
+                (MISSING_ENV, "missing_env"),
+                (INVALID_ENV, "invalid_env"),
+                (IO, "io"),
+                (OVERFLOW, "overflow"),
+                (UNDERFLOW, "underflow"),
+                (DIVISION_BY_ZERO, "division_by_zero"),
+                (NOT_POSITIVE, "not_positive"),
+                (ZERO, "zero"),
+                (NOT_MULTIPLE_OF, "not_multiple_of"),
+                (TIMEOUT, "timeout"),
+                (RETRIES_EXHAUSTED, "retries_exhausted"),
+                (CANCELLED, "cancelled"),
+                (INVALID_TRANSITION, "invalid_transition"),
+                (INSUFFICIENT_PERMISSION, "insufficient_permission"),
+                (EXCEEDED, "exceeded"),
+                (RATE_LIMITED, "rate_limited"),
+                (ARITY_MISMATCH, "arity_mismatch"),
+                (VERSION_OUTSIDE, "version_outside"),
+                (MISALIGNED, "misaligned"),
+                (ENDIANNESS_MISMATCH, "endianness_mismatch"),
+                (UNKNOWN_BITS, "unknown_bits"),
+                (TOO_SHORT, "too_short"),
+                (BAD_TEXT, "bad_text"),
+                (GOOF, "goof"),
+                (MULTI, "multi"),
+                (EMPTY_RANGE, "empty_range"),
+                (NOT_COMPARABLE, "not_comparable"),
+                (TOO_LARGE, "too_large"),
+                (TOO_SMALL, "too_small"),
+                (PARSE, "parse"),
+                (INVALID_DATA, "invalid_data"),
+            ],
+        }
+    }
+
+    /// Give `code` a `name`, unless the code is already taken or is
+    /// below [`FIRST_FREE`].
+    pub fn register(&mut self, code: u32, name: &'static str) -> Result<(), CodeTaken> {
+        let taken_by = match self.name(code) {
+            Some(taken_by) => Some(taken_by),
+            None => (code < FIRST_FREE).then_some("goof"),
+        };
+        if let Some(taken_by) = taken_by {
+            return Err(CodeTaken {
+                code,
+                taken_by,
+                name,
+            });
+        }
+        self.entries.push((code, name));
+        Ok(())
+    }
+
+    pub fn name(&self, code: u32) -> Option<&'static str> {
+        self.entries
+            .iter()
+            .find(|(known, _)| *known == code)
+            .map(|(_, name)| *name)
+    }
+
+    /// Every registered code and its name, in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &'static str)> + '_ {
+        self.entries.iter().copied()
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ErrorCode, Registry};
+    use crate::Fields;
+
+    #[test]
+    fn builtin_codes_are_registered_under_their_kind() {
+        let registry = Registry::new();
+        let mismatch = crate::assert_eq(&1, &2).unwrap_err();
+        let outside = crate::assert_in(&0, &(1..2)).unwrap_err();
+        let unknown = crate::assert_known(&[1], 2).unwrap_err();
+        assert_eq!(registry.name(mismatch.code()), Some(mismatch.kind()));
+        assert_eq!(registry.name(outside.code()), Some(outside.kind()));
+        assert_eq!(registry.name(unknown.code()), Some(unknown.kind()));
+    }
+}
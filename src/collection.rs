@@ -0,0 +1,761 @@
+//! Assertions about collections as a whole.
+
+use crate::{code, hook, ErrorCode, Fields};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::{Debug, Display};
+use core::ops::{Bound, RangeBounds};
+
+/// Assert that `collection` has exactly `expected` elements, returning
+/// its length.
+///
+/// Unlike `assert_eq(&expected, &collection.len())` the error knows
+/// that it is about a number of elements.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_len;
+///
+/// assert_eq!(assert_len(&[1, 2, 3], 3), Ok(3));
+/// assert_eq!(
+///     assert_len(&vec![1, 2], 3).unwrap_err().to_string(),
+///     "Expected 3 elements, but got 2"
+/// );
+/// ```
+pub fn assert_len<C>(collection: C, expected: usize) -> Result<usize, LengthMismatch>
+where
+    C: IntoIterator,
+    C::IntoIter: ExactSizeIterator,
+{
+    let actual = collection.into_iter().len();
+    if actual == expected {
+        Ok(actual)
+    } else {
+        hook::observe("length_mismatch", code::LENGTH_MISMATCH, "assert_len");
+        Err(LengthMismatch {
+            expected,
+            actual,
+            provenance: "assert_len",
+        })
+    }
+}
+
+/// Assert that the number of elements in `collection` lies in
+/// `range`, returning it.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_len_in;
+///
+/// assert_eq!(assert_len_in(&[1, 2, 3], 1..=3), Ok(3));
+/// assert_eq!(
+///     assert_len_in(&[0_u8; 7], 2..5).unwrap_err().to_string(),
+///     "Expected 2..5 elements, but got 7"
+/// );
+/// ```
+pub fn assert_len_in<C, R>(collection: C, range: R) -> Result<usize, LengthOutside>
+where
+    C: IntoIterator,
+    C::IntoIter: ExactSizeIterator,
+    R: RangeBounds<usize>,
+{
+    let actual = collection.into_iter().len();
+    if range.contains(&actual) {
+        Ok(actual)
+    } else {
+        hook::observe("length_outside", code::LENGTH_OUTSIDE, "assert_len_in");
+        Err(LengthOutside {
+            range: Bounds(range.start_bound().cloned(), range.end_bound().cloned()),
+            actual,
+            provenance: "assert_len_in",
+        })
+    }
+}
+
+/// A collection did not have the number of elements it had to have.
+#[derive(Clone, Copy)]
+pub struct LengthMismatch {
+    pub(crate) expected: usize,
+    pub(crate) actual: usize,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl LengthMismatch {
+    pub fn expected(&self) -> usize {
+        self.expected
+    }
+
+    pub fn actual(&self) -> usize {
+        self.actual
+    }
+
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_len"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl PartialEq for LengthMismatch {
+    fn eq(&self, other: &Self) -> bool {
+        self.expected == other.expected && self.actual == other.actual
+    }
+}
+
+impl Eq for LengthMismatch {}
+
+impl Debug for LengthMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("LengthMismatch")
+            .field("expected", &self.expected)
+            .field("actual", &self.actual)
+            .finish()
+    }
+}
+
+impl Display for LengthMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(
+            f,
+            "Expected {} {}, but got {}",
+            self.expected,
+            elements(self.expected),
+            self.actual
+        )
+    }
+}
+
+impl core::error::Error for LengthMismatch {}
+
+impl Fields for LengthMismatch {
+    fn kind(&self) -> &'static str {
+        "length_mismatch"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("expected", &self.expected);
+        visit("actual", &self.actual);
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(format!(
+            "use exactly {} {}",
+            self.expected,
+            elements(self.expected)
+        ))
+    }
+}
+
+impl ErrorCode for LengthMismatch {
+    fn code(&self) -> u32 {
+        code::LENGTH_MISMATCH
+    }
+}
+
+/// The bounds of a range of lengths, rendered the way they are
+/// written in Rust, e.g. `2..5` or `..=3`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds(pub Bound<usize>, pub Bound<usize>);
+
+impl Display for Bounds {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0 {
+            Bound::Included(start) => write!(f, "{}", start)?,
+            // Not expressible as a Rust range, so spelled out.
+            Bound::Excluded(start) => write!(f, "{}<", start)?,
+            Bound::Unbounded => {}
+        }
+        match self.1 {
+            Bound::Included(end) => write!(f, "..={}", end),
+            Bound::Excluded(end) => write!(f, "..{}", end),
+            Bound::Unbounded => f.write_str(".."),
+        }
+    }
+}
+
+/// A collection had too many or too few elements.
+#[derive(Clone, Copy)]
+pub struct LengthOutside {
+    pub(crate) range: Bounds,
+    pub(crate) actual: usize,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl LengthOutside {
+    pub fn range(&self) -> Bounds {
+        self.range
+    }
+
+    pub fn actual(&self) -> usize {
+        self.actual
+    }
+
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_len_in"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl PartialEq for LengthOutside {
+    fn eq(&self, other: &Self) -> bool {
+        self.range == other.range && self.actual == other.actual
+    }
+}
+
+impl Eq for LengthOutside {}
+
+impl Debug for LengthOutside {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("LengthOutside")
+            .field("range", &self.range)
+            .field("actual", &self.actual)
+            .finish()
+    }
+}
+
+impl Display for LengthOutside {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(
+            f,
+            "Expected {} elements, but got {}",
+            self.range, self.actual
+        )
+    }
+}
+
+impl core::error::Error for LengthOutside {}
+
+impl Fields for LengthOutside {
+    fn kind(&self) -> &'static str {
+        "length_outside"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("range", &self.range);
+        visit("actual", &self.actual);
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(format!("use {} elements", self.range))
+    }
+}
+
+impl ErrorCode for LengthOutside {
+    fn code(&self) -> u32 {
+        code::LENGTH_OUTSIDE
+    }
+}
+
+/// Assert that `slice` is sorted in non-decreasing order, e.g. before
+/// binary searching it, returning it.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_sorted;
+///
+/// assert!(assert_sorted(&[1, 2, 2, 5]).is_ok());
+/// assert_eq!(
+///     assert_sorted(&[1, 4, 3, 5]).unwrap_err().to_string(),
+///     "Not sorted at index 2: 4 comes before 3"
+/// );
+/// ```
+pub fn assert_sorted<T: PartialOrd + Clone>(slice: &[T]) -> Result<&[T], UnsortedAt<T>> {
+    match first_unsorted(slice, T::clone) {
+        None => Ok(slice),
+        Some(error) => Err(error.observed("assert_sorted")),
+    }
+}
+
+/// Like [`assert_sorted`], but compares the elements by `key`.
+///
+/// # Examples
+/// ```rust
+/// use goof::assert_sorted_by_key;
+///
+/// let names = ["Ann", "Bob", "Al"];
+/// let error = assert_sorted_by_key(&names, |name| name.len()).unwrap_err();
+/// assert_eq!((error.index(), error.prev(), error.next()), (2, &3, &2));
+/// ```
+pub fn assert_sorted_by_key<T, K: PartialOrd>(
+    slice: &[T],
+    key: impl FnMut(&T) -> K,
+) -> Result<&[T], UnsortedAt<K>> {
+    match first_unsorted(slice, key) {
+        None => Ok(slice),
+        Some(error) => Err(error.observed("assert_sorted_by_key")),
+    }
+}
+
+fn first_unsorted<T, K: PartialOrd>(
+    slice: &[T],
+    mut key: impl FnMut(&T) -> K,
+) -> Option<UnsortedAt<K>> {
+    let mut items = slice.iter().map(&mut key).enumerate();
+    let (_, mut prev) = items.next()?;
+    for (index, next) in items {
+        // Incomparable keys, such as NaN, count as out of order.
+        if !matches!(
+            prev.partial_cmp(&next),
+            Some(Ordering::Less | Ordering::Equal)
+        ) {
+            return Some(UnsortedAt {
+                index,
+                prev,
+                next,
+                provenance: "",
+            });
+        }
+        prev = next;
+    }
+    None
+}
+
+/// The first pair of neighbours in a slice that are out of order.
+#[derive(Clone, Copy)]
+pub struct UnsortedAt<T> {
+    /// The index of `next`.
+    pub(crate) index: usize,
+    pub(crate) prev: T,
+    pub(crate) next: T,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl<T> UnsortedAt<T> {
+    fn observed(mut self, provenance: &'static str) -> Self {
+        hook::observe("unsorted", code::UNSORTED, provenance);
+        self.provenance = provenance;
+        self
+    }
+
+    /// The index of the first element that is smaller than the one
+    /// before it.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn prev(&self) -> &T {
+        &self.prev
+    }
+
+    pub fn next(&self) -> &T {
+        &self.next
+    }
+
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_sorted"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl<T: PartialEq> PartialEq for UnsortedAt<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.prev == other.prev && self.next == other.next
+    }
+}
+
+impl<T: Eq> Eq for UnsortedAt<T> {}
+
+impl<T: Debug> Debug for UnsortedAt<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("UnsortedAt")
+            .field("index", &self.index)
+            .field("prev", &self.prev)
+            .field("next", &self.next)
+            .finish()
+    }
+}
+
+impl<T: Display> Display for UnsortedAt<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(
+            f,
+            "Not sorted at index {}: {} comes before {}",
+            self.index, self.prev, self.next
+        )
+    }
+}
+
+impl<T: Debug + Display> core::error::Error for UnsortedAt<T> {}
+
+impl<T: Display> Fields for UnsortedAt<T> {
+    fn kind(&self) -> &'static str {
+        "unsorted"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("index", &self.index);
+        visit("prev", &self.prev);
+        visit("next", &self.next);
+    }
+}
+
+impl<T> ErrorCode for UnsortedAt<T> {
+    fn code(&self) -> u32 {
+        code::UNSORTED
+    }
+}
+
+/// Assert that no value appears in `slice` twice, returning it.
+///
+/// If there are several duplicates, the error is about the one whose
+/// second occurrence comes first.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_unique;
+///
+/// assert!(assert_unique(&["id", "name"]).is_ok());
+/// assert_eq!(
+///     assert_unique(&[7, 3, 5, 3, 7]).unwrap_err().to_string(),
+///     "Value 3 appears at both index 1 and 3"
+/// );
+/// ```
+pub fn assert_unique<T: Ord + Clone>(slice: &[T]) -> Result<&[T], Duplicate<T>> {
+    let mut indices: Vec<usize> = (0..slice.len()).collect();
+    // Stable, so equal values stay in the order they appear in.
+    indices.sort_by(|&a, &b| slice[a].cmp(&slice[b]));
+    let duplicate = indices
+        .windows(2)
+        .filter(|pair| slice[pair[0]] == slice[pair[1]])
+        .min_by_key(|pair| pair[1]);
+    match duplicate {
+        None => Ok(slice),
+        Some(pair) => {
+            hook::observe("duplicate", code::DUPLICATE, "assert_unique");
+            Err(Duplicate {
+                value: slice[pair[0]].clone(),
+                first_index: pair[0],
+                second_index: pair[1],
+                provenance: "assert_unique",
+            })
+        }
+    }
+}
+
+/// The same value appeared twice where every value had to be unique.
+#[derive(Clone, Copy)]
+pub struct Duplicate<T> {
+    pub(crate) value: T,
+    pub(crate) first_index: usize,
+    pub(crate) second_index: usize,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl<T> Duplicate<T> {
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    pub fn first_index(&self) -> usize {
+        self.first_index
+    }
+
+    pub fn second_index(&self) -> usize {
+        self.second_index
+    }
+
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_unique"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl<T: PartialEq> PartialEq for Duplicate<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+            && self.first_index == other.first_index
+            && self.second_index == other.second_index
+    }
+}
+
+impl<T: Eq> Eq for Duplicate<T> {}
+
+impl<T: Debug> Debug for Duplicate<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Duplicate")
+            .field("value", &self.value)
+            .field("first_index", &self.first_index)
+            .field("second_index", &self.second_index)
+            .finish()
+    }
+}
+
+impl<T: Display> Display for Duplicate<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(
+            f,
+            "Value {} appears at both index {} and {}",
+            self.value, self.first_index, self.second_index
+        )
+    }
+}
+
+impl<T: Debug + Display> core::error::Error for Duplicate<T> {}
+
+impl<T: Display> Fields for Duplicate<T> {
+    fn kind(&self) -> &'static str {
+        "duplicate"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("value", &self.value);
+        visit("first_index", &self.first_index);
+        visit("second_index", &self.second_index);
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(format!(
+            "remove one of the two occurrences of {}",
+            self.value
+        ))
+    }
+}
+
+impl<T> ErrorCode for Duplicate<T> {
+    fn code(&self) -> u32 {
+        code::DUPLICATE
+    }
+}
+
+/// Assert that two slices are equal, reporting where they first
+/// differ rather than just that they do.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_slices_eq;
+///
+/// assert!(assert_slices_eq(b"GIF89a", b"GIF89a").is_ok());
+/// assert_eq!(
+///     assert_slices_eq(b"GIF89a", b"GIF87a").unwrap_err().to_string(),
+///     "Slices differ at index 4: expected 57, but got 55"
+/// );
+/// assert_eq!(
+///     assert_slices_eq(&[1, 2, 3], &[1, 2]).unwrap_err().to_string(),
+///     "Expected 3 elements, but got 2, of which all are equal"
+/// );
+/// ```
+pub fn assert_slices_eq<T: PartialEq + Clone>(
+    expected: &[T],
+    actual: &[T],
+) -> Result<(), SliceMismatch<T>> {
+    let index = expected
+        .iter()
+        .zip(actual)
+        .position(|(expected, actual)| expected != actual)
+        .unwrap_or(expected.len().min(actual.len()));
+    if index == expected.len() && index == actual.len() {
+        return Ok(());
+    }
+    hook::observe("slice_mismatch", code::SLICE_MISMATCH, "assert_slices_eq");
+    Err(SliceMismatch {
+        index,
+        expected: expected.get(index).cloned(),
+        actual: actual.get(index).cloned(),
+        expected_len: expected.len(),
+        actual_len: actual.len(),
+        provenance: "assert_slices_eq",
+    })
+}
+
+/// Two slices that had to be equal were not.
+#[derive(Clone, Copy)]
+pub struct SliceMismatch<T> {
+    /// The first index at which the slices differ, or the length of
+    /// the shorter one, if it is a prefix of the longer one.
+    pub(crate) index: usize,
+    /// The expected element at `index`, if there is one.
+    pub(crate) expected: Option<T>,
+    /// The actual element at `index`, if there is one.
+    pub(crate) actual: Option<T>,
+    pub(crate) expected_len: usize,
+    pub(crate) actual_len: usize,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl<T> SliceMismatch<T> {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn expected(&self) -> Option<&T> {
+        self.expected.as_ref()
+    }
+
+    pub fn actual(&self) -> Option<&T> {
+        self.actual.as_ref()
+    }
+
+    /// The lengths of the expected and actual slice, if they differ.
+    pub fn lengths(&self) -> Option<(usize, usize)> {
+        (self.expected_len != self.actual_len).then_some((self.expected_len, self.actual_len))
+    }
+
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_slices_eq"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl<T: PartialEq> PartialEq for SliceMismatch<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+            && self.expected == other.expected
+            && self.actual == other.actual
+            && self.expected_len == other.expected_len
+            && self.actual_len == other.actual_len
+    }
+}
+
+impl<T: Eq> Eq for SliceMismatch<T> {}
+
+impl<T: Debug> Debug for SliceMismatch<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SliceMismatch")
+            .field("index", &self.index)
+            .field("expected", &self.expected)
+            .field("actual", &self.actual)
+            .field("expected_len", &self.expected_len)
+            .field("actual_len", &self.actual_len)
+            .finish()
+    }
+}
+
+impl<T: Display> Display for SliceMismatch<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        if let (Some(expected), Some(actual)) = (&self.expected, &self.actual) {
+            write!(
+                f,
+                "Slices differ at index {}: expected {}, but got {}",
+                self.index, expected, actual
+            )?;
+            if let Some((expected_len, actual_len)) = self.lengths() {
+                write!(f, ", and have lengths {} and {}", expected_len, actual_len)?;
+            }
+            Ok(())
+        } else {
+            write!(
+                f,
+                "Expected {} {}, but got {}, of which all are equal",
+                self.expected_len,
+                elements(self.expected_len),
+                self.actual_len
+            )
+        }
+    }
+}
+
+impl<T: Debug + Display> core::error::Error for SliceMismatch<T> {}
+
+impl<T: Display> Fields for SliceMismatch<T> {
+    fn kind(&self) -> &'static str {
+        "slice_mismatch"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("index", &self.index);
+        if let Some(expected) = &self.expected {
+            visit("expected", expected);
+        }
+        if let Some(actual) = &self.actual {
+            visit("actual", actual);
+        }
+        visit("expected_len", &self.expected_len);
+        visit("actual_len", &self.actual_len);
+    }
+}
+
+impl<T> ErrorCode for SliceMismatch<T> {
+    fn code(&self) -> u32 {
+        code::SLICE_MISMATCH
+    }
+}
+
+fn elements(count: usize) -> &'static str {
+    if count == 1 {
+        "element"
+    } else {
+        "elements"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{assert_len, assert_len_in, assert_slices_eq, assert_sorted, assert_unique};
+    use std::collections::BTreeMap;
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn lengths() {
+        let map: BTreeMap<u8, u8> = [(1, 1)].into_iter().collect();
+        assert_eq!(assert_len(&map, 1), Ok(1));
+        assert_eq!(
+            assert_len("ab".chars().collect::<Vec<_>>(), 1)
+                .unwrap_err()
+                .to_string(),
+            "Expected 1 element, but got 2"
+        );
+        assert_eq!(
+            assert_len_in([1], 2..).unwrap_err().to_string(),
+            "Expected 2.. elements, but got 1"
+        );
+        assert_eq!(
+            assert_len_in([1, 2], ..=1).unwrap_err().to_string(),
+            "Expected ..=1 elements, but got 2"
+        );
+    }
+
+    #[test]
+    fn unsorted() {
+        assert!(assert_sorted::<u8>(&[]).is_ok());
+        let error = assert_sorted(&[1.0, f64::NAN, 2.0]).unwrap_err();
+        assert_eq!(error.index(), 1);
+        assert_eq!(error.provenance(), "assert_sorted");
+    }
+
+    #[test]
+    fn duplicates() {
+        assert!(assert_unique::<u8>(&[]).is_ok());
+        let error = assert_unique(&[9, 1, 2, 1, 9, 2]).unwrap_err();
+        assert_eq!(
+            (error.value(), error.first_index(), error.second_index()),
+            (&1, 1, 3)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn first_difference() {
+        let error = assert_slices_eq(&[1, 2, 3], &[1, 5]).unwrap_err();
+        assert_eq!((error.index(), error.lengths()), (1, Some((3, 2))));
+        assert_eq!(
+            error.to_string(),
+            "Slices differ at index 1: expected 2, but got 5, and have lengths 3 and 2"
+        );
+        let error = assert_slices_eq(&[1], &[1, 2]).unwrap_err();
+        assert_eq!(
+            (error.index(), error.expected(), error.actual()),
+            (1, None, Some(&2))
+        );
+    }
+}
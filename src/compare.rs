@@ -0,0 +1,487 @@
+//! Assertions against a single bound, which read better than a range
+//! with a made-up other end.
+
+use crate::{code, hook, ErrorCode, Fields, NotComparable};
+use alloc::format;
+use alloc::string::String;
+use core::cmp::Ordering;
+use core::fmt::{Debug, Display};
+
+/// Assert that `value` is at most `max`.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_le;
+///
+/// assert_eq!(assert_le(&16, &16), Ok(16));
+/// assert_eq!(
+///     assert_le(&17, &16).unwrap_err().to_string(),
+///     "Value 17 is too large (must be at most 16)"
+/// );
+/// assert_eq!(
+///     assert_le(&f64::NAN, &16.0).unwrap_err().to_string(),
+///     "Value NaN cannot be compared with the bound 16"
+/// );
+/// ```
+pub fn assert_le<T: PartialOrd + Copy>(value: &T, max: &T) -> Result<T, BoundGoof<T>> {
+    let limit = Limit::max(*max, false);
+    match value.partial_cmp(max) {
+        Some(Ordering::Greater) => Err(too_large(*value, limit, "assert_le").into()),
+        Some(_) => Ok(*value),
+        None => Err(not_comparable(*value, limit, "assert_le").into()),
+    }
+}
+
+/// Assert that `value` is less than `max`.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_lt;
+///
+/// assert_eq!(
+///     assert_lt(&16, &16).unwrap_err().to_string(),
+///     "Value 16 is too large (must be less than 16)"
+/// );
+/// ```
+pub fn assert_lt<T: PartialOrd + Copy>(value: &T, max: &T) -> Result<T, BoundGoof<T>> {
+    let limit = Limit::max(*max, true);
+    match value.partial_cmp(max) {
+        Some(Ordering::Less) => Ok(*value),
+        Some(_) => Err(too_large(*value, limit, "assert_lt").into()),
+        None => Err(not_comparable(*value, limit, "assert_lt").into()),
+    }
+}
+
+/// Assert that `value` is at least `min`.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_ge;
+///
+/// assert_eq!(assert_ge(&1, &1), Ok(1));
+/// assert_eq!(
+///     assert_ge(&0, &1).unwrap_err().to_string(),
+///     "Value 0 is too small (must be at least 1)"
+/// );
+/// ```
+pub fn assert_ge<T: PartialOrd + Copy>(value: &T, min: &T) -> Result<T, BoundGoof<T>> {
+    let limit = Limit::min(*min, false);
+    match value.partial_cmp(min) {
+        Some(Ordering::Less) => Err(too_small(*value, limit, "assert_ge").into()),
+        Some(_) => Ok(*value),
+        None => Err(not_comparable(*value, limit, "assert_ge").into()),
+    }
+}
+
+/// Assert that `value` is greater than `min`.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_gt;
+///
+/// assert_eq!(assert_gt(&0.5, &0.0), Ok(0.5));
+/// assert_eq!(
+///     assert_gt(&0.0, &0.0).unwrap_err().to_string(),
+///     "Value 0 is too small (must be greater than 0)"
+/// );
+/// ```
+pub fn assert_gt<T: PartialOrd + Copy>(value: &T, min: &T) -> Result<T, BoundGoof<T>> {
+    let limit = Limit::min(*min, true);
+    match value.partial_cmp(min) {
+        Some(Ordering::Greater) => Ok(*value),
+        Some(_) => Err(too_small(*value, limit, "assert_gt").into()),
+        None => Err(not_comparable(*value, limit, "assert_gt").into()),
+    }
+}
+
+fn too_large<T>(actual: T, limit: Limit<T>, provenance: &'static str) -> TooLarge<T> {
+    hook::observe("too_large", code::TOO_LARGE, provenance);
+    TooLarge {
+        max: limit.bound,
+        actual,
+        strict: limit.strict,
+        provenance,
+    }
+}
+
+fn too_small<T>(actual: T, limit: Limit<T>, provenance: &'static str) -> TooSmall<T> {
+    hook::observe("too_small", code::TOO_SMALL, provenance);
+    TooSmall {
+        min: limit.bound,
+        actual,
+        strict: limit.strict,
+        provenance,
+    }
+}
+
+fn not_comparable<T>(value: T, limit: Limit<T>, provenance: &'static str) -> NotComparable<T> {
+    hook::observe("not_comparable", code::NOT_COMPARABLE, provenance);
+    NotComparable::beyond(value, limit, provenance)
+}
+
+/// The ways [`assert_le`], [`assert_lt`], [`assert_ge`] and
+/// [`assert_gt`] can fail.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BoundGoof<T: PartialOrd + Copy> {
+    TooLarge(TooLarge<T>),
+    TooSmall(TooSmall<T>),
+    NotComparable(NotComparable<T>),
+}
+
+impl<T: PartialOrd + Copy> From<TooLarge<T>> for BoundGoof<T> {
+    fn from(error: TooLarge<T>) -> Self {
+        Self::TooLarge(error)
+    }
+}
+
+impl<T: PartialOrd + Copy> From<TooSmall<T>> for BoundGoof<T> {
+    fn from(error: TooSmall<T>) -> Self {
+        Self::TooSmall(error)
+    }
+}
+
+impl<T: PartialOrd + Copy> From<NotComparable<T>> for BoundGoof<T> {
+    fn from(error: NotComparable<T>) -> Self {
+        Self::NotComparable(error)
+    }
+}
+
+impl<T: PartialOrd + Copy + Display> Display for BoundGoof<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            BoundGoof::TooLarge(error) => Display::fmt(error, f),
+            BoundGoof::TooSmall(error) => Display::fmt(error, f),
+            BoundGoof::NotComparable(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl<T: PartialOrd + Copy + Debug + Display> core::error::Error for BoundGoof<T> {}
+
+impl<T: PartialOrd + Copy + Display> Fields for BoundGoof<T> {
+    fn kind(&self) -> &'static str {
+        match self {
+            BoundGoof::TooLarge(error) => error.kind(),
+            BoundGoof::TooSmall(error) => error.kind(),
+            BoundGoof::NotComparable(error) => error.kind(),
+        }
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        match self {
+            BoundGoof::TooLarge(error) => error.for_each_field(visit),
+            BoundGoof::TooSmall(error) => error.for_each_field(visit),
+            BoundGoof::NotComparable(error) => error.for_each_field(visit),
+        }
+    }
+
+    fn help(&self) -> Option<String> {
+        match self {
+            BoundGoof::TooLarge(error) => error.help(),
+            BoundGoof::TooSmall(error) => error.help(),
+            BoundGoof::NotComparable(error) => error.help(),
+        }
+    }
+}
+
+impl<T: PartialOrd + Copy> ErrorCode for BoundGoof<T> {
+    fn code(&self) -> u32 {
+        match self {
+            BoundGoof::TooLarge(error) => error.code(),
+            BoundGoof::TooSmall(error) => error.code(),
+            BoundGoof::NotComparable(error) => error.code(),
+        }
+    }
+}
+
+/// A value was above the largest one allowed.
+#[derive(Clone, Copy)]
+pub struct TooLarge<T> {
+    pub(crate) max: T,
+    pub(crate) actual: T,
+    /// Whether `max` itself was not allowed either.
+    pub(crate) strict: bool,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl<T> TooLarge<T> {
+    /// The largest value allowed, or the one all values had to be
+    /// less than if [`TooLarge::is_strict`].
+    pub fn max(&self) -> &T {
+        &self.max
+    }
+
+    /// The value that was too large.
+    pub fn actual(&self) -> &T {
+        &self.actual
+    }
+
+    /// Whether `max` itself was not allowed either.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_le"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl<T: PartialEq> PartialEq for TooLarge<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.max == other.max && self.actual == other.actual && self.strict == other.strict
+    }
+}
+
+impl<T: Eq> Eq for TooLarge<T> {}
+
+impl<T: Debug> Debug for TooLarge<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TooLarge")
+            .field("max", &self.max)
+            .field("actual", &self.actual)
+            .field("strict", &self.strict)
+            .finish()
+    }
+}
+
+impl<T: Display> Display for TooLarge<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(
+            f,
+            "Value {} is too large (must be {})",
+            self.actual,
+            Limit::max(&self.max, self.strict)
+        )
+    }
+}
+
+impl<T: Debug + Display> core::error::Error for TooLarge<T> {}
+
+impl<T: Display> Fields for TooLarge<T> {
+    fn kind(&self) -> &'static str {
+        "too_large"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("max", &self.max);
+        visit("actual", &self.actual);
+        visit("strict", &self.strict);
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(format!(
+            "use a value {}",
+            Limit::max(&self.max, self.strict)
+        ))
+    }
+}
+
+impl<T> ErrorCode for TooLarge<T> {
+    fn code(&self) -> u32 {
+        code::TOO_LARGE
+    }
+}
+
+/// A value was below the smallest one allowed.
+#[derive(Clone, Copy)]
+pub struct TooSmall<T> {
+    pub(crate) min: T,
+    pub(crate) actual: T,
+    /// Whether `min` itself was not allowed either.
+    pub(crate) strict: bool,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl<T> TooSmall<T> {
+    /// The smallest value allowed, or the one all values had to be
+    /// greater than if [`TooSmall::is_strict`].
+    pub fn min(&self) -> &T {
+        &self.min
+    }
+
+    /// The value that was too small.
+    pub fn actual(&self) -> &T {
+        &self.actual
+    }
+
+    /// Whether `min` itself was not allowed either.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_ge"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl<T: PartialEq> PartialEq for TooSmall<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.min == other.min && self.actual == other.actual && self.strict == other.strict
+    }
+}
+
+impl<T: Eq> Eq for TooSmall<T> {}
+
+impl<T: Debug> Debug for TooSmall<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TooSmall")
+            .field("min", &self.min)
+            .field("actual", &self.actual)
+            .field("strict", &self.strict)
+            .finish()
+    }
+}
+
+impl<T: Display> Display for TooSmall<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(
+            f,
+            "Value {} is too small (must be {})",
+            self.actual,
+            Limit::min(&self.min, self.strict)
+        )
+    }
+}
+
+impl<T: Debug + Display> core::error::Error for TooSmall<T> {}
+
+impl<T: Display> Fields for TooSmall<T> {
+    fn kind(&self) -> &'static str {
+        "too_small"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("min", &self.min);
+        visit("actual", &self.actual);
+        visit("strict", &self.strict);
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(format!(
+            "use a value {}",
+            Limit::min(&self.min, self.strict)
+        ))
+    }
+}
+
+impl<T> ErrorCode for TooSmall<T> {
+    fn code(&self) -> u32 {
+        code::TOO_SMALL
+    }
+}
+
+/// A single bound on a value, which renders as e.g. `at most 16`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Limit<T> {
+    pub(crate) bound: T,
+    /// Whether `bound` is the largest value rather than the smallest.
+    pub(crate) upper: bool,
+    /// Whether `bound` itself is not allowed.
+    pub(crate) strict: bool,
+}
+
+impl<T> Limit<T> {
+    pub(crate) fn max(bound: T, strict: bool) -> Self {
+        Self {
+            bound,
+            upper: true,
+            strict,
+        }
+    }
+
+    pub(crate) fn min(bound: T, strict: bool) -> Self {
+        Self {
+            bound,
+            upper: false,
+            strict,
+        }
+    }
+}
+
+impl<T: Display> Display for Limit<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let relation = match (self.upper, self.strict) {
+            (true, false) => "at most",
+            (true, true) => "less than",
+            (false, false) => "at least",
+            (false, true) => "greater than",
+        };
+        write!(f, "{} {}", relation, self.bound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BoundGoof;
+    use crate::{ErrorCode, Fields};
+
+    #[test]
+    fn help_and_provenance() {
+        let error = super::assert_lt(&9_u8, &4).unwrap_err();
+        assert_eq!(error.help().as_deref(), Some("use a value less than 4"));
+        let BoundGoof::TooLarge(error) = error else {
+            panic!("{:?}", error)
+        };
+        assert_eq!(error.provenance(), "assert_lt");
+        assert!(matches!(
+            super::assert_gt(&1, &2),
+            Err(BoundGoof::TooSmall(error)) if error.is_strict()
+        ));
+        assert!(matches!(
+            super::assert_ge(&1, &2),
+            Err(BoundGoof::TooSmall(error)) if !error.is_strict()
+        ));
+    }
+
+    #[test]
+    fn fields_say_whether_strict() {
+        let fields = |error: &BoundGoof<u8>| {
+            let mut fields = alloc::vec::Vec::new();
+            error.for_each_field(&mut |name, value| fields.push((name, value.to_string())));
+            fields
+        };
+        assert_eq!(
+            fields(&super::assert_lt(&9_u8, &4).unwrap_err()),
+            [
+                ("max", "4".into()),
+                ("actual", "9".into()),
+                ("strict", "true".into())
+            ]
+        );
+        assert_eq!(
+            fields(&super::assert_ge(&1_u8, &4).unwrap_err()),
+            [
+                ("min", "4".into()),
+                ("actual", "1".into()),
+                ("strict", "false".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn nan_is_not_comparable() {
+        let error = super::assert_ge(&f32::NAN, &1.0).unwrap_err();
+        assert_eq!(error.code(), crate::code::NOT_COMPARABLE);
+        assert_eq!(error.help().as_deref(), Some("use a number at least 1"));
+        assert!(matches!(
+            super::assert_le(&1.0, &f64::NAN),
+            Err(BoundGoof::NotComparable(_))
+        ));
+        assert_eq!(
+            super::assert_gt(&f64::NAN, &0.0),
+            super::assert_gt(&f64::NAN, &0.0)
+        );
+    }
+}
@@ -0,0 +1,223 @@
+//! Validating loaded configuration, reporting every problem at once
+//! instead of stopping at the first.
+
+use crate::schema::MissingField;
+use crate::{code, hook, Aggregate, Child, ErrorCode, Fields, Path};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt::{Debug, Display};
+
+/// One problem with one key of the configuration.
+#[derive(Debug)]
+pub struct Problem {
+    pub path: Path,
+    /// As in [`Fields::kind`] of `error`.
+    pub kind: &'static str,
+    pub error: Box<dyn Error + Send + Sync + 'static>,
+}
+
+/// Every problem found in a configuration.
+///
+/// The helpers check one key each, record a problem if the check
+/// fails, and return the value if it passes, so that validation can
+/// carry on past the first problem.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::config::ConfigGoof;
+///
+/// let mut config = ConfigGoof::new("app.toml");
+/// let port = config.key_in_range("port", 0_u32, &(1..65535));
+/// let level = config.key_one_of("level", "loud", &["debug", "info"]);
+/// let name: Option<&str> = config.require_key("name", None);
+/// assert_eq!((port, level, name), (None, None, None));
+///
+/// let error = config.finish().unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "3 problems in app.toml:\n\
+///      \x20   port: Value 0 is outside the allowed range (1, 65535] (must be greater than 1)\n\
+///      \x20   level: The value loud is not known, because it's not one of [debug, info]\n\
+///      \x20   name: Missing field `name` in app.toml"
+/// );
+/// ```
+#[derive(Debug)]
+pub struct ConfigGoof {
+    pub(crate) source: &'static str,
+    problems: Vec<Problem>,
+    /// How to get at the aggregate inside each of `problems`, which
+    /// was known before the error was boxed.
+    nesting: Vec<Nesting>,
+}
+
+type Nesting = for<'a> fn(&'a (dyn Error + 'static)) -> Option<&'a dyn Aggregate>;
+
+impl ConfigGoof {
+    /// Start validating the configuration loaded from `source`, e.g.
+    /// a file name.
+    pub const fn new(source: &'static str) -> Self {
+        Self {
+            source,
+            problems: Vec::new(),
+            nesting: Vec::new(),
+        }
+    }
+
+    /// Record `error` as a problem with the key at `path`.
+    pub fn push<E: Error + Fields + Send + Sync + 'static>(
+        &mut self,
+        path: impl Into<Path>,
+        error: E,
+    ) -> &mut Self {
+        self.problems.push(Problem {
+            path: path.into(),
+            kind: error.kind(),
+            error: Box::new(error),
+        });
+        self.nesting
+            .push(|error| error.downcast_ref::<E>()?.as_aggregate());
+        self
+    }
+
+    /// Check that the key `key` was set.
+    pub fn require_key<T>(&mut self, key: &'static str, value: Option<T>) -> Option<T> {
+        if value.is_none() {
+            self.push(key, MissingField::new(self.source, key));
+        }
+        value
+    }
+
+    /// Check the value of `key` with [`crate::assert_in`].
+    pub fn key_in_range<T>(
+        &mut self,
+        key: impl Into<Path>,
+        value: T,
+        range: &core::ops::Range<T>,
+    ) -> Option<T>
+    where
+        T: Ord + Copy + Debug + Display + Send + Sync + 'static,
+    {
+        crate::assert_in(&value, range)
+            .map_err(|error| self.push(key, error))
+            .ok()
+    }
+
+    /// Check the value of `key` with [`crate::assert_known_enum`].
+    pub fn key_one_of<T>(
+        &mut self,
+        key: impl Into<Path>,
+        value: T,
+        knowns: &'static [T],
+    ) -> Option<T>
+    where
+        T: Eq + Debug + Display + Send + Sync + 'static,
+    {
+        crate::assert_known_enum(knowns, value)
+            .map_err(|error| self.push(key, error))
+            .ok()
+    }
+
+    pub fn problems(&self) -> &[Problem] {
+        &self.problems
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    /// `Ok` if no problems were found, and all of them otherwise.
+    pub fn finish(self) -> Result<(), Self> {
+        if self.problems.is_empty() {
+            Ok(())
+        } else {
+            hook::observe("config", code::CONFIG, "ConfigGoof::finish");
+            Err(self)
+        }
+    }
+}
+
+impl Display for ConfigGoof {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        self.fmt_summary(f)?;
+        f.write_str(":")?;
+        for problem in &self.problems {
+            write!(f, "\n    {}: {}", problem.path, problem.error)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ConfigGoof {}
+
+impl Aggregate for ConfigGoof {
+    fn fmt_summary(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.problems.len() {
+            1 => write!(f, "1 problem in {}", self.source),
+            count => write!(f, "{} problems in {}", count, self.source),
+        }
+    }
+
+    fn for_each_child<'a>(&'a self, visit: &mut dyn FnMut(Option<&dyn Display>, Child<'a>)) {
+        for (problem, nesting) in self.problems.iter().zip(&self.nesting) {
+            let error: &(dyn Error + 'static) = &*problem.error;
+            visit(
+                Some(&problem.path),
+                Child {
+                    error,
+                    aggregate: nesting(error),
+                },
+            );
+        }
+    }
+}
+
+impl Fields for ConfigGoof {
+    fn kind(&self) -> &'static str {
+        "config"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("source", &self.source);
+        visit("count", &self.problems.len());
+    }
+
+    fn as_aggregate(&self) -> Option<&dyn Aggregate> {
+        Some(self)
+    }
+}
+
+impl ErrorCode for ConfigGoof {
+    fn code(&self) -> u32 {
+        code::CONFIG
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConfigGoof;
+    use crate::Path;
+
+    #[test]
+    fn valid_config_finishes() {
+        let mut config = ConfigGoof::new("app.toml");
+        assert_eq!(config.key_in_range("port", 80, &(1..65535)), Some(80));
+        assert_eq!(config.require_key("name", Some("app")), Some("app"));
+        assert!(config.finish().is_ok());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn nested_paths() {
+        let mut config = ConfigGoof::new("app.toml");
+        let path = Path::new().field("servers").index(1).field("port");
+        config.key_in_range(path, 0, &(1..65535));
+        let error = config.finish().unwrap_err();
+        assert_eq!(error.problems()[0].kind, "outside");
+        assert!(error
+            .to_string()
+            .starts_with("1 problem in app.toml:\n    servers[1].port: "));
+    }
+}
@@ -0,0 +1,264 @@
+//! Assertions that can run at compile time, on `usize`, the type of
+//! const generic lengths and of most configuration tables.
+//!
+//! They return the same errors as their generic namesakes, but the
+//! hook set with `set_hook` does not hear about them, because it
+//! cannot be called in a `const` context.
+//!
+//! # Examples
+//! ```rust
+//! use goof::consts;
+//!
+//! struct Ring<const N: usize>([u8; N]);
+//!
+//! impl<const N: usize> Ring<N> {
+//!     const CHECKED: () = assert!(
+//!         consts::assert_multiple_of(N, 8).is_ok(),
+//!         "the ring must hold whole words"
+//!     );
+//!
+//!     fn new() -> Self {
+//!         let () = Self::CHECKED;
+//!         Ring([0; N])
+//!     }
+//! }
+//!
+//! const WORKERS: usize = match consts::assert_in(8, 0, 16) {
+//!     Ok(workers) => workers,
+//!     Err(_) => panic!("between 1 and 16 workers"),
+//! };
+//!
+//! let _ = Ring::<64>::new();
+//! assert_eq!(WORKERS, 8);
+//! assert_eq!(
+//!     consts::assert_eq(32, 0).unwrap_err(),
+//!     goof::assert_eq(&32, &0).unwrap_err()
+//! );
+//! ```
+
+use crate::{Exceeded, Interval, Mismatch, NotMultipleOf, Outside, Zero};
+
+/// Like [`crate::assert_eq`].
+pub const fn assert_eq(expected: usize, actual: usize) -> Result<usize, Mismatch<usize>> {
+    if expected == actual {
+        Ok(expected)
+    } else {
+        Err(Mismatch {
+            expected,
+            actual,
+            provenance: "consts::assert_eq",
+        })
+    }
+}
+
+/// Like [`crate::assert_in`], with `start..end`: `value` must be
+/// greater than `start` and at most `end`.
+pub const fn assert_in(value: usize, start: usize, end: usize) -> Result<usize, Outside<usize>> {
+    if value > start && value <= end {
+        Ok(value)
+    } else {
+        Err(Outside {
+            range: start..end,
+            interval: Interval::LeftOpen,
+            value,
+            provenance: "consts::assert_in",
+        })
+    }
+}
+
+/// Like [`crate::assert_nonzero`].
+pub const fn assert_nonzero(value: usize) -> Result<usize, Zero> {
+    if value != 0 {
+        Ok(value)
+    } else {
+        Err(Zero {
+            provenance: "consts::assert_nonzero",
+        })
+    }
+}
+
+/// Like [`crate::assert_multiple_of`]; only zero is a multiple of
+/// zero.
+pub const fn assert_multiple_of(
+    value: usize,
+    factor: usize,
+) -> Result<usize, NotMultipleOf<usize>> {
+    let multiple = match value.checked_rem(factor) {
+        Some(remainder) => remainder == 0,
+        None => value == 0,
+    };
+    if multiple {
+        Ok(value)
+    } else {
+        Err(NotMultipleOf {
+            value,
+            factor,
+            provenance: "consts::assert_multiple_of",
+        })
+    }
+}
+
+/// Like [`crate::assert_capacity`].
+pub const fn assert_capacity(requested: usize, limit: usize) -> Result<usize, Exceeded<usize>> {
+    if requested <= limit {
+        Ok(requested)
+    } else {
+        Err(Exceeded {
+            requested,
+            limit,
+            over: requested - limit,
+            provenance: "consts::assert_capacity",
+        })
+    }
+}
+
+/// Fail the build unless two integer constant expressions are equal,
+/// with the same message as a [`crate::Mismatch`] at run time.
+///
+/// Both operands must be integers of at most 64 bits or an `i128`, so
+/// that they compare exactly as `i128`s.
+///
+/// # Examples
+/// ```rust
+/// const WORD: usize = 8;
+/// goof::static_goof!(WORD, core::mem::size_of::<u64>());
+/// ```
+///
+/// ```rust,compile_fail
+/// // error[E0080]: evaluation panicked: Expected 8, but got 4
+/// goof::static_goof!(8, core::mem::size_of::<u32>());
+/// ```
+///
+/// ```rust,compile_fail
+/// // error[E0277]: the trait bound `{float}: __Integer` is not satisfied
+/// goof::static_goof!(8.0, 8);
+/// ```
+///
+/// ```rust,compile_fail
+/// // error[E0277]: the trait bound `char: __Integer` is not satisfied
+/// goof::static_goof!('8', 56);
+/// ```
+#[macro_export]
+macro_rules! static_goof {
+    ($expected:expr, $actual:expr $(,)?) => {
+        const _: () = {
+            let (expected, actual) = (
+                $crate::consts::__integer($expected) as i128,
+                $crate::consts::__integer($actual) as i128,
+            );
+            if expected != actual {
+                ::core::panic!("{}", $crate::consts::__mismatch(expected, actual).as_str());
+            }
+        };
+    };
+}
+
+/// The types that [`static_goof!`] converts to an `i128` losslessly.
+#[doc(hidden)]
+pub trait __Integer: Copy {}
+
+macro_rules! integer {
+    ($($integer:ty)*) => {
+        $(impl __Integer for $integer {})*
+    };
+}
+
+integer!(u8 u16 u32 u64 usize i8 i16 i32 i64 i128 isize);
+
+/// Rejects the operands of [`static_goof!`] that are not integers, which
+/// `as` would convert as well.
+#[doc(hidden)]
+pub const fn __integer<T: __Integer>(value: T) -> T {
+    value
+}
+
+#[doc(hidden)]
+pub struct __Message {
+    bytes: [u8; 112],
+    len: usize,
+}
+
+impl __Message {
+    const fn push(mut self, text: &str) -> Self {
+        let text = text.as_bytes();
+        let mut index = 0;
+        while index < text.len() {
+            self.bytes[self.len] = text[index];
+            self.len += 1;
+            index += 1;
+        }
+        self
+    }
+
+    const fn push_int(mut self, value: i128) -> Self {
+        if value < 0 {
+            self = self.push("-");
+        }
+        let mut magnitude = value.unsigned_abs();
+        let mut digits = [0_u8; 39];
+        let mut count = 0;
+        loop {
+            digits[count] = b'0' + (magnitude % 10) as u8;
+            count += 1;
+            magnitude /= 10;
+            if magnitude == 0 {
+                break;
+            }
+        }
+        while count > 0 {
+            count -= 1;
+            self.bytes[self.len] = digits[count];
+            self.len += 1;
+        }
+        self
+    }
+
+    pub const fn as_str(&self) -> &str {
+        match core::str::from_utf8(self.bytes.split_at(self.len).0) {
+            Ok(message) => message,
+            Err(_) => "",
+        }
+    }
+}
+
+/// The `Display` of a [`Mismatch`], written in a `const` context.
+#[doc(hidden)]
+pub const fn __mismatch(expected: i128, actual: i128) -> __Message {
+    __Message {
+        bytes: [0; 112],
+        len: 0,
+    }
+    .push("Expected ")
+    .push_int(expected)
+    .push(", but got ")
+    .push_int(actual)
+}
+
+#[cfg(test)]
+mod tests {
+    const TOO_MANY: Result<usize, crate::Exceeded<usize>> = super::assert_capacity(12, 10);
+
+    #[test]
+    fn evaluated_at_compile_time() {
+        let error = TOO_MANY.unwrap_err();
+        assert_eq!(*error.over(), 2);
+        assert_eq!(error.provenance(), "consts::assert_capacity");
+        assert!(super::assert_multiple_of(0, 0).is_ok());
+        assert!(super::assert_multiple_of(3, 0).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn static_message() {
+        use alloc::string::ToString;
+
+        for (expected, actual) in [(32, 0), (-7, i128::MIN), (i128::MAX, 1)] {
+            assert_eq!(
+                super::__mismatch(expected, actual).as_str(),
+                crate::assert_eq(&expected, &actual)
+                    .unwrap_err()
+                    .to_string()
+            );
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! Declaring the error enum of a domain in a few lines.
+
+/// The `String` of `alloc`, for [`define_goofs!`] in crates that do
+/// not name it.
+#[doc(hidden)]
+pub type __String = alloc::string::String;
+
+/// Define an enum with one variant for each error of a domain, each
+/// wrapping an error of this crate and having a stable code.
+///
+/// The enum gets `Debug`, `Display`, `Error`, [`crate::Fields`],
+/// [`crate::ErrorCode`] with the given codes, [`crate::Transience`]
+/// and `From` every wrapped error, so that `?` converts them.  Since
+/// `From` is per type, no two variants can wrap the same type.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::{ErrorCode, Fields, Goof, Mismatch, Outside};
+///
+/// goof::define_goofs! {
+///     /// Everything that can be wrong with a configuration.
+///     pub enum ConfigError {
+///         /// The port is out of range.
+///         Port(Outside<u16>) = 1001,
+///         Version(Mismatch<u32>) = 1002,
+///         Other(Goof<'static>) = 1099,
+///     }
+/// }
+///
+/// fn check(port: u16, version: u32) -> Result<(), ConfigError> {
+///     goof::assert_eq(&2, &version)?;
+///     goof::assert_in(&port, &(1..65535))?;
+///     Ok(())
+/// }
+///
+/// let error = check(0, 2).unwrap_err();
+/// assert!(matches!(error, ConfigError::Port(_)));
+/// assert_eq!((error.kind(), error.code()), ("outside", 1001));
+/// assert_eq!(
+///     error.to_string(),
+///     "Value 0 is outside the allowed range (1, 65535] (must be greater than 1)"
+/// );
+/// assert_eq!(ConfigError::from(goof::goof("no such host")).code(), 1099);
+/// ```
+#[macro_export]
+macro_rules! define_goofs {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident($error:ty) = $code:literal),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug)]
+        $vis enum $name {
+            $($(#[$variant_meta])* $variant($error)),+
+        }
+
+        $(
+            impl ::core::convert::From<$error> for $name {
+                fn from(error: $error) -> Self {
+                    Self::$variant(error)
+                }
+            }
+        )+
+
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    $(Self::$variant(error) => ::core::fmt::Display::fmt(error, f)),+
+                }
+            }
+        }
+
+        impl ::core::error::Error for $name {
+            fn source(&self) -> ::core::option::Option<&(dyn ::core::error::Error + 'static)> {
+                match self {
+                    $(Self::$variant(error) => ::core::error::Error::source(error)),+
+                }
+            }
+        }
+
+        impl $crate::Fields for $name {
+            fn kind(&self) -> &'static str {
+                match self {
+                    $(Self::$variant(error) => $crate::Fields::kind(error)),+
+                }
+            }
+
+            fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn ::core::fmt::Display)) {
+                match self {
+                    $(Self::$variant(error) => $crate::Fields::for_each_field(error, visit)),+
+                }
+            }
+
+            fn help(&self) -> ::core::option::Option<$crate::__String> {
+                match self {
+                    $(Self::$variant(error) => $crate::Fields::help(error)),+
+                }
+            }
+        }
+
+        impl $crate::ErrorCode for $name {
+            fn code(&self) -> u32 {
+                match self {
+                    $(Self::$variant(_) => $code),+
+                }
+            }
+        }
+
+        impl $crate::Transience for $name {
+            fn is_transient(&self) -> bool {
+                match self {
+                    $(Self::$variant(error) => $crate::Transience::is_transient(error)),+
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Fields, Mismatch, Timeout, Transience};
+    use alloc::vec::Vec;
+    use core::time::Duration;
+
+    crate::define_goofs! {
+        enum Fetch {
+            Status(Mismatch<u16>) = 1,
+            Slow(Timeout) = 2,
+        }
+    }
+
+    #[test]
+    fn delegates_to_the_variant() {
+        let status = Fetch::from(crate::assert_eq(&200, &404).unwrap_err());
+        let mut names = Vec::new();
+        status.for_each_field(&mut |name, _| names.push(name));
+        assert_eq!(names, ["expected", "actual"]);
+        assert_eq!(status.help().as_deref(), Some("use 200 instead"));
+        assert!(!status.is_transient());
+        assert!(
+            Fetch::Slow(Timeout::new(Duration::from_secs(2), Duration::from_secs(1)))
+                .is_transient()
+        );
+    }
+}
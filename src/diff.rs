@@ -0,0 +1,76 @@
+//! Line diffs of multi-line values, for the alternate `Display` of
+//! [`crate::Mismatch`].
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Formatter;
+
+// Finding the smallest diff takes `lines * lines` memory, so past this
+// many cells every line is shown as changed instead.
+const MAX_CELLS: usize = 1 << 20;
+
+/// Writes a unified-style diff of `expected` and `actual`, line by
+/// line, without any context trimming.
+pub(crate) fn write_diff(f: &mut Formatter<'_>, expected: &str, actual: &str) -> core::fmt::Result {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    f.write_str("Expected and actual differ (-expected +actual):")?;
+
+    let (n, m) = (expected.len(), actual.len());
+    if (n + 1).saturating_mul(m + 1) > MAX_CELLS {
+        for line in &expected {
+            write!(f, "\n-{}", line)?;
+        }
+        for line in &actual {
+            write!(f, "\n+{}", line)?;
+        }
+        return Ok(());
+    }
+
+    // common[i * (m + 1) + j] is the length of the longest common
+    // subsequence of expected[i..] and actual[j..].
+    let mut common = vec![0_u32; (n + 1) * (m + 1)];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            common[i * (m + 1) + j] = if expected[i] == actual[j] {
+                common[(i + 1) * (m + 1) + j + 1] + 1
+            } else {
+                common[(i + 1) * (m + 1) + j].max(common[i * (m + 1) + j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && expected[i] == actual[j] {
+            write!(f, "\n {}", expected[i])?;
+            i += 1;
+            j += 1;
+        } else if j == m || (i < n && common[(i + 1) * (m + 1) + j] >= common[i * (m + 1) + j + 1])
+        {
+            write!(f, "\n-{}", expected[i])?;
+            i += 1;
+        } else {
+            write!(f, "\n+{}", actual[j])?;
+            j += 1;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn multi_line_mismatch_renders_a_diff() {
+        let error = crate::assert_eq(&"a\nb\nc", &"a\nx\nc\nd").unwrap_err();
+        assert_eq!(
+            format!("{:#}", error),
+            "Expected and actual differ (-expected +actual):\n a\n-b\n+x\n c\n+d"
+        );
+        assert_eq!(
+            format!("{:#}", crate::assert_eq(&1, &2).unwrap_err()),
+            "Expected 1, but got 2"
+        );
+    }
+}
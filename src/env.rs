@@ -0,0 +1,246 @@
+//! Reading configuration from environment variables.
+
+use crate::{code, hook, ErrorCode, Fields};
+use alloc::format;
+use alloc::string::String;
+use core::error::Error;
+use core::fmt::{Debug, Display};
+use core::str::FromStr;
+
+/// Read the environment variable `name`, which must be set.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::env::require_env;
+///
+/// assert_eq!(
+///     require_env("GOOF_SURELY_UNSET").unwrap_err().to_string(),
+///     "Environment variable GOOF_SURELY_UNSET is not set"
+/// );
+/// ```
+pub fn require_env(name: &'static str) -> Result<String, MissingEnv> {
+    match std::env::var(name) {
+        Ok(value) => Ok(value),
+        Err(error) => {
+            hook::observe("missing_env", code::MISSING_ENV, "require_env");
+            Err(MissingEnv {
+                name,
+                not_unicode: matches!(error, std::env::VarError::NotUnicode(_)),
+            })
+        }
+    }
+}
+
+/// Read the environment variable `name`, which must be set, and parse
+/// it as a `T`.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::env::{env_parsed, EnvGoof};
+///
+/// std::env::set_var("GOOF_EXAMPLE_PORT", "80a");
+/// let error = env_parsed::<u16>("GOOF_EXAMPLE_PORT").unwrap_err();
+/// assert_eq!(error.to_string(), r#"Environment variable GOOF_EXAMPLE_PORT has invalid value "80a""#);
+/// assert!(matches!(error, EnvGoof::Invalid(_)));
+/// ```
+pub fn env_parsed<T: FromStr>(name: &'static str) -> Result<T, EnvGoof<T::Err>> {
+    let value = require_env(name).map_err(EnvGoof::Missing)?;
+    value.parse().map_err(|error| {
+        hook::observe("invalid_env", code::INVALID_ENV, "env_parsed");
+        EnvGoof::Invalid(InvalidEnv { name, value, error })
+    })
+}
+
+/// An environment variable that had to be set was not, or was not
+/// valid Unicode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissingEnv {
+    pub(crate) name: &'static str,
+    pub(crate) not_unicode: bool,
+}
+
+impl MissingEnv {
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Whether the variable was set, but not to valid Unicode.
+    pub fn not_unicode(&self) -> bool {
+        self.not_unicode
+    }
+}
+
+impl Display for MissingEnv {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        if self.not_unicode {
+            write!(f, "Environment variable {} is not valid Unicode", self.name)
+        } else {
+            write!(f, "Environment variable {} is not set", self.name)
+        }
+    }
+}
+
+impl Error for MissingEnv {}
+
+impl Fields for MissingEnv {
+    fn kind(&self) -> &'static str {
+        "missing_env"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("name", &self.name);
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(format!("set {}", self.name))
+    }
+}
+
+impl ErrorCode for MissingEnv {
+    fn code(&self) -> u32 {
+        code::MISSING_ENV
+    }
+}
+
+/// An environment variable whose value could not be parsed.  The
+/// parse error is its [`Error::source`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidEnv<E> {
+    pub(crate) name: &'static str,
+    pub(crate) value: String,
+    pub(crate) error: E,
+}
+
+impl<E> InvalidEnv<E> {
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The value as it was set.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Why the value could not be parsed.
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+}
+
+impl<E> Display for InvalidEnv<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(
+            f,
+            "Environment variable {} has invalid value {:?}",
+            self.name, self.value
+        )
+    }
+}
+
+impl<E: Error + 'static> Error for InvalidEnv<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl<E> Fields for InvalidEnv<E> {
+    fn kind(&self) -> &'static str {
+        "invalid_env"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("name", &self.name);
+        visit("value", &self.value);
+    }
+}
+
+impl<E> ErrorCode for InvalidEnv<E> {
+    fn code(&self) -> u32 {
+        code::INVALID_ENV
+    }
+}
+
+/// Why [`env_parsed`] failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvGoof<E> {
+    Missing(MissingEnv),
+    Invalid(InvalidEnv<E>),
+}
+
+impl<E> Display for EnvGoof<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        match self {
+            EnvGoof::Missing(error) => Display::fmt(error, f),
+            EnvGoof::Invalid(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for EnvGoof<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            EnvGoof::Missing(error) => error.source(),
+            EnvGoof::Invalid(error) => error.source(),
+        }
+    }
+}
+
+impl<E> Fields for EnvGoof<E> {
+    fn kind(&self) -> &'static str {
+        match self {
+            EnvGoof::Missing(error) => error.kind(),
+            EnvGoof::Invalid(error) => error.kind(),
+        }
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        match self {
+            EnvGoof::Missing(error) => error.for_each_field(visit),
+            EnvGoof::Invalid(error) => error.for_each_field(visit),
+        }
+    }
+
+    fn help(&self) -> Option<String> {
+        match self {
+            EnvGoof::Missing(error) => error.help(),
+            EnvGoof::Invalid(error) => error.help(),
+        }
+    }
+}
+
+impl<E> ErrorCode for EnvGoof<E> {
+    fn code(&self) -> u32 {
+        match self {
+            EnvGoof::Missing(error) => error.code(),
+            EnvGoof::Invalid(error) => error.code(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{env_parsed, EnvGoof};
+    use core::error::Error;
+
+    #[test]
+    fn parses_or_keeps_the_raw_value() {
+        std::env::set_var("GOOF_TEST_PORT", "8080");
+        assert_eq!(env_parsed::<u16>("GOOF_TEST_PORT"), Ok(8080));
+        std::env::set_var("GOOF_TEST_PORT_BAD", "70000");
+        let error = env_parsed::<u16>("GOOF_TEST_PORT_BAD").unwrap_err();
+        let EnvGoof::Invalid(invalid) = &error else {
+            panic!("{:?}", error)
+        };
+        assert_eq!(invalid.value(), "70000");
+        assert!(error.source().is_some());
+        assert!(matches!(
+            env_parsed::<u16>("GOOF_TEST_UNSET"),
+            Err(EnvGoof::Missing(_))
+        ));
+    }
+}
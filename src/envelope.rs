@@ -0,0 +1,315 @@
+//! A consistent shape for reporting many errors at once over an API
+//! boundary.
+//!
+//! The rendered form is always
+//!
+//! ```json
+//! {"errors":[{"path":"...","code":0,"kind":"...","message":"...","meta":{"...":"..."}}]}
+//! ```
+//!
+//! where `code` is the [`ErrorCode`] and `kind` the [`Fields::kind`] of
+//! the error.  An entry that stands for an error repeated several times
+//! also has `"repeats":n`, and an envelope from a [`MultiError`] that
+//! stopped collecting has `"omitted":n` after the errors.  The field names are part of the public contract and will
+//! not change between minor versions.
+//!
+//! The `Display` of an [`Envelope`] writes this JSON by hand, so that
+//! it needs neither `serde` nor `std`.  With the `serde` feature the
+//! envelope is also `Serialize`, into the same schema, for services
+//! that embed it in responses of their own.
+
+use crate::{render, At, ErrorCode, Fields, MultiError};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Write};
+
+/// One error in an [`Envelope`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// Where in the request the error was found, e.g. `servers[2].port`.
+    pub path: String,
+    /// The number of the error, as reported by [`ErrorCode::code`].
+    pub code: u32,
+    /// The kind of error, as reported by [`Fields::kind`].
+    pub kind: &'static str,
+    /// The human-readable `Display` of the error.
+    pub message: String,
+    /// The structured payload, as reported by [`Fields::for_each_field`].
+    pub meta: Vec<(&'static str, String)>,
+    /// How many times the error occurred, as [`MultiError::counted`]
+    /// reports it; 1 unless it was deduplicated.
+    pub repeats: usize,
+}
+
+/// Builder for a multi-error API response.
+///
+/// # Examples
+/// ```rust
+/// use goof::{Envelope, assert_eq, assert_in};
+///
+/// let envelope = Envelope::new()
+///     .error("port", &assert_in(&0, &(1..65535)).unwrap_err())
+///     .error("version", &assert_eq(&2, &1).unwrap_err());
+///
+/// assert_eq!(envelope.len(), 2);
+/// assert!(envelope.to_string().starts_with(r#"{"errors":[{"path":"port","code":2,"kind":"outside""#));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Envelope {
+    entries: Vec<Entry>,
+    omitted: usize,
+}
+
+impl Envelope {
+    /// An envelope with no errors in it.
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            omitted: 0,
+        }
+    }
+
+    /// Add `error` found at `path`.
+    pub fn error<E: Fields + ErrorCode + Display + ?Sized>(
+        mut self,
+        path: impl Into<String>,
+        error: &E,
+    ) -> Self {
+        self.push(path, error);
+        self
+    }
+
+    /// Add `error` found at `path` without consuming the builder.
+    pub fn push<E: Fields + ErrorCode + Display + ?Sized>(
+        &mut self,
+        path: impl Into<String>,
+        error: &E,
+    ) -> &mut Self {
+        let mut meta = Vec::new();
+        error.for_each_field(&mut |name, value| meta.push((name, render(value, false))));
+        self.entries.push(Entry {
+            path: path.into(),
+            code: error.code(),
+            kind: error.kind(),
+            message: render(error, false),
+            meta,
+            repeats: 1,
+        });
+        self
+    }
+
+    /// The errors collected so far, in insertion order.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// The number of errors that were left out, e.g. past the limit of
+    /// a [`MultiError`].
+    pub fn omitted(&self) -> usize {
+        self.omitted
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Every error at its [`At::path`], in order, e.g. from
+/// [`crate::try_collect_all`], with its repeats and the number of
+/// errors that were omitted.
+///
+/// # Examples
+/// ```rust
+/// use goof::{At, Envelope, MultiError};
+///
+/// let errors = MultiError::from(vec![
+///     At::new(goof::assert_in(&0, &(1..65535)).unwrap_err()).field("port").index(2).field("servers"),
+///     At::new(goof::assert_in(&9, &(1..5)).unwrap_err()),
+/// ]);
+/// let envelope = Envelope::from(errors);
+/// let paths: Vec<_> = envelope.entries().iter().map(|entry| entry.path.as_str()).collect();
+/// assert_eq!(paths, ["servers[2].port", ""]);
+/// assert_eq!(envelope.entries()[0].kind, "outside");
+/// ```
+impl<E: Fields + ErrorCode + Display> From<MultiError<At<E>>> for Envelope {
+    fn from(errors: MultiError<At<E>>) -> Self {
+        let mut envelope = Self::new();
+        for (error, repeats) in errors.counted() {
+            envelope.push(render(error.path(), false), error.error());
+            if let Some(entry) = envelope.entries.last_mut() {
+                entry.repeats = repeats;
+            }
+        }
+        envelope.omitted = errors.omitted();
+        envelope
+    }
+}
+
+impl Display for Envelope {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(r#"{"errors":["#)?;
+        for (idx, entry) in self.entries.iter().enumerate() {
+            if idx > 0 {
+                f.write_char(',')?;
+            }
+            f.write_str(r#"{"path":"#)?;
+            write_escaped(f, &entry.path)?;
+            write!(f, r#","code":{},"kind":"#, entry.code)?;
+            write_escaped(f, entry.kind)?;
+            f.write_str(r#","message":"#)?;
+            write_escaped(f, &entry.message)?;
+            f.write_str(r#","meta":{"#)?;
+            for (idx, (name, value)) in entry.meta.iter().enumerate() {
+                if idx > 0 {
+                    f.write_char(',')?;
+                }
+                write_escaped(f, name)?;
+                f.write_char(':')?;
+                write_escaped(f, value)?;
+            }
+            f.write_char('}')?;
+            if entry.repeats != 1 {
+                write!(f, r#","repeats":{}"#, entry.repeats)?;
+            }
+            f.write_char('}')?;
+        }
+        f.write_char(']')?;
+        if self.omitted > 0 {
+            write!(f, r#","omitted":{}"#, self.omitted)?;
+        }
+        f.write_char('}')
+    }
+}
+
+/// Write `s` as a quoted JSON string.
+pub(crate) fn write_escaped(f: &mut (impl Write + ?Sized), s: &str) -> core::fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use super::{Entry, Envelope};
+    use ::serde::ser::{Serialize, SerializeMap, SerializeStruct, Serializer};
+
+    impl Serialize for Envelope {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut envelope = serializer.serialize_struct("Envelope", 2)?;
+            envelope.serialize_field("errors", &self.entries)?;
+            if self.omitted > 0 {
+                envelope.serialize_field("omitted", &self.omitted)?;
+            } else {
+                envelope.skip_field("omitted")?;
+            }
+            envelope.end()
+        }
+    }
+
+    impl Serialize for Entry {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut entry = serializer.serialize_struct("Entry", 6)?;
+            entry.serialize_field("path", &self.path)?;
+            entry.serialize_field("code", &self.code)?;
+            entry.serialize_field("kind", self.kind)?;
+            entry.serialize_field("message", &self.message)?;
+            entry.serialize_field("meta", &Meta(&self.meta))?;
+            if self.repeats != 1 {
+                entry.serialize_field("repeats", &self.repeats)?;
+            } else {
+                entry.skip_field("repeats")?;
+            }
+            entry.end()
+        }
+    }
+
+    /// The payload of an [`Entry`] as a map, in field order.
+    struct Meta<'a>(&'a [(&'static str, alloc::string::String)]);
+
+    impl Serialize for Meta<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut meta = serializer.serialize_map(Some(self.0.len()))?;
+            for (name, value) in self.0 {
+                meta.serialize_entry(name, value)?;
+            }
+            meta.end()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Envelope;
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn renders_stable_fields() {
+        let envelope = Envelope::new()
+            .error("a\"b", &crate::assert_eq(&1, &2).unwrap_err())
+            .error("c", &crate::assert_in(&7, &(1..5)).unwrap_err());
+        assert_eq!(
+            envelope.to_string(),
+            concat!(
+                r#"{"errors":["#,
+                r#"{"path":"a\"b","code":1,"kind":"mismatch","message":"Expected 1, but got 2","meta":{"expected":"1","actual":"2"}},"#,
+                r#"{"path":"c","code":2,"kind":"outside","message":"Value 7 is outside the allowed range (1, 5] (must be at most 5)","meta":{"start":"1","end":"5","value":"7","interval":"left_open"}}"#,
+                r#"]}"#
+            )
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_like_it_renders() {
+        let envelope = Envelope::new()
+            .error("a\"b", &crate::assert_eq(&1, &2).unwrap_err())
+            .error("c", &crate::assert_in(&7, &(1..5)).unwrap_err());
+        assert_eq!(
+            serde_json::to_string(&envelope).unwrap(),
+            envelope.to_string()
+        );
+        assert_eq!(
+            serde_json::to_string(&Envelope::new()).unwrap(),
+            r#"{"errors":[]}"#
+        );
+    }
+
+    #[test]
+    fn carries_repeats_and_omitted() {
+        use crate::{At, MultiError};
+
+        let mut errors = MultiError::with_limit(2);
+        for index in 0..5 {
+            errors.push_dedup(At::new(crate::assert_nonzero(0).unwrap_err()).index(index % 2));
+        }
+        errors.push(At::new(crate::assert_nonzero(0).unwrap_err()).index(7));
+        let envelope = Envelope::from(errors);
+        let counts: Vec<_> = envelope
+            .entries()
+            .iter()
+            .map(|entry| entry.repeats)
+            .collect();
+        assert_eq!(counts, [3, 2]);
+        assert_eq!(envelope.omitted(), 1);
+
+        let rendered = envelope.to_string();
+        assert!(rendered.contains(r#"},"repeats":3},{"path":"[1]""#));
+        assert!(rendered.ends_with(r#"},"repeats":2}],"omitted":1}"#));
+        #[cfg(feature = "serde")]
+        assert_eq!(serde_json::to_string(&envelope).unwrap(), rendered);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{At, Caused, Mismatch, Outside, Spanned, Unknown};
+
+/// Long-form help for a kind of error, in the spirit of
+/// `rustc --explain`.
+pub trait Explain {
+    /// A few sentences on what the error means and how it is usually
+    /// fixed.
+    fn explanation(&self) -> &'static str;
+
+    /// Where to read more.
+    fn url(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+impl<T: Copy + Eq> Explain for Mismatch<T> {
+    fn explanation(&self) -> &'static str {
+        "A value had to be exactly equal to an expected value, but it was \
+         something else.  Check where the actual value comes from, or whether \
+         the expectation is out of date."
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some("https://docs.rs/goof/latest/goof/struct.Mismatch.html")
+    }
+}
+
+impl<T: PartialOrd + Copy> Explain for Outside<T> {
+    fn explanation(&self) -> &'static str {
+        "A value had to lie within a range of acceptable values, but it was \
+         either too small or too large.  Clamp the value, or validate it \
+         earlier where the range is known."
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some("https://docs.rs/goof/latest/goof/struct.Outside.html")
+    }
+}
+
+impl<T: Eq> Explain for Unknown<'_, T> {
+    fn explanation(&self) -> &'static str {
+        "A value had to be one of a fixed set of known values, such as the \
+         variants of an enumeration, but it was not.  This usually means a \
+         typo, or data produced by a newer version of the program."
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        Some("https://docs.rs/goof/latest/goof/struct.Unknown.html")
+    }
+}
+
+impl<E: Explain, S: ?Sized> Explain for Caused<E, S> {
+    fn explanation(&self) -> &'static str {
+        self.error().explanation()
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        self.error().url()
+    }
+}
+
+impl<E: Explain> Explain for Spanned<E> {
+    fn explanation(&self) -> &'static str {
+        self.error().explanation()
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        self.error().url()
+    }
+}
+
+impl<E: Explain> Explain for At<E> {
+    fn explanation(&self) -> &'static str {
+        self.error().explanation()
+    }
+
+    fn url(&self) -> Option<&'static str> {
+        self.error().url()
+    }
+}
@@ -0,0 +1,596 @@
+//! A stable, C-compatible representation of the built-in errors.
+//!
+//! Every error with an integer payload converts into a [`CGoof`]: its
+//! [`crate::code`], the offending value, up to two values it was
+//! checked against and the rendered message.  The `goof_*` functions
+//! are the accessors exported to C, e.g. with `cbindgen`.
+
+use crate::arith::{DivisionByZero, Op, Overflow, Underflow};
+use crate::{
+    render, Duplicate, EmptyRange, Exceeded, Interval, LengthMismatch, Misaligned, Mismatch,
+    NotMultipleOf, NotPositive, Outside, TooLarge, TooShort, TooSmall, Unknown, UnknownBits,
+    UnsortedAt, Zero,
+};
+use alloc::boxed::Box;
+use alloc::ffi::CString;
+use core::ffi::{c_char, CStr};
+use core::fmt::Display;
+
+/// The kind code of a [`Mismatch`].
+pub const GOOF_MISMATCH: u32 = crate::code::MISMATCH;
+/// The kind code of an [`Outside`].
+pub const GOOF_OUTSIDE: u32 = crate::code::OUTSIDE;
+/// The kind code of an [`Unknown`].
+pub const GOOF_UNKNOWN: u32 = crate::code::UNKNOWN;
+
+/// An error, laid out for C.  The `kind` is the [`crate::code`] of
+/// the error, and the values are:
+///
+/// | kind                 | `value`         | `first`       | `second`       |
+/// |----------------------|-----------------|---------------|----------------|
+/// | `MISMATCH`           | `actual`        | `expected`    | 0              |
+/// | `OUTSIDE`            | `value`         | `range.start` | `range.end`    |
+/// | `UNKNOWN`            | `value`         | 0             | 0              |
+/// | `EMPTY_RANGE`        | 0               | `range.start` | `range.end`    |
+/// | `LENGTH_MISMATCH`    | `actual`        | `expected`    | 0              |
+/// | `UNSORTED`           | `index`         | `prev`        | `next`         |
+/// | `DUPLICATE`          | `value`         | `first_index` | `second_index` |
+/// | `DIVISION_BY_ZERO`   | `dividend`      | 0             | 0              |
+/// | `OVERFLOW`           | `lhs`           | `rhs`         | `op`           |
+/// | `UNDERFLOW`          | `lhs`           | `rhs`         | `op`           |
+/// | `NOT_POSITIVE`       | `value`         | 0             | 0              |
+/// | `ZERO`               | 0               | 0             | 0              |
+/// | `NOT_MULTIPLE_OF`    | `value`         | `factor`      | 0              |
+/// | `EXCEEDED`           | `requested`     | `limit`       | `over`         |
+/// | `MISALIGNED`         | `actual_offset` | `required`    | 0              |
+/// | `UNKNOWN_BITS`       | `value`         | `known_mask`  | `unknown`      |
+/// | `TOO_SHORT`          | `available`     | `needed`      | 0              |
+/// | `TOO_LARGE`          | `actual`        | `max`         | `strict`       |
+/// | `TOO_SMALL`          | `actual`        | `min`         | `strict`       |
+///
+/// The slots hold the bits of a 64-bit integer.  `signed` is 1 if the
+/// values of the error are of a signed type, so that C reads them as
+/// `int64_t`, and 0 if they are unsigned, so that `u64::MAX` arrives
+/// intact as a `uint64_t`.  Lengths, indices, `op` and `strict` are
+/// never negative.
+///
+/// `op` is 0 to 3 for `+`, `-`, `*` and `/`, and `strict` is 0 or 1.
+/// The `interval` of an `OUTSIDE` or `EMPTY_RANGE` is 0 to 3 for
+/// [`Interval::Closed`], [`Interval::LeftOpen`], [`Interval::HalfOpen`]
+/// and [`Interval::Open`], and 0 for every other kind.
+///
+/// The message is owned by the `CGoof` and freed with it.
+#[repr(C)]
+#[derive(Debug)]
+pub struct CGoof {
+    pub kind: u32,
+    pub interval: u32,
+    pub signed: u32,
+    pub value: u64,
+    pub first: u64,
+    pub second: u64,
+    message: *mut c_char,
+}
+
+impl CGoof {
+    fn new(kind: u32, value: u64, first: u64, second: u64, message: &dyn Display) -> Self {
+        let mut message = render(message, false).into_bytes();
+        message.retain(|&byte| byte != 0);
+        Self {
+            kind,
+            interval: 0,
+            signed: 0,
+            value,
+            first,
+            second,
+            message: CString::new(message).map_or(core::ptr::null_mut(), CString::into_raw),
+        }
+    }
+
+    fn interval(&self) -> Option<Interval> {
+        match self.interval {
+            0 => Some(Interval::Closed),
+            1 => Some(Interval::LeftOpen),
+            2 => Some(Interval::HalfOpen),
+            3 => Some(Interval::Open),
+            _ => None,
+        }
+    }
+
+    fn op(&self) -> Option<Op> {
+        match self.second {
+            0 => Some(Op::Add),
+            1 => Some(Op::Sub),
+            2 => Some(Op::Mul),
+            3 => Some(Op::Div),
+            _ => None,
+        }
+    }
+
+    fn strict(&self) -> Option<bool> {
+        match self.second {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+
+    /// The rendered message.
+    pub fn message(&self) -> &str {
+        if self.message.is_null() {
+            return "";
+        }
+        // SAFETY: non-null messages always come from `CString::into_raw`
+        // in `new` and live as long as `self`.
+        unsafe { CStr::from_ptr(self.message) }
+            .to_str()
+            .unwrap_or_default()
+    }
+
+    /// Move `self` to the heap, to hand over to C.  Free it with
+    /// [`goof_free`].
+    pub fn into_raw(self) -> *mut CGoof {
+        Box::into_raw(Box::new(self))
+    }
+}
+
+impl Drop for CGoof {
+    fn drop(&mut self) {
+        if !self.message.is_null() {
+            // SAFETY: see `message`; the pointer is not used again.
+            drop(unsafe { CString::from_raw(self.message) });
+        }
+    }
+}
+
+/// An integer that fits in a [`CGoof`] value slot.  Conversions
+/// round-trip for every value of the type.
+pub trait CValue: Copy {
+    /// Whether C is to read the slot as `int64_t` rather than `uint64_t`.
+    const SIGNED: bool;
+
+    fn to_c(self) -> u64;
+    fn from_c(value: u64) -> Self;
+}
+
+macro_rules! c_value {
+    ($signed:literal => $($t:ty),*) => {
+        $(impl CValue for $t {
+            const SIGNED: bool = $signed;
+
+            fn to_c(self) -> u64 {
+                // Sign-extends signed types, so that the bits read as an
+                // `int64_t` are the value.
+                self as i64 as u64
+            }
+
+            fn from_c(value: u64) -> Self {
+                value as $t
+            }
+        })*
+    };
+}
+
+c_value!(false => u8, u16, u32, u64, usize);
+c_value!(true => i8, i16, i32, i64, isize);
+
+/// [`CValue::SIGNED`] as the `signed` of a [`CGoof`].
+fn signed<T: CValue>(_: &T) -> u32 {
+    u32::from(T::SIGNED)
+}
+
+/// Conversion of an error to and from a [`CGoof`].
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::ffi::{IntoC, GOOF_OUTSIDE};
+/// use goof::Outside;
+///
+/// let error = goof::assert_in(&70_u8, &(1..10)).unwrap_err();
+/// let c = error.clone().into_c();
+/// assert_eq!((c.kind, c.value, c.first, c.second), (GOOF_OUTSIDE, 70, 1, 10));
+/// assert_eq!(c.interval, goof::Interval::LeftOpen as u32);
+/// assert_eq!(c.message(), "Value 70 is outside the allowed range (1, 10] (must be at most 10)");
+/// assert_eq!(Outside::<u8>::from_c(&c), Some(error));
+/// ```
+pub trait IntoC: Sized {
+    fn into_c(self) -> CGoof;
+
+    /// Rebuild the error, or `None` if `c` is of another kind.
+    fn from_c(c: &CGoof) -> Option<Self>;
+}
+
+/// `IntoC` for errors whose payload is only integers, each in the
+/// slot given.
+macro_rules! into_c {
+    ($(impl<$($generic:ident: $bound:path),*> $error:ty = $code:path { $($field:ident: $slot:ident),* })*) => {
+        $(impl<$($generic: CValue + $bound),*> IntoC for $error
+        where
+            $error: Display,
+        {
+            fn into_c(self) -> CGoof {
+                let mut c = CGoof::new($code, 0, 0, 0, &self);
+                $(c.$slot = self.$field.to_c();
+                c.signed |= signed(&self.$field);)*
+                c
+            }
+
+            fn from_c(c: &CGoof) -> Option<Self> {
+                (c.kind == $code).then(|| Self {
+                    $($field: CValue::from_c(c.$slot),)*
+                    provenance: "from_c",
+                })
+            }
+        })*
+    };
+}
+
+into_c! {
+    impl<T: Eq> Mismatch<T> = GOOF_MISMATCH { actual: value, expected: first }
+    impl<> LengthMismatch = crate::code::LENGTH_MISMATCH { actual: value, expected: first }
+    impl<T: Eq> UnsortedAt<T> = crate::code::UNSORTED { index: value, prev: first, next: second }
+    impl<T: Eq> Duplicate<T> = crate::code::DUPLICATE {
+        value: value,
+        first_index: first,
+        second_index: second
+    }
+    impl<T: Eq> DivisionByZero<T> = crate::code::DIVISION_BY_ZERO { dividend: value }
+    impl<T: PartialOrd> NotPositive<T> = crate::code::NOT_POSITIVE { value: value }
+    impl<T: Eq> NotMultipleOf<T> = crate::code::NOT_MULTIPLE_OF { value: value, factor: first }
+    impl<T: Eq> Exceeded<T> = crate::code::EXCEEDED {
+        requested: value,
+        limit: first,
+        over: second
+    }
+    impl<> Misaligned = crate::code::MISALIGNED { actual_offset: value, required: first }
+    impl<T: Eq> UnknownBits<T> = crate::code::UNKNOWN_BITS {
+        value: value,
+        known_mask: first,
+        unknown: second
+    }
+    impl<> TooShort = crate::code::TOO_SHORT { available: value, needed: first }
+}
+
+impl<T: CValue + Display + PartialOrd> IntoC for Outside<T> {
+    fn into_c(self) -> CGoof {
+        let mut c = CGoof::new(
+            GOOF_OUTSIDE,
+            self.value.to_c(),
+            self.range.start.to_c(),
+            self.range.end.to_c(),
+            &self,
+        );
+        c.interval = self.interval as u32;
+        c.signed = signed(&self.value);
+        c
+    }
+
+    fn from_c(c: &CGoof) -> Option<Self> {
+        if c.kind != GOOF_OUTSIDE {
+            return None;
+        }
+        Some(Outside {
+            range: T::from_c(c.first)..T::from_c(c.second),
+            interval: c.interval()?,
+            value: T::from_c(c.value),
+            provenance: "from_c",
+        })
+    }
+}
+
+impl<T: CValue + Display + PartialOrd> IntoC for EmptyRange<T> {
+    fn into_c(self) -> CGoof {
+        let code = crate::code::EMPTY_RANGE;
+        let mut c = CGoof::new(
+            code,
+            0,
+            self.range.start.to_c(),
+            self.range.end.to_c(),
+            &self,
+        );
+        c.interval = self.interval as u32;
+        c.signed = signed(&self.range.start);
+        c
+    }
+
+    fn from_c(c: &CGoof) -> Option<Self> {
+        if c.kind != crate::code::EMPTY_RANGE {
+            return None;
+        }
+        Some(EmptyRange {
+            range: T::from_c(c.first)..T::from_c(c.second),
+            interval: c.interval()?,
+            provenance: "from_c",
+        })
+    }
+}
+
+/// The knowns are not representable in C and are dropped.
+impl<T: CValue + Display + Eq> IntoC for Unknown<'_, T> {
+    fn into_c(self) -> CGoof {
+        let mut c = CGoof::new(GOOF_UNKNOWN, self.value.to_c(), 0, 0, &self);
+        c.signed = signed(&self.value);
+        c
+    }
+
+    fn from_c(c: &CGoof) -> Option<Self> {
+        (c.kind == GOOF_UNKNOWN).then(|| Unknown {
+            knowns: None,
+            value: T::from_c(c.value),
+            provenance: "from_c",
+        })
+    }
+}
+
+impl IntoC for Zero {
+    fn into_c(self) -> CGoof {
+        CGoof::new(crate::code::ZERO, 0, 0, 0, &self)
+    }
+
+    fn from_c(c: &CGoof) -> Option<Self> {
+        (c.kind == crate::code::ZERO).then_some(Zero {
+            provenance: "from_c",
+        })
+    }
+}
+
+macro_rules! into_c_bound {
+    ($($error:ident = $code:path { $bound:ident }),*) => {
+        $(impl<T: CValue + Display> IntoC for $error<T> {
+            fn into_c(self) -> CGoof {
+                let strict = u64::from(self.strict);
+                let mut c =
+                    CGoof::new($code, self.actual.to_c(), self.$bound.to_c(), strict, &self);
+                c.signed = signed(&self.actual);
+                c
+            }
+
+            fn from_c(c: &CGoof) -> Option<Self> {
+                if c.kind != $code {
+                    return None;
+                }
+                Some($error {
+                    $bound: T::from_c(c.first),
+                    actual: T::from_c(c.value),
+                    strict: c.strict()?,
+                    provenance: "from_c",
+                })
+            }
+        })*
+    };
+}
+
+into_c_bound!(
+    TooLarge = crate::code::TOO_LARGE { max },
+    TooSmall = crate::code::TOO_SMALL { min }
+);
+
+macro_rules! into_c_out_of_range {
+    ($($error:ident = $code:path),*) => {
+        $(impl<T: CValue + Display> IntoC for $error<T> {
+            fn into_c(self) -> CGoof {
+                let (lhs, rhs) = (self.lhs().to_c(), self.rhs().to_c());
+                let mut c = CGoof::new($code, lhs, rhs, self.op() as u64, &self);
+                c.signed = signed(self.lhs());
+                c
+            }
+
+            fn from_c(c: &CGoof) -> Option<Self> {
+                if c.kind != $code {
+                    return None;
+                }
+                let (lhs, rhs) = (T::from_c(c.value), T::from_c(c.first));
+                Some($error::new(lhs, c.op()?, rhs, "from_c"))
+            }
+        })*
+    };
+}
+
+into_c_out_of_range!(
+    Overflow = crate::code::OVERFLOW,
+    Underflow = crate::code::UNDERFLOW
+);
+
+/// The kind code of `goof`.
+///
+/// # Safety
+///
+/// `goof` must point to a live `CGoof`.
+#[no_mangle]
+pub unsafe extern "C" fn goof_kind(goof: *const CGoof) -> u32 {
+    (*goof).kind
+}
+
+/// The [`Interval`] of `goof`, if it is an `OUTSIDE` or an
+/// `EMPTY_RANGE`, as numbered in [`CGoof`].
+///
+/// # Safety
+///
+/// `goof` must point to a live `CGoof`.
+#[no_mangle]
+pub unsafe extern "C" fn goof_interval(goof: *const CGoof) -> u32 {
+    (*goof).interval
+}
+
+/// Whether the values of `goof` are signed, as in [`CGoof`].
+///
+/// # Safety
+///
+/// `goof` must point to a live `CGoof`.
+#[no_mangle]
+pub unsafe extern "C" fn goof_signed(goof: *const CGoof) -> u32 {
+    (*goof).signed
+}
+
+/// The offending value of `goof`.
+///
+/// # Safety
+///
+/// `goof` must point to a live `CGoof`.
+#[no_mangle]
+pub unsafe extern "C" fn goof_value(goof: *const CGoof) -> u64 {
+    (*goof).value
+}
+
+/// The first value `goof` was checked against.
+///
+/// # Safety
+///
+/// `goof` must point to a live `CGoof`.
+#[no_mangle]
+pub unsafe extern "C" fn goof_first(goof: *const CGoof) -> u64 {
+    (*goof).first
+}
+
+/// The second value `goof` was checked against.
+///
+/// # Safety
+///
+/// `goof` must point to a live `CGoof`.
+#[no_mangle]
+pub unsafe extern "C" fn goof_second(goof: *const CGoof) -> u64 {
+    (*goof).second
+}
+
+/// The NUL-terminated message of `goof`, valid until it is freed.
+///
+/// # Safety
+///
+/// `goof` must point to a live `CGoof`.
+#[no_mangle]
+pub unsafe extern "C" fn goof_message(goof: *const CGoof) -> *const c_char {
+    (*goof).message
+}
+
+/// Free a `CGoof` obtained from [`CGoof::into_raw`], including its
+/// message.  Does nothing if `goof` is null.
+///
+/// # Safety
+///
+/// `goof` must be null or come from `CGoof::into_raw`, and must not
+/// be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn goof_free(goof: *mut CGoof) {
+    if !goof.is_null() {
+        drop(Box::from_raw(goof));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arith::{checked_add, checked_div, checked_sub, ArithGoof};
+    use crate::BoundGoof;
+    use core::fmt::Debug;
+
+    fn round_trip<E: IntoC + Clone + PartialEq + Debug>(error: E) {
+        assert_eq!(E::from_c(&error.clone().into_c()), Some(error));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn round_trips_through_c() {
+        let mismatch = crate::assert_eq(&u64::MAX, &3).unwrap_err();
+        let raw = mismatch.into_c().into_raw();
+        unsafe {
+            assert_eq!(goof_kind(raw), GOOF_MISMATCH);
+            assert_eq!((goof_signed(raw), goof_first(raw)), (0, u64::MAX));
+            assert_eq!(Mismatch::<u64>::from_c(&*raw), Some(mismatch));
+            assert_eq!(Outside::<u64>::from_c(&*raw), None);
+            let message = CStr::from_ptr(goof_message(raw));
+            assert_eq!(
+                message.to_str(),
+                Ok("Expected 18446744073709551615, but got 3")
+            );
+            goof_free(raw);
+        }
+
+        let unknown = crate::assert_known_enum(&[1_i8, 2], -3).unwrap_err();
+        let c = unknown.into_c();
+        assert_eq!(
+            c.message(),
+            "The value -3 is not known, because it's not one of [1, 2]"
+        );
+        assert_eq!(Unknown::<i8>::from_c(&c).map(|u| u.value), Some(-3));
+        assert_eq!((c.signed, c.value as i64), (1, -3));
+    }
+
+    #[test]
+    fn unsigned_values_above_i64_max_cross() {
+        let outside = crate::assert_in(&u64::MAX, &(0..u64::MAX - 1)).unwrap_err();
+        let c = outside.clone().into_c();
+        assert_eq!((c.signed, c.value, c.second), (0, u64::MAX, u64::MAX - 1));
+        assert_eq!(Outside::<u64>::from_c(&c), Some(outside));
+        round_trip(crate::assert_capacity(usize::MAX, 4).unwrap_err());
+        match crate::assert_le(&u64::MAX, &1).unwrap_err() {
+            BoundGoof::TooLarge(error) => round_trip(error),
+            _ => unreachable!(),
+        }
+        match checked_add(u64::MAX, 1).unwrap_err() {
+            ArithGoof::Overflow(error) => round_trip(error),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn every_interval_crosses() {
+        for interval in [
+            Interval::Closed,
+            Interval::LeftOpen,
+            Interval::HalfOpen,
+            Interval::Open,
+        ] {
+            let outside = crate::assert_in_with(&9_u8, &(1..5), interval).unwrap_err();
+            let raw = outside.clone().into_c().into_raw();
+            unsafe {
+                assert_eq!(goof_interval(raw), interval as u32);
+                assert_eq!(Outside::<u8>::from_c(&*raw), Some(outside));
+                goof_free(raw);
+            }
+            let (start, end) = (5_i16, -5);
+            round_trip(crate::assert_range(&(start..end), interval).unwrap_err());
+        }
+
+        let mut c = crate::assert_in(&7_u8, &(1..5)).unwrap_err().into_c();
+        c.interval = 4;
+        assert_eq!(Outside::<u8>::from_c(&c), None);
+    }
+
+    #[test]
+    fn every_integer_kind_crosses() {
+        round_trip(crate::assert_len([1, 2], 3).unwrap_err());
+        round_trip(crate::assert_sorted(&[3_u16, 1]).unwrap_err());
+        round_trip(crate::assert_unique(&[4_i64, 2, 4]).unwrap_err());
+        round_trip(crate::assert_positive(-3_i8).unwrap_err());
+        round_trip(crate::assert_nonzero(0_u32).unwrap_err());
+        round_trip(crate::assert_multiple_of(7_u8, 3).unwrap_err());
+        round_trip(crate::assert_capacity(9_usize, 4).unwrap_err());
+        round_trip(crate::assert_aligned(3_usize, 8).unwrap_err());
+        round_trip(crate::assert_known_bits(0b1010_u8, 0b0111).unwrap_err());
+        round_trip(crate::assert_remaining(&[0; 2], 5).unwrap_err());
+        for error in [
+            crate::assert_le(&5_i32, &4).unwrap_err(),
+            crate::assert_lt(&4_i32, &4).unwrap_err(),
+            crate::assert_ge(&3_i32, &4).unwrap_err(),
+            crate::assert_gt(&4_i32, &4).unwrap_err(),
+        ] {
+            match error {
+                BoundGoof::TooLarge(error) => round_trip(error),
+                BoundGoof::TooSmall(error) => round_trip(error),
+                BoundGoof::NotComparable(_) => unreachable!(),
+            }
+        }
+        for error in [
+            checked_add(i8::MAX, 1).unwrap_err(),
+            checked_sub(i8::MIN, 1).unwrap_err(),
+            checked_div(i8::MIN, 0).unwrap_err(),
+        ] {
+            match error {
+                ArithGoof::Overflow(error) => round_trip(error),
+                ArithGoof::Underflow(error) => round_trip(error),
+                ArithGoof::DivisionByZero(error) => round_trip(error),
+            }
+        }
+    }
+}
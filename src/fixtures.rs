@@ -0,0 +1,546 @@
+//! Representative instances of every error in the crate, for
+//! generating documentation of what users will actually see.
+
+use crate::arith::{DivisionByZero, Op, Overflow, Underflow};
+use crate::config::ConfigGoof;
+#[cfg(feature = "std")]
+use crate::env::{InvalidEnv, MissingEnv};
+use crate::partial::Against;
+use crate::retry::RetriesExhausted;
+use crate::schema::{DuplicateKey, InvalidData, MissingField, UnknownField};
+#[cfg(feature = "semver")]
+use crate::semver::{Version, VersionOutside};
+use crate::{
+    render, ArityMismatch, BadText, Bounds, Cancelled, Duplicate, EmptyRange, Endian,
+    EndiannessMismatch, Exceeded, ExpectedOneOf, FailedPredicate, Fields, Goof,
+    InsufficientPermission, Interval, InvalidTransition, LengthMismatch, LengthOutside,
+    MagicMismatch, Misaligned, Mismatch, MultiError, NotComparable, NotDisjoint, NotMultipleOf,
+    NotPositive, NotSubset, Outside, ParseGoof, PatternMismatch, RateLimited, SliceMismatch,
+    Timeout, TooLarge, TooShort, TooSmall, Unknown, UnknownBits, UnsortedAt, Zero,
+};
+#[cfg(feature = "std")]
+use crate::{IoGoof, PanicLocation, Panicked};
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display};
+use core::ops::{Bound, Range};
+use core::time::Duration;
+
+/// The ways in which an error can be rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Verbosity {
+    /// `{}`
+    Display,
+    /// `{:#}`
+    Alternate,
+    /// `{:?}`
+    Debug,
+}
+
+impl Verbosity {
+    /// Every verbosity, from the tersest to the most detailed.
+    pub const ALL: [Verbosity; 3] = [Verbosity::Display, Verbosity::Alternate, Verbosity::Debug];
+}
+
+/// One rendered error in the [`gallery`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Exhibit {
+    /// The kind of error, as reported by [`Fields::kind`].
+    pub kind: &'static str,
+    pub verbosity: Verbosity,
+    pub rendered: String,
+}
+
+/// Render one representative instance of every kind of error with
+/// every [`Verbosity`].
+///
+/// # Examples
+/// ```rust
+/// use goof::fixtures::Verbosity;
+///
+/// for exhibit in goof::gallery() {
+///     if exhibit.verbosity == Verbosity::Display {
+///         println!("| {} | {} |", exhibit.kind, exhibit.rendered);
+///     }
+/// }
+/// ```
+pub fn gallery() -> Vec<Exhibit> {
+    let mut exhibits = Vec::new();
+    exhibit(
+        &mut exhibits,
+        &Mismatch {
+            expected: 32_usize,
+            actual: 0,
+            provenance: "assert_eq",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &Outside {
+            range: 1..5_u32,
+            interval: Interval::LeftOpen,
+            value: 7,
+            provenance: "assert_in",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &Unknown {
+            knowns: Some(&["red", "green", "blue"][..]),
+            value: "purple",
+            provenance: "assert_known_enum",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &PatternMismatch {
+            pattern: "State::Idle",
+            value: String::from("Busy(3)"),
+            provenance: "assert_matches",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &FailedPredicate {
+            value: 48_u32,
+            description: "is a power of two",
+            provenance: "assert_that",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &LengthMismatch {
+            expected: 3,
+            actual: 2,
+            provenance: "assert_len",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &LengthOutside {
+            range: Bounds(Bound::Included(2), Bound::Excluded(5)),
+            actual: 7,
+            provenance: "assert_len_in",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &UnsortedAt {
+            index: 2,
+            prev: 4,
+            next: 3,
+            provenance: "assert_sorted",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &Duplicate {
+            value: "id",
+            first_index: 0,
+            second_index: 3,
+            provenance: "assert_unique",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &NotSubset {
+            missing: vec!["lz4", "xz"],
+            provenance: "assert_subset",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &NotDisjoint {
+            common: vec!["root"],
+            provenance: "assert_disjoint",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &SliceMismatch {
+            index: 4,
+            expected: Some(b'9'),
+            actual: Some(b'7'),
+            expected_len: 6,
+            actual_len: 6,
+            provenance: "assert_slices_eq",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &MagicMismatch {
+            expected: b"\x89PNG",
+            actual: b"GIF8".to_vec(),
+            provenance: "assert_magic",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &ExpectedOneOf {
+            expected: &[',', '}'][..],
+            found: ']',
+            provenance: "assert_one_of",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &MissingField {
+            container: "Server",
+            field: "port",
+            provenance: "require_field",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &UnknownField {
+            container: "Server",
+            field: String::from("prot"),
+            expected: &["host", "port"],
+            provenance: "UnknownField::new",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &DuplicateKey {
+            container: "Server",
+            key: String::from("port"),
+            provenance: "DuplicateKey::new",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &InvalidData {
+            expected: String::from("u16"),
+            unexpected: String::from("string \"http\""),
+            provenance: "de::Error::invalid_type",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &NotPositive {
+            value: -3,
+            provenance: "assert_positive",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &NotMultipleOf {
+            value: 1000_u64,
+            factor: 512,
+            provenance: "assert_multiple_of",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &InvalidTransition {
+            from: "Paused",
+            to: "Finished",
+            allowed: &["Running", "Stopped"][..],
+            provenance: "assert_transition",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &InsufficientPermission {
+            required: vec!["read", "delete"],
+            held: vec!["read", "write"],
+            missing: vec!["delete"],
+            provenance: "assert_permitted",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &Exceeded {
+            requested: 1500_usize,
+            limit: 1000,
+            over: 500,
+            provenance: "assert_capacity",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &ArityMismatch {
+            callee: "foo",
+            expected: 2,
+            actual: 5,
+            provenance: "assert_arity",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &Misaligned {
+            required: 8,
+            actual_offset: 0x13,
+            provenance: "assert_aligned",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &EndiannessMismatch {
+            expected: Endian::Little,
+            found: Endian::Big,
+            provenance: "EndiannessMismatch::new",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &UnknownBits {
+            known_mask: 0b0111_u8,
+            value: 0b1010,
+            unknown: 0b1000,
+            provenance: "assert_known_bits",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &TooShort {
+            needed: 8,
+            available: 3,
+            provenance: "take_exact",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &Goof {
+            message: Cow::Borrowed("bad header"),
+            provenance: "goof!",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &EmptyRange {
+            range: Range { start: 5, end: 1 },
+            interval: Interval::LeftOpen,
+            provenance: "assert_range",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &NotComparable {
+            value: f64::NAN,
+            against: Against::Range(0.0..1.0, Interval::LeftOpen),
+            provenance: "assert_in_partial",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &TooLarge {
+            max: 16,
+            actual: 17,
+            strict: false,
+            provenance: "assert_le",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &TooSmall {
+            min: 0.0,
+            actual: 0.0,
+            strict: true,
+            provenance: "assert_gt",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &MultiError::from(vec![
+            Mismatch {
+                expected: 32,
+                actual: 0,
+                provenance: "assert_eq",
+            },
+            Mismatch {
+                expected: 32,
+                actual: 64,
+                provenance: "assert_eq",
+            },
+        ]),
+    );
+    let mut config = ConfigGoof::new("app.toml");
+    config
+        .push(
+            "port",
+            Outside {
+                range: 1..65535,
+                interval: Interval::LeftOpen,
+                value: 0,
+                provenance: "assert_in",
+            },
+        )
+        .push(
+            "name",
+            MissingField {
+                container: "app.toml",
+                field: "name",
+                provenance: "require_key",
+            },
+        );
+    exhibit(&mut exhibits, &config);
+    #[cfg(feature = "std")]
+    {
+        exhibit(
+            &mut exhibits,
+            &MissingEnv {
+                name: "DATABASE_URL",
+                not_unicode: false,
+            },
+        );
+        exhibit(
+            &mut exhibits,
+            &InvalidEnv {
+                name: "PORT",
+                value: String::from("eighty"),
+                error: "eighty".parse::<u16>().unwrap_err(),
+            },
+        );
+        exhibit(
+            &mut exhibits,
+            &Panicked {
+                message: String::from("index out of bounds: the len is 3 but the index is 7"),
+                location: Some(PanicLocation {
+                    file: String::from("src/main.rs"),
+                    line: 12,
+                    column: 5,
+                }),
+            },
+        );
+        exhibit(
+            &mut exhibits,
+            &IoGoof::new(std::io::ErrorKind::NotFound.into())
+                .during("open")
+                .on_path("app.toml"),
+        );
+    }
+    exhibit(
+        &mut exhibits,
+        &Overflow::new(200_u8, Op::Add, 100, "checked_add"),
+    );
+    exhibit(
+        &mut exhibits,
+        &Underflow::new(i8::MIN, Op::Sub, 1, "checked_sub"),
+    );
+    exhibit(
+        &mut exhibits,
+        &DivisionByZero {
+            dividend: 7,
+            provenance: "checked_div",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &Zero {
+            provenance: "assert_nonzero",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &Timeout {
+            elapsed: Duration::from_millis(1500),
+            limit: Duration::from_secs(1),
+            provenance: "assert_within",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &RetriesExhausted {
+            attempts: 5,
+            last_error: Timeout {
+                elapsed: Duration::from_secs(3),
+                limit: Duration::from_secs(2),
+                provenance: "assert_within",
+            },
+            provenance: "retry",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &Cancelled {
+            reason: Some("shutting down"),
+            provenance: "Cancelled::because",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &RateLimited {
+            limit: 100,
+            window: Duration::from_secs(60),
+            retry_after: Some(Duration::from_secs(5)),
+            provenance: "RateLimited::new",
+        },
+    );
+    #[cfg(feature = "semver")]
+    exhibit(
+        &mut exhibits,
+        &VersionOutside {
+            requirement: "^1.4".parse().expect("a valid requirement"),
+            found: Version::new(2, 1, 0),
+            provenance: "assert_version_in",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &BadText {
+            error: String::from_utf8(vec![b'c', b'a', b'f', 0xe9])
+                .unwrap_err()
+                .utf8_error(),
+            provenance: "assert_utf8",
+        },
+    );
+    exhibit(
+        &mut exhibits,
+        &ParseGoof {
+            input: "]}",
+            expected: vec!["digit".into(), "array".into()],
+            provenance: "nom",
+        },
+    );
+    exhibits
+}
+
+fn exhibit<E: Fields + Display + Debug>(exhibits: &mut Vec<Exhibit>, error: &E) {
+    for verbosity in Verbosity::ALL {
+        let rendered = match verbosity {
+            Verbosity::Display => render(error, false),
+            Verbosity::Alternate => render(error, true),
+            Verbosity::Debug => format!("{error:?}"),
+        };
+        exhibits.push(Exhibit {
+            kind: error.kind(),
+            verbosity,
+            rendered,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{gallery, Verbosity};
+    use crate::code::Registry;
+
+    #[test]
+    fn every_kind_in_every_verbosity() {
+        let exhibits = gallery();
+        for (_, kind) in Registry::new().iter() {
+            let gated = match kind {
+                "panicked" | "missing_env" | "invalid_env" | "io" => !cfg!(feature = "std"),
+                "version_outside" => !cfg!(feature = "semver"),
+                _ => false,
+            };
+            if gated {
+                continue;
+            }
+            for verbosity in Verbosity::ALL {
+                assert!(
+                    exhibits
+                        .iter()
+                        .any(|exhibit| exhibit.kind == kind && exhibit.verbosity == verbosity),
+                    "no {} in {:?}",
+                    kind,
+                    verbosity
+                );
+            }
+        }
+    }
+}
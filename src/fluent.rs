@@ -0,0 +1,148 @@
+//! Building errors with method chains that read as sentences, as an
+//! alternative to the `assert_*` functions.
+
+use crate::{check_in, code, hook, At, Interval, Mismatch, Outside};
+use alloc::string::String;
+use core::ops::Range;
+
+/// Start checking that a value is `expected`.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// let version = 3_u8;
+/// assert_eq!(goof::expect(2).but_got(2), Ok(2));
+/// assert_eq!(
+///     goof::expect(2).named("version").but_got(version).unwrap_err().to_string(),
+///     "version: Expected 2, but got 3"
+/// );
+/// ```
+pub fn expect<T>(expected: T) -> Expect<T> {
+    Expect { expected }
+}
+
+/// Start checking `value`.
+///
+/// Ranges are checked as [`Range::contains`] does, so the start is
+/// allowed and the end is not.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// assert_eq!(goof::value(1).must_be_in(&(1..65536)), Ok(1));
+/// assert_eq!(
+///     goof::value(0).named("port").must_be_in(&(1..65536)).unwrap_err().to_string(),
+///     "port: Value 0 is outside the allowed range 1..65536 (must be at least 1)"
+/// );
+/// ```
+pub fn value<T>(value: T) -> Value<T> {
+    Value { value }
+}
+
+/// Builder returned by [`expect`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[must_use = "nothing is checked until `but_got` is called"]
+pub struct Expect<T> {
+    expected: T,
+}
+
+/// Builder returned by [`value`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[must_use = "nothing is checked until `must_be_in` is called"]
+pub struct Value<T> {
+    value: T,
+}
+
+/// A builder with the name of what is being checked, so that the
+/// error is [`At`] that name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use = "nothing is checked until the check is called"]
+pub struct Named<B> {
+    builder: B,
+    name: String,
+}
+
+impl<T: Copy + Eq> Expect<T> {
+    /// Name what is being checked, e.g. the field it was read from.
+    pub fn named(self, name: impl Into<String>) -> Named<Self> {
+        Named {
+            builder: self,
+            name: name.into(),
+        }
+    }
+
+    /// Like [`crate::assert_eq`].
+    pub fn but_got(self, actual: T) -> Result<T, Mismatch<T>> {
+        if self.expected == actual {
+            Ok(actual)
+        } else {
+            hook::observe("mismatch", code::MISMATCH, "expect");
+            Err(Mismatch {
+                expected: self.expected,
+                actual,
+                provenance: "expect",
+            })
+        }
+    }
+}
+
+impl<T: Ord + Copy> Value<T> {
+    /// Name what is being checked, e.g. the field it was read from.
+    pub fn named(self, name: impl Into<String>) -> Named<Self> {
+        Named {
+            builder: self,
+            name: name.into(),
+        }
+    }
+
+    /// Like [`crate::assert_in_with`] with [`Interval::HalfOpen`].
+    pub fn must_be_in(self, range: &Range<T>) -> Result<T, Outside<T>> {
+        check_in(self.value, range, Interval::HalfOpen, "value")
+    }
+}
+
+impl<T: Copy + Eq> Named<Expect<T>> {
+    /// Like [`Expect::but_got`], with the error [`At`] the name.
+    pub fn but_got(self, actual: T) -> Result<T, At<Mismatch<T>>> {
+        let name = self.name;
+        self.builder
+            .but_got(actual)
+            .map_err(|error| At::new(error).field(name))
+    }
+}
+
+impl<T: Ord + Copy> Named<Value<T>> {
+    /// Like [`Value::must_be_in`], with the error [`At`] the name.
+    pub fn must_be_in(self, range: &Range<T>) -> Result<T, At<Outside<T>>> {
+        let name = self.name;
+        self.builder
+            .must_be_in(range)
+            .map_err(|error| At::new(error).field(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn provenance_names_the_builder() {
+        assert_eq!(
+            super::expect(1).but_got(2).unwrap_err().provenance(),
+            "expect"
+        );
+        let error = super::value(9)
+            .named("jobs")
+            .must_be_in(&(1..4))
+            .unwrap_err();
+        assert_eq!(error.error().provenance(), "value");
+        assert_eq!(error.path().to_string(), "jobs");
+    }
+
+    #[test]
+    fn ranges_include_the_start_only() {
+        let range = 1..4;
+        assert_eq!(super::value(0).must_be_in(&range).unwrap_err().value, 0);
+        assert_eq!(super::value(1).must_be_in(&range), Ok(1));
+        assert_eq!(super::value(3).must_be_in(&range), Ok(3));
+        assert!(super::value(4).named("jobs").must_be_in(&range).is_err());
+    }
+}
@@ -0,0 +1,138 @@
+//! Adding context to the errors of futures, so that `.await` chains
+//! read like synchronous code.
+
+use crate::Report;
+use core::fmt::Display;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// Adds context to the error of a future that returns a `Result`,
+/// turning it into a [`Report`] with [`Report::attach`].
+///
+/// # Examples
+/// ```rust
+/// use goof::FutureGoofExt;
+/// use std::future::Future;
+/// use std::pin::pin;
+/// use std::task::{Context, Poll, Waker};
+///
+/// async fn port() -> Result<u32, goof::Outside<u32>> {
+///     goof::assert_in(&0, &(1..65535))
+/// }
+///
+/// let future = pin!(port().context("while reading app.toml"));
+/// let Poll::Ready(result) = future.poll(&mut Context::from_waker(Waker::noop())) else {
+///     unreachable!()
+/// };
+/// assert_eq!(result.unwrap_err().context(), ["while reading app.toml"]);
+/// ```
+pub trait FutureGoofExt<T, E>: Future<Output = Result<T, E>> + Sized {
+    /// Attach `context` to the error, if there is one.
+    fn context<C: Display>(self, context: C) -> WithContext<Self, C> {
+        WithContext {
+            future: self,
+            context: Some(context),
+        }
+    }
+
+    /// Attach the context returned by `context` to the error, only
+    /// working it out if there is one.
+    fn with_context<C: Display, F: FnOnce() -> C>(self, context: F) -> WithContextFn<Self, F> {
+        WithContextFn {
+            future: self,
+            context: Some(context),
+        }
+    }
+}
+
+impl<T, E: Into<Report>, F: Future<Output = Result<T, E>>> FutureGoofExt<T, E> for F {}
+
+/// Future returned by [`FutureGoofExt::context`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct WithContext<F, C> {
+    future: F,
+    context: Option<C>,
+}
+
+/// Future returned by [`FutureGoofExt::with_context`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct WithContextFn<F, G> {
+    future: F,
+    context: Option<G>,
+}
+
+impl<T, E, F, C> Future for WithContext<F, C>
+where
+    E: Into<Report>,
+    F: Future<Output = Result<T, E>>,
+    C: Display,
+{
+    type Output = Result<T, Report>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: `future` is pinned along with `self` and never moved
+        // out, and `context` is never pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+        future.poll(cx).map(|result| {
+            result.map_err(|error| {
+                let context = this.context.take().expect("polled after completion");
+                error.into().attach(context)
+            })
+        })
+    }
+}
+
+impl<T, E, F, C, G> Future for WithContextFn<F, G>
+where
+    E: Into<Report>,
+    F: Future<Output = Result<T, E>>,
+    C: Display,
+    G: FnOnce() -> C,
+{
+    type Output = Result<T, Report>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: as in `WithContext::poll`.
+        let this = unsafe { self.get_unchecked_mut() };
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+        future.poll(cx).map(|result| {
+            result.map_err(|error| {
+                let context = this.context.take().expect("polled after completion");
+                error.into().attach(context())
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FutureGoofExt;
+    use core::cell::Cell;
+    use core::future::{ready, Future};
+    use core::pin::pin;
+    use core::task::{Context, Poll, Waker};
+
+    #[test]
+    fn context_is_lazy_and_nests() {
+        let called = Cell::new(false);
+        let ok = pin!(ready(Ok::<_, crate::Cancelled>(1)).with_context(|| {
+            called.set(true);
+            "never"
+        }));
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(matches!(ok.poll(&mut cx), Poll::Ready(Ok(1))));
+        assert!(!called.get());
+
+        let failed = pin!(ready(Err::<(), _>(crate::Cancelled::new()))
+            .context("inner")
+            .with_context(|| "outer"));
+        let Poll::Ready(Err(report)) = failed.poll(&mut cx) else {
+            panic!("expected an error")
+        };
+        assert_eq!(report.context(), ["inner", "outer"]);
+    }
+}
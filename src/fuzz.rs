@@ -0,0 +1,494 @@
+//! Entry point for fuzzing the rendering of every error.
+
+use crate::arith::{DivisionByZero, Op, Overflow, Underflow};
+use crate::compare::Limit;
+use crate::config::ConfigGoof;
+use crate::partial::Against;
+use crate::retry::RetriesExhausted;
+use crate::schema::{DuplicateKey, InvalidData, MissingField, UnknownField};
+use crate::{
+    ArityMismatch, BadText, Bounds, Cancelled, Duplicate, EmptyRange, Endian, EndiannessMismatch,
+    Envelope, ErrorCode, Exceeded, ExpectedOneOf, FailedPredicate, Fields, Goof,
+    InsufficientPermission, Interval, InvalidTransition, LengthMismatch, LengthOutside,
+    MagicMismatch, Misaligned, Mismatch, MultiError, NotComparable, NotDisjoint, NotMultipleOf,
+    NotPositive, NotSubset, Outside, ParseGoof, PatternMismatch, RateLimited, SliceMismatch,
+    Timeout, TooLarge, TooShort, TooSmall, Unknown, UnknownBits, UnsortedAt, Zero,
+};
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display, Write};
+use core::ops::Bound;
+use core::time::Duration;
+
+/// Build an instance of every kind of error from arbitrary `bytes`
+/// and render it in every supported way, discarding the output.
+///
+/// Rendering an error must never panic, because the rendering usually
+/// happens while something else has already gone wrong.  This
+/// function is meant to be called from a fuzz target, e.g. with
+/// `cargo fuzz`:
+///
+/// ```rust,ignore
+/// libfuzzer_sys::fuzz_target!(|bytes: &[u8]| goof::fuzz_check(bytes));
+/// ```
+pub fn fuzz_check(bytes: &[u8]) {
+    build(bytes, &mut check);
+}
+
+/// An error as `fuzz_check` renders it.
+trait Fuzzed: Fields + ErrorCode + Display + Debug {}
+
+impl<E: Fields + ErrorCode + Display + Debug> Fuzzed for E {}
+
+fn check(error: &dyn Fuzzed) {
+    let _ = write!(Sink, "{} {:#} {:?}", error, error, error);
+    error.for_each_field(&mut |name, value| {
+        let _ = write!(Sink, "{}{}", name, value);
+    });
+    let _ = write!(Sink, "{:?}", error.help());
+    let _ = write!(Sink, "{}", Envelope::new().error("", error));
+}
+
+/// Hand every error built from `bytes` to `visit`, with every
+/// [`Interval`] for the ones that have one.
+fn build(bytes: &[u8], visit: &mut dyn FnMut(&dyn Fuzzed)) {
+    let mut input = Input(bytes);
+    let bytes = input.take().to_vec();
+    let text = String::from_utf8_lossy(&bytes).into_owned();
+    let words: Vec<&str> = text.split(' ').collect();
+    let chars: Vec<char> = text.chars().collect();
+
+    let (start, end, value) = (input.int(), input.int(), input.int());
+    let (low, high, real) = (input.float(), input.float(), input.float());
+    for interval in Interval::ALL {
+        visit(&Outside {
+            range: start..end,
+            interval,
+            value,
+            provenance: "fuzz_check",
+        });
+        visit(&Outside {
+            range: low..high,
+            interval,
+            value: real,
+            provenance: "fuzz_check",
+        });
+        visit(&EmptyRange {
+            range: start..end,
+            interval,
+            provenance: "fuzz_check",
+        });
+        visit(&NotComparable {
+            value: real,
+            against: Against::Range(low..high, interval),
+            provenance: "fuzz_check",
+        });
+    }
+    let (upper, strict) = (input.flag(), input.flag());
+    visit(&NotComparable {
+        value: real,
+        against: Against::Limit(Limit {
+            bound: low,
+            upper,
+            strict,
+        }),
+        provenance: "fuzz_check",
+    });
+    visit(&TooLarge {
+        max: low,
+        actual: real,
+        strict,
+        provenance: "fuzz_check",
+    });
+    visit(&TooSmall {
+        min: start,
+        actual: value,
+        strict,
+        provenance: "fuzz_check",
+    });
+    visit(&Mismatch {
+        expected: input.int(),
+        actual: input.int(),
+        provenance: "fuzz_check",
+    });
+    visit(&LengthMismatch {
+        expected: input.index(),
+        actual: input.index(),
+        provenance: "fuzz_check",
+    });
+    visit(&LengthOutside {
+        range: Bounds(input.bound(), input.bound()),
+        actual: input.index(),
+        provenance: "fuzz_check",
+    });
+    visit(&UnsortedAt {
+        index: input.index(),
+        prev: real,
+        next: low,
+        provenance: "fuzz_check",
+    });
+    visit(&NotPositive {
+        value: real,
+        provenance: "fuzz_check",
+    });
+    visit(&Zero {
+        provenance: "fuzz_check",
+    });
+    visit(&NotMultipleOf {
+        value: start,
+        factor: end,
+        provenance: "fuzz_check",
+    });
+    visit(&Exceeded {
+        requested: start,
+        limit: end,
+        over: value,
+        provenance: "fuzz_check",
+    });
+    visit(&Misaligned {
+        required: input.index(),
+        actual_offset: input.index(),
+        provenance: "fuzz_check",
+    });
+    visit(&UnknownBits {
+        known_mask: input.int() as u64,
+        value: input.int() as u64,
+        unknown: input.int() as u64,
+        provenance: "fuzz_check",
+    });
+    visit(&TooShort {
+        needed: input.index(),
+        available: input.index(),
+        provenance: "fuzz_check",
+    });
+    visit(&ArityMismatch {
+        callee: "fuzz",
+        expected: input.index(),
+        actual: input.index(),
+        provenance: "fuzz_check",
+    });
+    visit(&EndiannessMismatch {
+        expected: if input.flag() {
+            Endian::Big
+        } else {
+            Endian::Little
+        },
+        found: if input.flag() {
+            Endian::Big
+        } else {
+            Endian::Little
+        },
+        provenance: "fuzz_check",
+    });
+    visit(&InsufficientPermission {
+        required: input.int() as u64,
+        held: input.int() as u64,
+        missing: input.int() as u64,
+        provenance: "fuzz_check",
+    });
+    let ops = [Op::Add, Op::Sub, Op::Mul, Op::Div];
+    let op = ops[usize::from(input.byte() % 4)];
+    visit(&Overflow::new(start, op, end, "fuzz_check"));
+    visit(&Underflow::new(start, op, end, "fuzz_check"));
+    visit(&DivisionByZero {
+        dividend: value,
+        provenance: "fuzz_check",
+    });
+    let (elapsed, limit) = (input.duration(), input.duration());
+    visit(&Timeout {
+        elapsed,
+        limit,
+        provenance: "fuzz_check",
+    });
+    visit(&RateLimited {
+        limit: input.int() as u64,
+        window: elapsed,
+        retry_after: input.flag().then_some(limit),
+        provenance: "fuzz_check",
+    });
+    visit(&RetriesExhausted {
+        attempts: input.int() as u32,
+        last_error: Zero {
+            provenance: "fuzz_check",
+        },
+        provenance: "fuzz_check",
+    });
+    visit(&Cancelled {
+        reason: input.flag().then_some("fuzz"),
+        provenance: "fuzz_check",
+    });
+
+    let mut invalid = bytes.clone();
+    invalid.push(0xff);
+    if let Err(error) = String::from_utf8(invalid) {
+        visit(&BadText {
+            error: error.utf8_error(),
+            provenance: "fuzz_check",
+        });
+    }
+    visit(&Mismatch {
+        expected: text.as_str(),
+        actual: "",
+        provenance: "fuzz_check",
+    });
+    visit(&Unknown {
+        knowns: None,
+        value: text.as_str(),
+        provenance: "fuzz_check",
+    });
+    visit(&Unknown {
+        knowns: Some(&words[..]),
+        value: words[0],
+        provenance: "fuzz_check",
+    });
+    visit(&PatternMismatch {
+        pattern: "Some(_)",
+        value: text.clone(),
+        provenance: "fuzz_check",
+    });
+    visit(&FailedPredicate {
+        value: text.as_str(),
+        description: "is fuzzed",
+        provenance: "fuzz_check",
+    });
+    visit(&Duplicate {
+        value: text.as_str(),
+        first_index: input.index(),
+        second_index: input.index(),
+        provenance: "fuzz_check",
+    });
+    visit(&NotSubset {
+        missing: words.clone(),
+        provenance: "fuzz_check",
+    });
+    visit(&NotDisjoint {
+        common: words.clone(),
+        provenance: "fuzz_check",
+    });
+    visit(&SliceMismatch {
+        index: input.index(),
+        expected: chars.first().copied(),
+        actual: chars.last().copied(),
+        expected_len: input.index(),
+        actual_len: input.index(),
+        provenance: "fuzz_check",
+    });
+    visit(&MagicMismatch {
+        expected: b"\x89PNG",
+        actual: bytes.clone(),
+        provenance: "fuzz_check",
+    });
+    visit(&ExpectedOneOf {
+        expected: &chars[..],
+        found: chars.first().copied().unwrap_or_default(),
+        provenance: "fuzz_check",
+    });
+    visit(&ParseGoof {
+        input: text.as_str(),
+        expected: text
+            .split_whitespace()
+            .map(|word| String::from(word).into())
+            .collect(),
+        provenance: "fuzz_check",
+    });
+    visit(&MissingField {
+        container: "Fuzz",
+        field: "fuzz",
+        provenance: "fuzz_check",
+    });
+    visit(&UnknownField {
+        container: "Fuzz",
+        field: text.clone(),
+        expected: &["fuzz"],
+        provenance: "fuzz_check",
+    });
+    visit(&DuplicateKey {
+        container: "Fuzz",
+        key: text.clone(),
+        provenance: "fuzz_check",
+    });
+    visit(&InvalidData {
+        expected: text.clone(),
+        unexpected: words[0].into(),
+        provenance: "fuzz_check",
+    });
+    visit(&InvalidTransition {
+        from: words[0],
+        to: text.as_str(),
+        allowed: &words[..],
+        provenance: "fuzz_check",
+    });
+    visit(&Goof {
+        message: Cow::Borrowed(&text),
+        provenance: "fuzz_check",
+    });
+    visit(&MultiError::from(vec![
+        Mismatch {
+            expected: start,
+            actual: end,
+            provenance: "fuzz_check",
+        };
+        usize::from(input.byte() % 4)
+    ]));
+
+    let mut config = ConfigGoof::new("fuzz");
+    for word in &words {
+        config.push(
+            *word,
+            DuplicateKey {
+                container: "Fuzz",
+                key: String::from(*word),
+                provenance: "fuzz_check",
+            },
+        );
+    }
+    visit(&config);
+
+    #[cfg(feature = "std")]
+    {
+        use crate::env::{InvalidEnv, MissingEnv};
+        use crate::{IoGoof, PanicLocation, Panicked};
+
+        visit(&Panicked {
+            message: text.clone(),
+            location: input.flag().then(|| PanicLocation {
+                file: text.clone(),
+                line: input.int() as u32,
+                column: input.int() as u32,
+            }),
+        });
+        visit(&MissingEnv {
+            name: "FUZZ",
+            not_unicode: input.flag(),
+        });
+        visit(&InvalidEnv {
+            name: "FUZZ",
+            value: text.clone(),
+            error: text.parse::<u8>().err(),
+        });
+        visit(&IoGoof::new(std::io::Error::other(text.clone())).on_path(&text));
+    }
+
+    #[cfg(feature = "semver")]
+    {
+        use crate::semver::{Version, VersionOutside};
+
+        if let Ok(requirement) = text.parse() {
+            visit(&VersionOutside {
+                requirement,
+                found: Version::new(start as u64, end as u64, value as u64),
+                provenance: "fuzz_check",
+            });
+        }
+    }
+}
+
+/// Reads values off the front of the fuzzer's input, yielding zeroes
+/// once it runs dry.
+struct Input<'a>(&'a [u8]);
+
+impl Input<'_> {
+    fn int(&mut self) -> i64 {
+        let mut buffer = [0; 8];
+        let len = self.0.len().min(8);
+        buffer[..len].copy_from_slice(&self.0[..len]);
+        self.0 = &self.0[len..];
+        i64::from_le_bytes(buffer)
+    }
+
+    /// Any `f64`, including the infinities and `NaN`s.
+    fn float(&mut self) -> f64 {
+        f64::from_bits(self.int() as u64)
+    }
+
+    fn byte(&mut self) -> u8 {
+        let (&byte, rest) = self.0.split_first().unwrap_or((&0, &[]));
+        self.0 = rest;
+        byte
+    }
+
+    fn flag(&mut self) -> bool {
+        self.byte() & 1 == 1
+    }
+
+    fn index(&mut self) -> usize {
+        self.int() as usize
+    }
+
+    fn duration(&mut self) -> Duration {
+        Duration::new(self.int() as u64, self.int() as u32)
+    }
+
+    fn bound(&mut self) -> Bound<usize> {
+        match self.byte() % 3 {
+            0 => Bound::Included(self.index()),
+            1 => Bound::Excluded(self.index()),
+            _ => Bound::Unbounded,
+        }
+    }
+
+    /// A length-prefixed run of bytes.
+    fn take(&mut self) -> &[u8] {
+        let len = self
+            .0
+            .first()
+            .map_or(0, |&len| usize::from(len))
+            .min(self.0.len().saturating_sub(1));
+        let (taken, rest) = self.0.get(1..).unwrap_or_default().split_at(len);
+        self.0 = rest;
+        taken
+    }
+}
+
+struct Sink;
+
+impl Write for Sink {
+    fn write_str(&mut self, _: &str) -> core::fmt::Result {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build, fuzz_check};
+    use crate::code::Registry;
+    use alloc::collections::BTreeSet;
+
+    #[test]
+    fn rendering_never_panics() {
+        fuzz_check(&[]);
+        fuzz_check(&[0xff; 64]);
+        // A cheap deterministic corpus: inverted and empty ranges,
+        // values on every boundary, invalid UTF-8 and empty knowns.
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        for len in 0..256 {
+            let bytes: Vec<u8> = (0..len)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    (state % 4) as u8 * 0x55
+                })
+                .collect();
+            fuzz_check(&bytes);
+        }
+    }
+
+    #[test]
+    fn builds_every_registered_kind() {
+        let mut kinds = BTreeSet::new();
+        build(b"\x04^1.4", &mut |error| {
+            kinds.insert(error.kind());
+        });
+        for (_, kind) in Registry::new().iter() {
+            let gated = match kind {
+                "panicked" | "missing_env" | "invalid_env" | "io" => !cfg!(feature = "std"),
+                "version_outside" => !cfg!(feature = "semver"),
+                _ => false,
+            };
+            assert!(gated || kinds.contains(kind), "no {}", kind);
+        }
+    }
+}
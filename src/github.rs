@@ -0,0 +1,121 @@
+//! GitHub Actions workflow commands, so that a validator running in
+//! CI can annotate the lines of a pull request it complains about.
+
+use crate::{render, Severity, Spanned};
+use core::fmt::{Display, Formatter, Write};
+
+/// Render `error`, found in `file`, as an annotation at the level
+/// `error`.
+///
+/// The line and column are only known if the error was
+/// [`Spanned::located_in`] its source; otherwise the whole file is
+/// annotated.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::{github, Severity, Span, Spanned};
+///
+/// let source = "[server]\nport = 0\n";
+/// let error = goof::assert_in(&0, &(1..65535)).unwrap_err();
+/// let error = Spanned::new(error, Span::new(16, 1)).located_in(source);
+/// assert_eq!(
+///     github::annotate("app.toml", &error).to_string(),
+///     "::error file=app.toml,line=2,col=8::Value 0 is outside the allowed range \
+///      (1, 65535] (must be greater than 1)"
+/// );
+///
+/// let hint = Spanned::new(goof::Goof::from("50%\nof the time"), Span::new(0, 1));
+/// assert_eq!(
+///     github::annotate("a, b.txt", &hint).severity(Severity::Hint).to_string(),
+///     "::notice file=a%2C b.txt::50%25%0Aof the time"
+/// );
+/// ```
+pub fn annotate<'a, E: Display>(file: &'a str, error: &'a Spanned<E>) -> Annotation<'a, E> {
+    Annotation {
+        file,
+        error,
+        severity: Severity::Error,
+        title: None,
+    }
+}
+
+/// `Display` adapter returned by [`annotate`].
+pub struct Annotation<'a, E> {
+    file: &'a str,
+    error: &'a Spanned<E>,
+    severity: Severity,
+    title: Option<&'a str>,
+}
+
+impl<'a, E> Annotation<'a, E> {
+    /// Annotate as a `notice` for a [`Severity::Hint`], a `warning`
+    /// for a [`Severity::Warning`], and an `error` otherwise.
+    pub fn severity(self, severity: Severity) -> Self {
+        Self { severity, ..self }
+    }
+
+    /// The heading of the annotation, instead of the default one.
+    pub fn title(self, title: &'a str) -> Self {
+        Self {
+            title: Some(title),
+            ..self
+        }
+    }
+}
+
+impl<E: Display> Display for Annotation<'_, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let command = match self.severity {
+            Severity::Hint => "notice",
+            Severity::Warning => "warning",
+            Severity::Error | Severity::Fatal => "error",
+        };
+        write!(f, "::{} file=", command)?;
+        write_escaped(f, self.file, true)?;
+        if let Some(position) = self.error.position() {
+            write!(f, ",line={},col={}", position.line, position.column)?;
+        }
+        if let Some(title) = self.title {
+            f.write_str(",title=")?;
+            write_escaped(f, title, true)?;
+        }
+        f.write_str("::")?;
+        write_escaped(f, &render(self.error.error(), false), false)
+    }
+}
+
+/// Escape what the runner would otherwise read as the end of the
+/// command, or, in a property, of the property.
+fn write_escaped(f: &mut Formatter<'_>, text: &str, property: bool) -> core::fmt::Result {
+    for c in text.chars() {
+        match c {
+            '%' => f.write_str("%25")?,
+            '\r' => f.write_str("%0D")?,
+            '\n' => f.write_str("%0A")?,
+            ':' if property => f.write_str("%3A")?,
+            ',' if property => f.write_str("%2C")?,
+            c => f.write_char(c)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Severity, Span, Spanned};
+    use alloc::string::ToString;
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn title_and_warning() {
+        let error = Spanned::new(crate::Cancelled::new(), Span::new(0, 0)).located_in("");
+        assert_eq!(
+            super::annotate("C:\\x", &error)
+                .severity(Severity::Warning)
+                .title("lint: cancelled")
+                .to_string(),
+            "::warning file=C%3A\\x,line=1,col=1,title=lint%3A cancelled::Cancelled"
+        );
+    }
+}
@@ -0,0 +1,101 @@
+//! A single object-safe trait for holding errors of different types
+//! side by side.
+
+use crate::envelope::write_escaped;
+use crate::{render, ErrorCode, Fields};
+use alloc::string::String;
+use core::fmt::{Debug, Display};
+
+/// What every error of this crate can do, as one trait that can be
+/// made into an object, for collections such as `Vec<Box<dyn Goofy>>`.
+///
+/// It is implemented for everything with [`Fields`] and an
+/// [`ErrorCode`].  Other errors can implement it by hand, and need not
+/// have a payload.  Where [`Fields`] or [`ErrorCode`] are also in
+/// scope, call the methods as `Goofy::kind(&error)`.
+///
+/// # Examples
+/// ```rust
+/// use goof::Goofy;
+///
+/// let goofs: Vec<Box<dyn Goofy>> = vec![
+///     Box::new(goof::assert_eq(&32, &0).unwrap_err()),
+///     Box::new(goof::assert_in(&0, &(1..5)).unwrap_err()),
+/// ];
+/// let kinds: Vec<_> = goofs.iter().map(|goof| (goof.kind(), goof.code())).collect();
+/// assert_eq!(kinds, [("mismatch", 1), ("outside", 2)]);
+/// assert_eq!(
+///     goofs[0].payload_json().as_deref(),
+///     Some(r#"{"expected":"32","actual":"0"}"#)
+/// );
+/// ```
+pub trait Goofy: Display + Debug {
+    /// As in [`Fields::kind`].
+    fn kind(&self) -> &'static str;
+
+    /// As in [`ErrorCode::code`].
+    fn code(&self) -> u32;
+
+    /// The payload as a JSON object, with every value as a string, or
+    /// `None` if the error does not expose one.
+    fn payload_json(&self) -> Option<String> {
+        None
+    }
+}
+
+impl<E: Fields + ErrorCode + Display + Debug> Goofy for E {
+    fn kind(&self) -> &'static str {
+        Fields::kind(self)
+    }
+
+    fn code(&self) -> u32 {
+        ErrorCode::code(self)
+    }
+
+    fn payload_json(&self) -> Option<String> {
+        let mut json = String::from("{");
+        self.for_each_field(&mut |name, value| {
+            if json.len() > 1 {
+                json.push(',');
+            }
+            let _ = write_escaped(&mut json, name);
+            json.push(':');
+            let _ = write_escaped(&mut json, &render(value, false));
+        });
+        json.push('}');
+        Some(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Goofy;
+    use alloc::boxed::Box;
+
+    #[derive(Debug)]
+    struct Custom;
+
+    impl core::fmt::Display for Custom {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.write_str("custom")
+        }
+    }
+
+    impl Goofy for Custom {
+        fn kind(&self) -> &'static str {
+            "custom"
+        }
+
+        fn code(&self) -> u32 {
+            1000
+        }
+    }
+
+    #[test]
+    fn payload_is_optional() {
+        let custom: Box<dyn Goofy + Send + Sync> = Box::new(Custom);
+        assert_eq!(custom.payload_json(), None);
+        let cancelled: Box<dyn Goofy> = Box::new(crate::Cancelled::new());
+        assert_eq!(cancelled.payload_json().as_deref(), Some("{}"));
+    }
+}
@@ -0,0 +1,355 @@
+//! Mapping errors onto gRPC statuses.
+
+use crate::{render, At, Caused, ErrorCode, Fields, Mismatch, Outside, Spanned, Unknown};
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+/// The `domain` of the `google.rpc.ErrorInfo` in [`GrpcGoof::details`].
+pub const DOMAIN: &str = "goof";
+
+/// The subset of gRPC status codes that the crate's errors map to.
+///
+/// The discriminants are the ones from the gRPC specification, so
+/// `tonic::Code::from(code as i32)` converts losslessly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum GrpcCode {
+    Cancelled = 1,
+    InvalidArgument = 3,
+    DeadlineExceeded = 4,
+    PermissionDenied = 7,
+    ResourceExhausted = 8,
+    FailedPrecondition = 9,
+    OutOfRange = 11,
+    Internal = 13,
+    Unavailable = 14,
+}
+
+/// An error that can be reported as a gRPC status.
+///
+/// Every error in the crate implements it, and wrappers report the code
+/// of the error they wrap.
+///
+/// With the `tonic` feature, every implementation also converts into
+/// a `tonic::Status`, so that services can `?` it.
+///
+/// # Examples
+/// ```rust
+/// use goof::grpc::{GrpcCode, GrpcGoof};
+///
+/// let error = goof::assert_in(&0, &(1..10)).unwrap_err();
+/// assert_eq!(error.grpc_code(), GrpcCode::OutOfRange);
+/// assert!(!error.details().is_empty());
+/// ```
+pub trait GrpcGoof: Fields + ErrorCode + Display {
+    fn grpc_code(&self) -> GrpcCode {
+        GrpcCode::InvalidArgument
+    }
+
+    /// The `details` of the status, as the rich error model has them:
+    /// a protobuf `google.rpc.Status` with one `google.rpc.ErrorInfo`,
+    /// which clients such as `tonic-types` decode.
+    ///
+    /// The `reason` of the `ErrorInfo` is the [`Fields::kind`] in upper
+    /// case, its `domain` is [`DOMAIN`], and its `metadata` has the
+    /// [`ErrorCode`] as `code` and every payload field.
+    fn details(&self) -> Vec<u8> {
+        let mut info = Vec::new();
+        string(&mut info, 1, &self.kind().to_ascii_uppercase());
+        string(&mut info, 2, DOMAIN);
+        let mut entry = Vec::new();
+        string(&mut entry, 1, "code");
+        string(&mut entry, 2, &render(&self.code(), false));
+        bytes(&mut info, 3, &entry);
+        self.for_each_field(&mut |name, value| {
+            entry.clear();
+            string(&mut entry, 1, name);
+            string(&mut entry, 2, &render(value, false));
+            bytes(&mut info, 3, &entry);
+        });
+
+        let mut any = Vec::new();
+        string(&mut any, 1, "type.googleapis.com/google.rpc.ErrorInfo");
+        bytes(&mut any, 2, &info);
+
+        let mut status = Vec::new();
+        // Field 1, `code`, as a varint.
+        status.push(1 << 3);
+        varint(&mut status, self.grpc_code() as u64);
+        string(&mut status, 2, &render(self, false));
+        bytes(&mut status, 3, &any);
+        status
+    }
+}
+
+fn varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Append a length-delimited protobuf field.
+fn bytes(out: &mut Vec<u8>, field: u8, value: &[u8]) {
+    out.push(field << 3 | 2);
+    varint(out, value.len() as u64);
+    out.extend_from_slice(value);
+}
+
+fn string(out: &mut Vec<u8>, field: u8, value: &str) {
+    bytes(out, field, value.as_bytes());
+}
+
+/// `GrpcGoof` with a fixed code, and with the `tonic` feature
+/// `From<_> for tonic::Status`, for each error given.
+macro_rules! grpc_goof {
+    ($($(#[$attr:meta])* $code:ident => [$($generics:tt)*] $error:ty,)*) => {
+        $($(#[$attr])*
+        impl<$($generics)*> GrpcGoof for $error {
+            fn grpc_code(&self) -> GrpcCode {
+                GrpcCode::$code
+            }
+        }
+
+        $(#[$attr])*
+        #[cfg(feature = "tonic")]
+        impl<$($generics)*> From<$error> for ::tonic::Status {
+            fn from(error: $error) -> Self {
+                tonic::status(&error)
+            }
+        })*
+    };
+}
+
+grpc_goof! {
+    InvalidArgument => [T: Display + Copy + Eq] Mismatch<T>,
+    InvalidArgument => [T: Display + Eq] Unknown<'_, T>,
+    InvalidArgument => [] crate::Goof<'_>,
+    InvalidArgument => [const N: usize] crate::InlineGoof<N>,
+    InvalidArgument => [] crate::PatternMismatch,
+    InvalidArgument => [] crate::LengthMismatch,
+    InvalidArgument => [] crate::MagicMismatch,
+    InvalidArgument => [] crate::ArityMismatch,
+    InvalidArgument => [] crate::Misaligned,
+    InvalidArgument => [] crate::EndiannessMismatch,
+    InvalidArgument => [] crate::TooShort,
+    InvalidArgument => [] crate::BadText,
+    InvalidArgument => [T: Display] crate::ExpectedOneOf<'_, T>,
+    InvalidArgument => [I: Display] crate::ParseGoof<I>,
+    InvalidArgument => [] crate::schema::MissingField,
+    InvalidArgument => [] crate::schema::UnknownField,
+    InvalidArgument => [] crate::schema::DuplicateKey,
+    InvalidArgument => [] crate::schema::InvalidData,
+    InvalidArgument => [] crate::schema::DeserGoof,
+    InvalidArgument => [T: Display] crate::EmptyRange<T>,
+    InvalidArgument => [T: Display] crate::NotComparable<T>,
+    InvalidArgument => [T: Display] crate::NotPositive<T>,
+    InvalidArgument => [] crate::Zero,
+    InvalidArgument => [T: Display] crate::NotMultipleOf<T>,
+    InvalidArgument => [T: Display] crate::FailedPredicate<T>,
+    InvalidArgument => [T: Display] crate::UnsortedAt<T>,
+    InvalidArgument => [T: Display] crate::Duplicate<T>,
+    InvalidArgument => [T: Display] crate::SliceMismatch<T>,
+    InvalidArgument => [T: Display] crate::NotSubset<T>,
+    InvalidArgument => [T: Display] crate::NotDisjoint<T>,
+    InvalidArgument => [T: crate::ToHex] crate::UnknownBits<T>,
+    InvalidArgument => [T: Display] crate::arith::DivisionByZero<T>,
+    InvalidArgument => [T: Display] crate::arith::ArithGoof<T>,
+    InvalidArgument => [E: core::error::Error + Fields + Display + 'static] crate::MultiError<E>,
+    OutOfRange => [T: Display + PartialOrd + Copy] Outside<T>,
+    OutOfRange => [T: Display + PartialOrd + Copy] crate::RangeGoof<T>,
+    OutOfRange => [T: Display + PartialOrd + Copy] crate::BoundGoof<T>,
+    OutOfRange => [T: Display] crate::TooLarge<T>,
+    OutOfRange => [T: Display] crate::TooSmall<T>,
+    OutOfRange => [] crate::LengthOutside,
+    OutOfRange => [T: Display] crate::arith::Overflow<T>,
+    OutOfRange => [T: Display] crate::arith::Underflow<T>,
+    #[cfg(feature = "semver")]
+    OutOfRange => [] crate::semver::VersionOutside,
+    FailedPrecondition => [S: Display] crate::InvalidTransition<'_, S>,
+    PermissionDenied => [P: crate::Permissions] crate::InsufficientPermission<P>,
+    ResourceExhausted => [T: Display] crate::Exceeded<T>,
+    ResourceExhausted => [] crate::RateLimited,
+    Internal => [] crate::config::ConfigGoof,
+    #[cfg(feature = "std")]
+    Internal => [] crate::Panicked,
+    #[cfg(feature = "std")]
+    Internal => [] crate::IoGoof,
+    #[cfg(feature = "std")]
+    Internal => [] crate::env::MissingEnv,
+    #[cfg(feature = "std")]
+    Internal => [E] crate::env::InvalidEnv<E>,
+    #[cfg(feature = "std")]
+    Internal => [E] crate::env::EnvGoof<E>,
+    Cancelled => [] crate::Cancelled,
+    DeadlineExceeded => [] crate::Timeout,
+    Unavailable => [E] crate::retry::RetriesExhausted<E>,
+}
+
+impl<E: GrpcGoof> GrpcGoof for crate::Diagnostic<E> {
+    fn grpc_code(&self) -> GrpcCode {
+        self.error().grpc_code()
+    }
+}
+
+impl<E: GrpcGoof> GrpcGoof for crate::WithTransience<E> {
+    fn grpc_code(&self) -> GrpcCode {
+        self.error().grpc_code()
+    }
+}
+
+impl<E: GrpcGoof, S: ?Sized> GrpcGoof for Caused<E, S> {
+    fn grpc_code(&self) -> GrpcCode {
+        self.error().grpc_code()
+    }
+}
+
+impl<E: GrpcGoof> GrpcGoof for Spanned<E> {
+    fn grpc_code(&self) -> GrpcCode {
+        self.error().grpc_code()
+    }
+}
+
+impl<E: GrpcGoof> GrpcGoof for At<E> {
+    fn grpc_code(&self) -> GrpcCode {
+        self.error().grpc_code()
+    }
+}
+
+#[cfg(feature = "tonic")]
+mod tonic {
+    use super::GrpcGoof;
+    use crate::{render, At, Caused, Diagnostic, Fields, Spanned, WithTransience};
+    use ::tonic::{Code, Status};
+    use core::fmt::Display;
+
+    pub(super) fn status(error: &impl GrpcGoof) -> Status {
+        Status::with_details(
+            Code::from(error.grpc_code() as i32),
+            render(error, false),
+            error.details().into(),
+        )
+    }
+
+    macro_rules! into_status {
+        ($([$($generics:tt)*] $error:ty,)*) => {
+            $(impl<$($generics)*> From<$error> for Status {
+                fn from(error: $error) -> Self {
+                    status(&error)
+                }
+            })*
+        };
+    }
+
+    into_status! {
+        [E: GrpcGoof, S: ?Sized] Caused<E, S>,
+        [E: GrpcGoof] Diagnostic<E>,
+        [E: GrpcGoof] WithTransience<E>,
+        [E: GrpcGoof] Spanned<E>,
+        [E: GrpcGoof + Fields + Display] At<E>,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GrpcCode, GrpcGoof};
+
+    #[test]
+    fn codes() {
+        assert_eq!(
+            crate::assert_eq(&1, &2).unwrap_err().grpc_code(),
+            GrpcCode::InvalidArgument
+        );
+        assert_eq!(
+            crate::assert_known(&[1], 2).unwrap_err().grpc_code(),
+            GrpcCode::InvalidArgument
+        );
+        assert_eq!(GrpcCode::OutOfRange as i32, 11);
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        assert_eq!(crate::goof("bad").grpc_code(), GrpcCode::InvalidArgument);
+        assert_eq!(
+            crate::assert_nonzero(0).unwrap_err().grpc_code(),
+            GrpcCode::InvalidArgument
+        );
+        let mut errors = crate::MultiError::new();
+        errors.push(crate::assert_nonzero(0).unwrap_err());
+        assert_eq!(errors.grpc_code(), GrpcCode::InvalidArgument);
+    }
+
+    #[test]
+    fn out_of_range() {
+        assert_eq!(
+            crate::assert_le(&3, &2).unwrap_err().grpc_code(),
+            GrpcCode::OutOfRange
+        );
+        assert_eq!(
+            crate::assert_len_in([1, 2], 3..5).unwrap_err().grpc_code(),
+            GrpcCode::OutOfRange
+        );
+    }
+
+    #[test]
+    fn refusals() {
+        assert_eq!(
+            crate::assert_permitted(0b10_u8, 0b01)
+                .unwrap_err()
+                .grpc_code(),
+            GrpcCode::PermissionDenied
+        );
+        assert_eq!(
+            crate::assert_transition(1, 3, &[2])
+                .unwrap_err()
+                .grpc_code(),
+            GrpcCode::FailedPrecondition
+        );
+        assert_eq!(
+            crate::assert_capacity(11, 10).unwrap_err().grpc_code(),
+            GrpcCode::ResourceExhausted
+        );
+    }
+
+    #[test]
+    fn server_failures() {
+        assert_eq!(
+            crate::config::ConfigGoof::new("app.toml").grpc_code(),
+            GrpcCode::Internal
+        );
+        let cancelled = crate::Diagnostic::new(crate::Severity::Error, crate::Cancelled::new());
+        assert_eq!(cancelled.grpc_code(), GrpcCode::Cancelled);
+    }
+
+    #[cfg(feature = "tonic")]
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn statuses() {
+        let error = crate::assert_in(&0, &(1..10)).unwrap_err();
+        let details = error.details();
+        let status = tonic::Status::from(error);
+        assert_eq!(status.code(), tonic::Code::OutOfRange);
+        assert_eq!(
+            status.message(),
+            "Value 0 is outside the allowed range (1, 10] (must be greater than 1)"
+        );
+        assert_eq!(status.details(), &details[..]);
+    }
+
+    #[cfg(feature = "tonic")]
+    #[test]
+    fn details_are_a_google_rpc_status() {
+        use tonic_types::StatusExt;
+
+        let status = tonic::Status::from(crate::assert_in(&0, &(1..10)).unwrap_err());
+        let details = status.get_error_details();
+        let info = details.error_info().expect("an ErrorInfo");
+        assert_eq!(info.reason, "OUTSIDE");
+        assert_eq!(info.domain, super::DOMAIN);
+        assert_eq!(info.metadata["code"], "2");
+        assert_eq!(info.metadata["start"], "1");
+        assert_eq!(info.metadata["value"], "0");
+        assert_eq!(info.metadata["interval"], "left_open");
+    }
+}
@@ -0,0 +1,103 @@
+//! Rendering values as hexadecimal, which is how hashes, checksums
+//! and magic numbers are usually written down.
+
+use crate::Mismatch;
+use core::fmt::{Display, Formatter};
+
+/// A value with a conventional hexadecimal rendering: integers as
+/// zero-padded `0x` literals of their full width, and bytes as a
+/// plain string of lowercase digit pairs.
+pub trait ToHex {
+    fn write_hex(&self, f: &mut Formatter<'_>) -> core::fmt::Result;
+}
+
+macro_rules! hex_integer {
+    ($($t:ty),*) => {
+        $(impl ToHex for $t {
+            fn write_hex(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                write!(f, "{:#0width$x}", self, width = 2 + 2 * core::mem::size_of::<$t>())
+            }
+        })*
+    };
+}
+
+hex_integer!(u8, u16, u32, u64, u128, usize);
+
+impl ToHex for [u8] {
+    fn write_hex(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for byte in self {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> ToHex for [u8; N] {
+    fn write_hex(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self[..].write_hex(f)
+    }
+}
+
+impl<T: ToHex + ?Sized> ToHex for &T {
+    fn write_hex(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (**self).write_hex(f)
+    }
+}
+
+impl<T: ToHex + Copy + Eq> Mismatch<T> {
+    /// Show the values in hexadecimal, e.g. for checksums.  This
+    /// also works for byte arrays, which have no `Display` of their
+    /// own.
+    ///
+    /// # Examples
+    /// ```rust
+    /// let error = goof::assert_eq(&0xcafe_f00d_u32, &0xdead_beef).unwrap_err();
+    /// assert_eq!(error.hex().to_string(), "Expected 0xcafef00d, but got 0xdeadbeef");
+    ///
+    /// let error = goof::assert_eq(&[0xab_u8, 0x01], &[0xab, 0x02]).unwrap_err();
+    /// assert_eq!(error.hex().to_string(), "Expected ab01, but got ab02");
+    /// ```
+    pub fn hex(&self) -> HexMismatch<'_, T> {
+        HexMismatch(self)
+    }
+}
+
+/// `Display` adapter returned by [`Mismatch::hex`].
+#[derive(Debug, Clone, Copy)]
+pub struct HexMismatch<'a, T: Copy + Eq>(&'a Mismatch<T>);
+
+impl<T: ToHex + Copy + Eq> Display for HexMismatch<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Expected ")?;
+        self.0.expected.write_hex(f)?;
+        f.write_str(", but got ")?;
+        self.0.actual.write_hex(f)
+    }
+}
+
+/// `Display` adapter for any [`ToHex`] value.
+///
+/// # Examples
+/// ```rust
+/// assert_eq!(goof::Hex(&[0xca_u8, 0xfe]).to_string(), "cafe");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Hex<'a, T: ?Sized>(pub &'a T);
+
+impl<T: ToHex + ?Sized> Display for Hex<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.0.write_hex(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Hex;
+
+    #[test]
+    fn integers_are_padded_to_their_width() {
+        assert_eq!(Hex(&0x0f_u8).to_string(), "0x0f");
+        assert_eq!(Hex(&1_u64).to_string(), "0x0000000000000001");
+        assert_eq!(Hex(&b"\x00\xff"[..]).to_string(), "00ff");
+    }
+}
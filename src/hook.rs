@@ -0,0 +1,141 @@
+//! A process-wide observer of every error that this crate creates.
+
+#[cfg(feature = "std")]
+use alloc::sync::Arc;
+#[cfg(feature = "std")]
+use core::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "std")]
+use std::sync::RwLock;
+
+/// What a hook is told about an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ErrorMeta {
+    /// As in [`crate::Fields::kind`].
+    pub kind: &'static str,
+    /// As in [`crate::ErrorCode::code`].
+    pub code: u32,
+    /// The name of the assertion or constructor that created the error,
+    /// such as `"assert_eq"` or `"Timeout::new"`.
+    pub provenance: &'static str,
+}
+
+// Shared, so that `observe` can call it after releasing the lock.
+#[cfg(feature = "std")]
+type Hook = Arc<dyn Fn(&ErrorMeta) + Send + Sync>;
+
+#[cfg(feature = "std")]
+static HOOK: RwLock<Option<Hook>> = RwLock::new(None);
+// Lets `observe` skip the lock entirely while no hook is set.
+#[cfg(feature = "std")]
+static HOOKED: AtomicBool = AtomicBool::new(false);
+
+/// Call `hook` every time this crate creates an error, replacing any
+/// previous hook.
+///
+/// This is meant for counting, sampling or logging every failure
+/// centrally.  The hook runs on the thread that created the error,
+/// before the error is returned, so it should be quick.
+///
+/// Errors count whether a failed assertion or a constructor created
+/// them, so `Goof::from("...")`, `InlineGoof::try_from`,
+/// `Timeout::new` and a panic caught by `catch` call the hook as well,
+/// with the constructor as the [`ErrorMeta::provenance`].  Only decoding an error that was created
+/// elsewhere, with `wire::Wire::decode`, does not.
+///
+/// # Examples
+/// ```rust
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// static FAILURES: AtomicUsize = AtomicUsize::new(0);
+/// goof::set_hook(|_meta| {
+///     FAILURES.fetch_add(1, Ordering::Relaxed);
+/// });
+///
+/// let _ = goof::assert_eq(&1, &2);
+/// assert_eq!(FAILURES.load(Ordering::Relaxed), 1);
+///
+/// let _ = goof::Goof::from("constructed");
+/// assert_eq!(FAILURES.load(Ordering::Relaxed), 2);
+/// # goof::take_hook();
+/// ```
+#[cfg(feature = "std")]
+pub fn set_hook(hook: impl Fn(&ErrorMeta) + Send + Sync + 'static) {
+    let mut slot = HOOK
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    *slot = Some(Arc::new(hook));
+    HOOKED.store(true, Ordering::Release);
+}
+
+/// Remove the hook set with [`set_hook`], returning whether there
+/// was one.
+#[cfg(feature = "std")]
+pub fn take_hook() -> bool {
+    let mut slot = HOOK
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    HOOKED.store(false, Ordering::Release);
+    slot.take().is_some()
+}
+
+/// Held by every test that sets or takes the hook, since there is only
+/// one per process and the tests run in parallel.
+#[cfg(all(test, feature = "std"))]
+pub(crate) static HOOK_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+pub(crate) fn observe(kind: &'static str, code: u32, provenance: &'static str) {
+    #[cfg(feature = "metrics")]
+    crate::metrics::increment(code);
+    #[cfg(feature = "std")]
+    if HOOKED.load(Ordering::Acquire) {
+        // The hook may fail an assertion itself, which must not wait
+        // for a `set_hook` that is waiting for this read to end.
+        let hook = HOOK
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+        if let Some(hook) = hook {
+            hook(&ErrorMeta {
+                kind,
+                code,
+                provenance,
+            });
+        }
+    }
+    #[cfg(not(feature = "std"))]
+    let _ = (kind, code, provenance);
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::{set_hook, take_hook, ErrorMeta, HOOK_LOCK};
+    use std::sync::Mutex;
+
+    #[test]
+    fn hook_sees_every_assertion() {
+        let _lock = HOOK_LOCK
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        static SEEN: Mutex<Vec<ErrorMeta>> = Mutex::new(Vec::new());
+        set_hook(|meta| SEEN.lock().unwrap().push(*meta));
+        let _ = crate::assert_known(&[1], 2);
+        let _ = crate::assert_in(&0, &(1..2));
+        assert!(take_hook());
+
+        // Other tests may fail assertions concurrently.
+        let seen = SEEN.lock().unwrap();
+        assert!(seen.contains(&ErrorMeta {
+            kind: "unknown",
+            code: crate::code::UNKNOWN,
+            provenance: "assert_known",
+        }));
+        assert!(seen.iter().any(|meta| meta.provenance == "assert_in"));
+
+        // Taking the lock from inside the hook must not deadlock.
+        set_hook(|_| {
+            take_hook();
+        });
+        let _ = crate::assert_known(&[1], 2);
+        assert!(!take_hook());
+    }
+}
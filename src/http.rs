@@ -0,0 +1,405 @@
+//! Mapping errors onto HTTP responses.
+
+use crate::{
+    render, At, Caused, Envelope, ErrorCode, Fields, Mismatch, Outside, RateLimited, Spanned,
+    Unknown,
+};
+use alloc::string::String;
+use core::fmt::{Debug, Display};
+use core::time::Duration;
+
+/// An error that can be reported to an HTTP client.
+///
+/// Every error in the crate implements it: malformed input is a 400,
+/// values that are not allowed a 422, refusals 403, 409 or 413, and
+/// failures on the server's side 500, 503 or 504.  Wrappers report the
+/// status of the error they wrap.
+///
+/// With the `axum` feature, every implementation is also an
+/// `axum::response::IntoResponse`, so that handlers can return it.
+///
+/// # Examples
+/// ```rust
+/// use goof::HttpGoof;
+///
+/// let error = goof::assert_in(&0, &(1..65535)).unwrap_err();
+/// assert_eq!(error.status(), 422);
+/// assert_eq!(error.with_status(416).status(), 416);
+/// ```
+pub trait HttpGoof: Fields + ErrorCode + Display {
+    /// The status code to respond with.
+    fn status(&self) -> u16 {
+        400
+    }
+
+    /// The value of the `Retry-After` header, if the client may try
+    /// again later.  The header takes whole seconds, so round up.
+    fn retry_after(&self) -> Option<Duration> {
+        None
+    }
+
+    /// The response body: an [`Envelope`] holding just this error.
+    fn body(&self) -> String {
+        render(&Envelope::new().error("", self), false)
+    }
+
+    /// Respond with `status` instead of the default for this error.
+    fn with_status(self, status: u16) -> WithStatus<Self>
+    where
+        Self: Sized,
+    {
+        WithStatus {
+            error: self,
+            status,
+        }
+    }
+}
+
+/// `HttpGoof` with a fixed status, and with the `axum` feature
+/// `IntoResponse`, for each error given.
+macro_rules! http_goof {
+    ($($(#[$attr:meta])* $status:literal => [$($generics:tt)*] $error:ty,)*) => {
+        $($(#[$attr])*
+        impl<$($generics)*> HttpGoof for $error {
+            fn status(&self) -> u16 {
+                $status
+            }
+        }
+
+        $(#[$attr])*
+        #[cfg(feature = "axum")]
+        impl<$($generics)*> axum_core::response::IntoResponse for $error {
+            fn into_response(self) -> axum_core::response::Response {
+                axum::respond(&self)
+            }
+        })*
+    };
+}
+
+http_goof! {
+    // Malformed requests.
+    400 => [T: Display + Copy + Eq] Mismatch<T>,
+    400 => [] crate::Goof<'_>,
+    400 => [const N: usize] crate::InlineGoof<N>,
+    400 => [] crate::PatternMismatch,
+    400 => [] crate::LengthMismatch,
+    400 => [] crate::MagicMismatch,
+    400 => [] crate::ArityMismatch,
+    400 => [] crate::Misaligned,
+    400 => [] crate::EndiannessMismatch,
+    400 => [] crate::TooShort,
+    400 => [] crate::BadText,
+    400 => [T: Display] crate::ExpectedOneOf<'_, T>,
+    400 => [I: Display] crate::ParseGoof<I>,
+    400 => [] crate::schema::MissingField,
+    400 => [] crate::schema::UnknownField,
+    400 => [] crate::schema::DuplicateKey,
+    400 => [] crate::schema::InvalidData,
+    400 => [] crate::schema::DeserGoof,
+    // Well-formed requests with values that are not allowed.
+    422 => [T: Display + PartialOrd + Copy] Outside<T>,
+    422 => [T: Display + Eq] Unknown<'_, T>,
+    422 => [T: Display + PartialOrd + Copy] crate::RangeGoof<T>,
+    422 => [T: Display + PartialOrd + Copy] crate::BoundGoof<T>,
+    422 => [T: Display] crate::EmptyRange<T>,
+    422 => [T: Display] crate::TooLarge<T>,
+    422 => [T: Display] crate::TooSmall<T>,
+    422 => [T: Display] crate::NotComparable<T>,
+    422 => [] crate::LengthOutside,
+    422 => [T: Display] crate::NotPositive<T>,
+    422 => [] crate::Zero,
+    422 => [T: Display] crate::NotMultipleOf<T>,
+    422 => [T: Display] crate::FailedPredicate<T>,
+    422 => [T: Display] crate::UnsortedAt<T>,
+    422 => [T: Display] crate::Duplicate<T>,
+    422 => [T: Display] crate::SliceMismatch<T>,
+    422 => [T: Display] crate::NotSubset<T>,
+    422 => [T: Display] crate::NotDisjoint<T>,
+    422 => [T: crate::ToHex] crate::UnknownBits<T>,
+    422 => [T: Display] crate::arith::Overflow<T>,
+    422 => [T: Display] crate::arith::Underflow<T>,
+    422 => [T: Display] crate::arith::DivisionByZero<T>,
+    422 => [T: Display] crate::arith::ArithGoof<T>,
+    422 => [E: core::error::Error + Fields + Display + 'static] crate::MultiError<E>,
+    #[cfg(feature = "semver")]
+    422 => [] crate::semver::VersionOutside,
+    // Requests that are valid, but not now or not from this client.
+    403 => [P: crate::Permissions] crate::InsufficientPermission<P>,
+    409 => [S: Display] crate::InvalidTransition<'_, S>,
+    413 => [T: Display] crate::Exceeded<T>,
+    // Failures of the server rather than of the request.
+    500 => [] crate::config::ConfigGoof,
+    #[cfg(feature = "std")]
+    500 => [] crate::Panicked,
+    #[cfg(feature = "std")]
+    500 => [] crate::IoGoof,
+    #[cfg(feature = "std")]
+    500 => [] crate::env::MissingEnv,
+    #[cfg(feature = "std")]
+    500 => [E] crate::env::InvalidEnv<E>,
+    #[cfg(feature = "std")]
+    500 => [E] crate::env::EnvGoof<E>,
+    503 => [] crate::Cancelled,
+    503 => [E] crate::retry::RetriesExhausted<E>,
+    504 => [] crate::Timeout,
+}
+
+impl HttpGoof for RateLimited {
+    fn status(&self) -> u16 {
+        429
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        self.retry_after
+    }
+}
+
+impl<E: HttpGoof, S: ?Sized> HttpGoof for Caused<E, S> {
+    fn status(&self) -> u16 {
+        self.error().status()
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        self.error().retry_after()
+    }
+}
+
+impl<E: HttpGoof> HttpGoof for Spanned<E> {
+    fn status(&self) -> u16 {
+        self.error().status()
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        self.error().retry_after()
+    }
+}
+
+impl<E: HttpGoof> HttpGoof for crate::Diagnostic<E> {
+    fn status(&self) -> u16 {
+        self.error().status()
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        self.error().retry_after()
+    }
+}
+
+impl<E: HttpGoof> HttpGoof for crate::WithTransience<E> {
+    fn status(&self) -> u16 {
+        self.error().status()
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        self.error().retry_after()
+    }
+}
+
+impl<E: HttpGoof> HttpGoof for At<E> {
+    fn status(&self) -> u16 {
+        self.error().status()
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        self.error().retry_after()
+    }
+}
+
+/// An error with an overridden status code, see
+/// [`HttpGoof::with_status`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WithStatus<E> {
+    error: E,
+    status: u16,
+}
+
+impl<E> WithStatus<E> {
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+}
+
+impl<E: Display> Display for WithStatus<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.error, f)
+    }
+}
+
+impl<E: core::error::Error> core::error::Error for WithStatus<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        self.error.source()
+    }
+}
+
+impl<E: Fields> Fields for WithStatus<E> {
+    fn kind(&self) -> &'static str {
+        self.error.kind()
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        self.error.for_each_field(visit)
+    }
+
+    fn help(&self) -> Option<String> {
+        self.error.help()
+    }
+
+    fn severity(&self) -> crate::Severity {
+        self.error.severity()
+    }
+
+    fn as_explain(&self) -> Option<&dyn crate::Explain> {
+        self.error.as_explain()
+    }
+}
+
+impl<E: HttpGoof> HttpGoof for WithStatus<E> {
+    fn status(&self) -> u16 {
+        self.status
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        self.error.retry_after()
+    }
+}
+
+#[cfg(feature = "axum")]
+mod axum {
+    use super::{HttpGoof, WithStatus};
+    use crate::{At, Caused, Diagnostic, Fields, RateLimited, Spanned, WithTransience};
+    use ::http::header::{CONTENT_TYPE, RETRY_AFTER};
+    use ::http::{HeaderValue, StatusCode};
+    use axum_core::response::{IntoResponse, Response};
+    use core::fmt::Display;
+
+    /// The [`HttpGoof::status`], with its [`HttpGoof::body`] as JSON
+    /// and its [`HttpGoof::retry_after`] rounded up to whole seconds.
+    pub(super) fn respond(error: &impl HttpGoof) -> Response {
+        let status = StatusCode::from_u16(error.status()).unwrap_or(StatusCode::BAD_REQUEST);
+        let mut response =
+            (status, [(CONTENT_TYPE, "application/json")], error.body()).into_response();
+        if let Some(after) = error.retry_after() {
+            let seconds = after.as_secs() + u64::from(after.subsec_nanos() > 0);
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, HeaderValue::from(seconds));
+        }
+        response
+    }
+
+    macro_rules! into_response {
+        ($([$($generics:tt)*] $error:ty,)*) => {
+            $(impl<$($generics)*> IntoResponse for $error {
+                fn into_response(self) -> Response {
+                    respond(&self)
+                }
+            })*
+        };
+    }
+
+    into_response! {
+        [] RateLimited,
+        [E: HttpGoof] Diagnostic<E>,
+        [E: HttpGoof] WithTransience<E>,
+        [E: HttpGoof, S: ?Sized] Caused<E, S>,
+        [E: HttpGoof] Spanned<E>,
+        [E: HttpGoof + Fields + Display] At<E>,
+        [E: HttpGoof] WithStatus<E>,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HttpGoof;
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn default_statuses_and_body() {
+        let mismatch = crate::assert_eq(&1, &2).unwrap_err();
+        assert_eq!(mismatch.status(), 400);
+        assert_eq!(crate::assert_known(&[1], 2).unwrap_err().status(), 422);
+        assert_eq!(
+            mismatch.with_status(409).body(),
+            r#"{"errors":[{"path":"","code":1,"kind":"mismatch","message":"Expected 1, but got 2","meta":{"expected":"1","actual":"2"}}]}"#
+        );
+    }
+
+    #[test]
+    fn malformed_requests() {
+        assert_eq!(crate::goof("bad").status(), 400);
+        assert_eq!(crate::assert_len([1, 2], 3).unwrap_err().status(), 400);
+        assert_eq!(
+            crate::schema::MissingField::new("User", "name").status(),
+            400
+        );
+    }
+
+    #[test]
+    fn unacceptable_values() {
+        assert_eq!(crate::assert_le(&3, &2).unwrap_err().status(), 422);
+        assert_eq!(crate::assert_nonzero(0).unwrap_err().status(), 422);
+        assert_eq!(
+            crate::assert_range(&(3..3), crate::Interval::HalfOpen)
+                .unwrap_err()
+                .status(),
+            422
+        );
+        let mut errors = crate::MultiError::new();
+        errors.push(crate::assert_nonzero(0).unwrap_err());
+        assert_eq!(errors.status(), 422);
+    }
+
+    #[test]
+    fn refused_requests() {
+        assert_eq!(
+            crate::assert_permitted(0b10_u8, 0b01).unwrap_err().status(),
+            403
+        );
+        assert_eq!(
+            crate::assert_transition(1, 3, &[2]).unwrap_err().status(),
+            409
+        );
+        assert_eq!(crate::assert_capacity(11, 10).unwrap_err().status(), 413);
+    }
+
+    #[test]
+    fn server_failures() {
+        assert_eq!(crate::config::ConfigGoof::new("app.toml").status(), 500);
+        assert_eq!(crate::Cancelled::new().status(), 503);
+        let wrapped = crate::Diagnostic::new(crate::Severity::Error, crate::Cancelled::new());
+        assert_eq!(wrapped.status(), 503);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn timeouts() {
+        let error = crate::assert_within(core::time::Duration::ZERO, || {
+            std::thread::sleep(core::time::Duration::from_millis(1))
+        })
+        .unwrap_err();
+        assert_eq!(error.status(), 504);
+    }
+
+    #[cfg(feature = "axum")]
+    #[test]
+    fn responses() {
+        use axum_core::response::IntoResponse;
+        use core::time::Duration;
+
+        let response = crate::assert_in(&0, &(1..10)).unwrap_err().into_response();
+        assert_eq!(response.status(), 422);
+        assert_eq!(response.headers()["content-type"], "application/json");
+
+        let limited = crate::RateLimited::new(
+            10,
+            Duration::from_secs(1),
+            Some(Duration::from_millis(1500)),
+        );
+        let response = limited.with_status(503).into_response();
+        assert_eq!(response.status(), 503);
+        assert_eq!(response.headers()["retry-after"], "2");
+    }
+}
@@ -0,0 +1,190 @@
+//! Localized messages, from templates keyed by [`ErrorCode`].
+//!
+//! A template is the message with the names of [`Fields`] in braces,
+//! e.g. `"Attendu {expected}, reçu {actual}"`; `{{` and `}}` stand
+//! for literal braces.  Errors without a template, and placeholders
+//! that are not fields, are rendered as usual.
+
+use crate::{render, ErrorCode, Fields};
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+/// Where message templates come from, e.g. a table compiled into the
+/// binary or a Fluent bundle loaded at startup.
+pub trait Catalog {
+    /// The template for errors with `code`, if there is one.
+    fn template(&self, code: u32) -> Option<&str>;
+}
+
+impl Catalog for [(u32, &str)] {
+    fn template(&self, code: u32) -> Option<&str> {
+        self.iter()
+            .find(|(candidate, _)| *candidate == code)
+            .map(|(_, template)| *template)
+    }
+}
+
+impl<const N: usize> Catalog for [(u32, &str); N] {
+    fn template(&self, code: u32) -> Option<&str> {
+        self[..].template(code)
+    }
+}
+
+impl Catalog for BTreeMap<u32, String> {
+    fn template(&self, code: u32) -> Option<&str> {
+        self.get(&code).map(String::as_str)
+    }
+}
+
+#[cfg(feature = "std")]
+type Global = alloc::boxed::Box<dyn Catalog + Send + Sync>;
+
+#[cfg(feature = "std")]
+static CATALOG: std::sync::RwLock<Option<Global>> = std::sync::RwLock::new(None);
+
+/// Use `catalog` for [`Localize::localized`] from now on, replacing
+/// any previous one.
+#[cfg(feature = "std")]
+pub fn set_catalog(catalog: impl Catalog + Send + Sync + 'static) {
+    let mut slot = CATALOG
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    *slot = Some(alloc::boxed::Box::new(catalog));
+}
+
+/// Remove the catalog set with [`set_catalog`], returning whether
+/// there was one.
+#[cfg(feature = "std")]
+pub fn take_catalog() -> bool {
+    let mut slot = CATALOG
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    slot.take().is_some()
+}
+
+/// Rendering any error in the language of a [`Catalog`].
+///
+/// # Examples
+/// ```rust
+/// use goof::i18n::Localize;
+///
+/// let french = [(goof::code::MISMATCH, "Attendu {expected}, reçu {actual}")];
+/// let error = goof::assert_eq(&32, &0).unwrap_err();
+/// assert_eq!(error.localized_in(&french).to_string(), "Attendu 32, reçu 0");
+///
+/// let unknown = goof::assert_known(&[1], 2).unwrap_err();
+/// assert_eq!(
+///     unknown.localized_in(&french).to_string(),
+///     unknown.to_string()
+/// );
+/// ```
+pub trait Localize: Fields + ErrorCode + Display {
+    /// Render with the catalog set with [`set_catalog`].
+    #[cfg(feature = "std")]
+    fn localized(&self) -> Localized<'_, Self> {
+        Localized {
+            error: self,
+            catalog: None,
+        }
+    }
+
+    /// Render with `catalog`.
+    fn localized_in<'a>(&'a self, catalog: &'a dyn Catalog) -> Localized<'a, Self> {
+        Localized {
+            error: self,
+            catalog: Some(catalog),
+        }
+    }
+}
+
+impl<E: Fields + ErrorCode + Display + ?Sized> Localize for E {}
+
+/// `Display` adapter returned by [`Localize::localized`].
+pub struct Localized<'a, E: ?Sized> {
+    error: &'a E,
+    /// `None` for the global catalog.
+    catalog: Option<&'a dyn Catalog>,
+}
+
+impl<E: Fields + ErrorCode + Display + ?Sized> Display for Localized<'_, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let code = self.error.code();
+        if let Some(catalog) = self.catalog {
+            return match catalog.template(code) {
+                Some(template) => fill(f, template, self.error),
+                None => Display::fmt(self.error, f),
+            };
+        }
+        #[cfg(feature = "std")]
+        {
+            let slot = CATALOG
+                .read()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            if let Some(template) = slot.as_ref().and_then(|catalog| catalog.template(code)) {
+                return fill(f, template, self.error);
+            }
+        }
+        Display::fmt(self.error, f)
+    }
+}
+
+fn fill(
+    f: &mut Formatter<'_>,
+    template: &str,
+    error: &(impl Fields + ?Sized),
+) -> core::fmt::Result {
+    let mut fields: Vec<(&'static str, String)> = Vec::new();
+    error.for_each_field(&mut |name, value| fields.push((name, render(value, false))));
+
+    let mut rest = template;
+    while let Some(index) = rest.find(['{', '}']) {
+        f.write_str(&rest[..index])?;
+        let tail = &rest[index..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            f.write_str(&tail[..1])?;
+            rest = &tail[2..];
+            continue;
+        }
+        let placeholder = tail
+            .strip_prefix('{')
+            .and_then(|inner| Some(&inner[..inner.find('}')?]));
+        let value = placeholder.and_then(|name| {
+            fields
+                .iter()
+                .find(|(field, _)| *field == name)
+                .map(|(_, value)| value)
+        });
+        match (placeholder, value) {
+            (Some(name), Some(value)) => {
+                f.write_str(value)?;
+                rest = &tail[name.len() + 2..];
+            }
+            _ => {
+                f.write_str(&tail[..1])?;
+                rest = &tail[1..];
+            }
+        }
+    }
+    f.write_str(rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Localize;
+    use alloc::string::ToString;
+
+    #[test]
+    fn placeholders() {
+        let error = crate::assert_in(&7, &(1..5)).unwrap_err();
+        let catalog = [(
+            crate::code::OUTSIDE,
+            "{{{value}}} ∉ [{start}, {end}] {nope} {",
+        )];
+        assert_eq!(
+            error.localized_in(&catalog).to_string(),
+            "{7} ∉ [1, 5] {nope} {"
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! Context for I/O errors, which on their own do not say what was
+//! being done or to which file.
+
+use crate::{code, ErrorCode, Fields, Mismatch, Outside, Unknown};
+use core::error::Error;
+use core::fmt::{Debug, Display};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// An [`std::io::Error`] with the operation that failed and the path
+/// it failed on.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::IoGoofExt;
+///
+/// let error = std::fs::read("/surely/not/here.toml")
+///     .during("read")
+///     .on_path("/surely/not/here.toml")
+///     .unwrap_err();
+/// assert!(error
+///     .to_string()
+///     .starts_with("Could not read /surely/not/here.toml: "));
+/// assert_eq!(error.io().kind(), std::io::ErrorKind::NotFound);
+/// ```
+#[derive(Debug)]
+pub struct IoGoof {
+    error: std::io::Error,
+    operation: Option<&'static str>,
+    path: Option<PathBuf>,
+}
+
+impl IoGoof {
+    pub fn new(error: std::io::Error) -> Self {
+        Self {
+            error,
+            operation: None,
+            path: None,
+        }
+    }
+
+    /// Record what was being done, as a verb, e.g. `"open"`.
+    pub fn during(mut self, operation: &'static str) -> Self {
+        self.operation = Some(operation);
+        self
+    }
+
+    /// Record the path that was being operated on.
+    pub fn on_path(mut self, path: impl AsRef<Path>) -> Self {
+        self.path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn io(&self) -> &std::io::Error {
+        &self.error
+    }
+
+    pub fn operation(&self) -> Option<&'static str> {
+        self.operation
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn into_io(self) -> std::io::Error {
+        self.error
+    }
+}
+
+impl From<std::io::Error> for IoGoof {
+    fn from(error: std::io::Error) -> Self {
+        Self::new(error)
+    }
+}
+
+impl Display for IoGoof {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        match (self.operation, &self.path) {
+            (Some(operation), Some(path)) => {
+                write!(f, "Could not {} {}: ", operation, path.display())?
+            }
+            (Some(operation), None) => write!(f, "Could not {}: ", operation)?,
+            (None, Some(path)) => write!(f, "{}: ", path.display())?,
+            (None, None) => {}
+        }
+        Display::fmt(&self.error, f)
+    }
+}
+
+/// Data that was read did not have the value it had to have, for
+/// `Read` implementations, which can only fail with an I/O error.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use std::io::{ErrorKind, Read};
+///
+/// struct Header<R>(R);
+///
+/// impl<R: Read> Read for Header<R> {
+///     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+///         let mut version = [0];
+///         self.0.read_exact(&mut version)?;
+///         goof::assert_eq(&1_u8, &version[0])?;
+///         self.0.read(buf)
+///     }
+/// }
+///
+/// let error = Header(&[2_u8][..]).read(&mut [0; 4]).unwrap_err();
+/// assert_eq!(error.kind(), ErrorKind::InvalidData);
+/// assert_eq!(error.to_string(), "Expected 1, but got 2");
+/// ```
+impl<T> From<Mismatch<T>> for std::io::Error
+where
+    T: Debug + Display + Copy + Eq + Send + Sync + 'static,
+{
+    fn from(error: Mismatch<T>) -> Self {
+        Self::new(ErrorKind::InvalidData, error)
+    }
+}
+
+/// An argument, such as an offset to seek to, was out of range.
+impl<T> From<Outside<T>> for std::io::Error
+where
+    T: Debug + Display + Ord + Copy + Send + Sync + 'static,
+{
+    fn from(error: Outside<T>) -> Self {
+        Self::new(ErrorKind::InvalidInput, error)
+    }
+}
+
+/// Data that was read was not one of the values it could have.
+impl<T> From<Unknown<'static, T>> for std::io::Error
+where
+    T: Debug + Display + Eq + Send + Sync + 'static,
+{
+    fn from(error: Unknown<'static, T>) -> Self {
+        Self::new(ErrorKind::InvalidData, error)
+    }
+}
+
+// The message of the I/O error is part of the `Display`, so the
+// chain continues with whatever caused the I/O error.
+impl Error for IoGoof {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+}
+
+impl Fields for IoGoof {
+    fn kind(&self) -> &'static str {
+        "io"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        if let Some(operation) = &self.operation {
+            visit("operation", operation);
+        }
+        if let Some(path) = &self.path {
+            visit("path", &path.display());
+        }
+        visit("io_kind", &self.error.kind());
+    }
+}
+
+impl ErrorCode for IoGoof {
+    fn code(&self) -> u32 {
+        code::IO
+    }
+}
+
+/// Adds context to the error of a fallible I/O operation.
+pub trait IoGoofExt<T> {
+    /// See [`IoGoof::during`].
+    fn during(self, operation: &'static str) -> Result<T, IoGoof>;
+
+    /// See [`IoGoof::on_path`].
+    fn on_path(self, path: impl AsRef<Path>) -> Result<T, IoGoof>;
+}
+
+impl<T, E: Into<IoGoof>> IoGoofExt<T> for Result<T, E> {
+    fn during(self, operation: &'static str) -> Result<T, IoGoof> {
+        self.map_err(|error| error.into().during(operation))
+    }
+
+    fn on_path(self, path: impl AsRef<Path>) -> Result<T, IoGoof> {
+        self.map_err(|error| error.into().on_path(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IoGoof, IoGoofExt};
+    use crate::Outside;
+    use std::io::{Error, ErrorKind};
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn context_is_optional() {
+        let error = || Error::other("boom");
+        assert_eq!(IoGoof::from(error()).to_string(), "boom");
+        assert_eq!(
+            Err::<(), _>(error())
+                .on_path("a.txt")
+                .unwrap_err()
+                .to_string(),
+            "a.txt: boom"
+        );
+        assert_eq!(
+            IoGoof::new(error()).during("sync").to_string(),
+            "Could not sync: boom"
+        );
+    }
+
+    #[test]
+    fn range_errors_are_invalid_input() {
+        let error = Error::from(crate::assert_in(&9_u64, &(0..4)).unwrap_err());
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+        assert!(error.get_ref().unwrap().is::<Outside<u64>>());
+    }
+}
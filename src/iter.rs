@@ -0,0 +1,95 @@
+//! Validating the elements of an iterator.
+
+use crate::At;
+
+/// Adds validation to every iterator.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::IterGoofExt;
+///
+/// let ports = [80, 443, 0, 70000];
+/// let error = ports
+///     .iter()
+///     .validate_each(|port| goof::assert_in(port, &(1..65535)))
+///     .find_goof()
+///     .unwrap();
+/// assert_eq!(
+///     error.to_string(),
+///     "[2]: Value 0 is outside the allowed range (1, 65535] (must be greater than 1)"
+/// );
+/// ```
+pub trait IterGoofExt: Iterator + Sized {
+    /// Check every element with `validator` as it is reached, instead
+    /// of all of them up front.
+    fn validate_each<T, E, F>(self, validator: F) -> ValidateEach<Self, F>
+    where
+        F: FnMut(Self::Item) -> Result<T, E>,
+    {
+        ValidateEach {
+            iter: self,
+            validator,
+        }
+    }
+
+    /// The first error among the results, at its index, without
+    /// checking the elements after it.
+    fn find_goof<T, E>(self) -> Option<At<E>>
+    where
+        Self: Iterator<Item = Result<T, E>>,
+    {
+        self.enumerate()
+            .find_map(|(index, result)| result.err().map(|error| At::new(error).index(index)))
+    }
+}
+
+impl<I: Iterator> IterGoofExt for I {}
+
+/// Iterator returned by [`IterGoofExt::validate_each`].
+#[derive(Debug, Clone)]
+pub struct ValidateEach<I, F> {
+    iter: I,
+    validator: F,
+}
+
+impl<I, F, T, E> Iterator for ValidateEach<I, F>
+where
+    I: Iterator,
+    F: FnMut(I::Item) -> Result<T, E>,
+{
+    type Item = Result<T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(&mut self.validator)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IterGoofExt;
+    use core::cell::Cell;
+
+    #[test]
+    fn stops_at_the_first_goof() {
+        let checked = Cell::new(0);
+        let error = [1, 2, 3, 4]
+            .into_iter()
+            .validate_each(|n| {
+                checked.set(checked.get() + 1);
+                crate::assert_eq(&1, &n)
+            })
+            .find_goof()
+            .unwrap();
+        assert_eq!(checked.get(), 2);
+        assert_eq!(error.path().to_string(), "[1]");
+        assert!([Ok::<_, crate::Cancelled>(1)]
+            .into_iter()
+            .find_goof()
+            .is_none());
+    }
+}
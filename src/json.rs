@@ -0,0 +1,140 @@
+//! Structured JSON rendering, so that log pipelines can index the
+//! payload of an error instead of parsing its message.
+//!
+//! Every error renders as a flat object with a `type`, a `code`, a
+//! `message` and one key per payload field, as in [`Fields`].  Values
+//! that read as JSON numbers or booleans are written as such, and
+//! everything else as a string:
+//!
+//! ```json
+//! {"type":"mismatch","code":1,"message":"Expected 32, but got 0","expected":32,"actual":0}
+//! ```
+//!
+//! A payload field named like one of the first three keys, such as
+//! the `message` of a [`crate::Panicked`], is written as e.g.
+//! `field_message`, unless it only repeats that key.
+
+use crate::envelope::write_escaped;
+use crate::style::write_object;
+use crate::{render, ErrorCode, Fields};
+use alloc::string::String;
+use core::fmt::{Display, Formatter};
+
+/// An error that can be rendered as a structured JSON object, which
+/// is every error with [`Fields`] and an [`ErrorCode`].
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::{ToJson, assert_eq};
+///
+/// let error = assert_eq(&32, &0).unwrap_err();
+/// assert_eq!(
+///     error.to_json(),
+///     r#"{"type":"mismatch","code":1,"message":"Expected 32, but got 0","expected":32,"actual":0}"#
+/// );
+///
+/// let error = goof::assert_in_with(&7, &(1..5), goof::Interval::Open).unwrap_err();
+/// assert!(error.to_json().ends_with(r#""start":1,"end":5,"value":7,"interval":"open"}"#));
+/// ```
+pub trait ToJson: Fields + ErrorCode + Display {
+    /// Borrow `self` as something whose `Display` is the JSON object.
+    fn as_json(&self) -> Json<'_, Self> {
+        Json(self)
+    }
+
+    fn to_json(&self) -> String {
+        render(&self.as_json(), false)
+    }
+}
+
+impl<E: Fields + ErrorCode + Display + ?Sized> ToJson for E {}
+
+/// `Display` adapter returned by [`ToJson::as_json`].
+pub struct Json<'a, E: ?Sized>(&'a E);
+
+impl<E: ToJson + ?Sized> Display for Json<'_, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write_object(f, self.0, |f, value| {
+            if is_literal(value) {
+                f.write_str(value)
+            } else {
+                write_escaped(f, value)
+            }
+        })
+    }
+}
+
+/// Whether `value` can be written into JSON as it is: a boolean, or a
+/// number in the grammar of JSON, which has no `NaN`, `inf` or `+1`.
+fn is_literal(value: &str) -> bool {
+    fn digits(bytes: &[u8]) -> usize {
+        bytes
+            .iter()
+            .take_while(|byte| byte.is_ascii_digit())
+            .count()
+    }
+
+    if value == "true" || value == "false" {
+        return true;
+    }
+    let bytes = value.as_bytes();
+    let mut at = usize::from(bytes.first() == Some(&b'-'));
+    match digits(&bytes[at..]) {
+        0 => return false,
+        // No leading zeros.
+        n if n > 1 && bytes[at] == b'0' => return false,
+        n => at += n,
+    }
+    if bytes.get(at) == Some(&b'.') {
+        match digits(&bytes[at + 1..]) {
+            0 => return false,
+            n => at += 1 + n,
+        }
+    }
+    if matches!(bytes.get(at), Some(b'e' | b'E')) {
+        at += 1;
+        if matches!(bytes.get(at), Some(b'+' | b'-')) {
+            at += 1;
+        }
+        match digits(&bytes[at..]) {
+            0 => return false,
+            n => at += n,
+        }
+    }
+    at == bytes.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_literal, ToJson};
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn typed_payloads() {
+        assert_eq!(
+            crate::assert_in(&7_u8, &(1..5)).unwrap_err().to_json(),
+            r#"{"type":"outside","code":2,"message":"Value 7 is outside the allowed range (1, 5] (must be at most 5)","start":1,"end":5,"value":7,"interval":"left_open"}"#
+        );
+        assert_eq!(
+            crate::assert_known(&["a"], "b\"").unwrap_err().to_json(),
+            r#"{"type":"unknown","code":3,"message":"The value b\" is not known.","value":"b\""}"#
+        );
+        assert_eq!(
+            crate::Goof::from("007").to_json(),
+            r#"{"type":"goof","code":43,"message":"007"}"#
+        );
+    }
+
+    #[test]
+    fn json_literals() {
+        for literal in ["0", "-1", "1.5", "2e10", "-0.5E-3", "true"] {
+            assert!(is_literal(literal), "{}", literal);
+        }
+        for text in [
+            "", "-", "007", "1.", ".5", "NaN", "inf", "+1", "1e", "0x10", "True",
+        ] {
+            assert!(!is_literal(text), "{}", text);
+        }
+    }
+}
@@ -1,9 +1,255 @@
 //! The goof library is a collection of re-usable error handling
 //! structs and patterns that are meant to make error handling
 //! lightweight, portable and inter-convertible.
+//!
+//! Every error implements `core::error::Error` and is `Send + Sync +
+//! 'static` whenever its payload is.  That is all that `anyhow`,
+//! `eyre` and `error-stack` ask for, so goof errors can be returned
+//! as an `anyhow::Error`, used as an `error_stack::Context`, or
+//! attached to an `error_stack::Report` with `attach_printable`,
+//! without any glue code.  [`find_cause`] recovers them from a wrapped chain.
+//!
+//! The crate is `no_std` when its default `std` feature is turned
+//! off, and then only needs an allocator.  For the smallest binaries,
+//! the `compact` feature replaces every message with the error code
+//! and the raw payload, e.g. `#1 expected=32 actual=0`, so that the
+//! optimiser can drop the message strings.  The [`Compact`] style
+//! renders the same way without the feature.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
 use core::fmt::{Debug, Display};
 
+/// Return early from `Display::fmt` with [`write_compact`] when the
+/// `compact` feature is on, so the message below it is dead code.
+macro_rules! compact {
+    ($error:expr, $f:expr) => {
+        if cfg!(feature = "compact") {
+            return $crate::write_compact($f, $error);
+        }
+    };
+}
+
+#[cfg(feature = "std")]
+pub mod alarm;
+pub mod arith;
+mod binary;
+mod call;
+mod cancel;
+mod caused;
+pub mod code;
+mod collection;
+mod compare;
+pub mod config;
+pub mod consts;
+mod define;
+#[cfg(feature = "diff")]
+mod diff;
+#[cfg(feature = "std")]
+pub mod env;
+pub mod envelope;
+mod explain;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
+mod fluent;
+#[cfg(feature = "async")]
+mod future;
+mod fuzz;
+pub mod github;
+mod goofy;
+pub mod grpc;
+mod hex;
+mod hook;
+pub mod http;
+#[cfg(feature = "i18n")]
+pub mod i18n;
+#[cfg(feature = "std")]
+mod io;
+mod iter;
+#[cfg(feature = "json")]
+pub mod json;
+mod limit;
+#[cfg(feature = "log")]
+mod logging;
+#[cfg(feature = "lsp")]
+pub mod lsp;
+mod magic;
+mod matches;
+mod message;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "miette")]
+mod miette;
+mod multi;
+mod numeric;
+#[cfg(feature = "std")]
+mod panic;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+mod partial;
+mod path;
+mod permission;
+mod predicate;
+mod range;
+mod refined;
+mod report;
+pub mod retry;
+mod roles;
+#[cfg(feature = "sarif")]
+pub mod sarif;
+pub mod schema;
+mod secret;
+#[cfg(feature = "semver")]
+pub mod semver;
+#[cfg(feature = "sentry")]
+pub mod sentry;
+mod set;
+mod severity;
+mod span;
+mod state;
+mod style;
+mod text;
+mod time;
+mod token;
+#[cfg(feature = "tracing")]
+mod trace;
+mod transience;
+mod tree;
+mod truncate;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "wire")]
+pub mod wire;
+
+#[cfg(feature = "std")]
+pub use alarm::Alarm;
+pub use binary::{
+    assert_aligned, assert_known_bits, assert_remaining, take_exact, Address, Endian,
+    EndiannessMismatch, Misaligned, TooShort, UnknownBits,
+};
+pub use call::{assert_arity, ArityMismatch};
+pub use cancel::Cancelled;
+pub use caused::{find_cause, AsSource, Caused, CausedBy};
+pub use code::ErrorCode;
+pub use collection::{
+    assert_len, assert_len_in, assert_slices_eq, assert_sorted, assert_sorted_by_key,
+    assert_unique, Bounds, Duplicate, LengthMismatch, LengthOutside, SliceMismatch, UnsortedAt,
+};
+pub use compare::{assert_ge, assert_gt, assert_le, assert_lt, BoundGoof, TooLarge, TooSmall};
+#[doc(hidden)]
+pub use define::__String;
+pub use envelope::Envelope;
+pub use explain::Explain;
+#[cfg(feature = "fixtures")]
+pub use fixtures::gallery;
+pub use fluent::{expect, value, Expect, Named, Value};
+#[cfg(feature = "async")]
+pub use future::{FutureGoofExt, WithContext, WithContextFn};
+pub use fuzz::fuzz_check;
+pub use goofy::Goofy;
+pub use hex::{Hex, HexMismatch, ToHex};
+pub use hook::ErrorMeta;
+#[cfg(feature = "std")]
+pub use hook::{set_hook, take_hook};
+pub use http::HttpGoof;
+#[cfg(feature = "std")]
+pub use io::{IoGoof, IoGoofExt};
+pub use iter::{IterGoofExt, ValidateEach};
+#[cfg(feature = "json")]
+pub use json::ToJson;
+pub use limit::{assert_capacity, Exceeded, RateLimited};
+#[cfg(feature = "log")]
+pub use logging::LogGoofExt;
+#[cfg(feature = "std")]
+pub use magic::read_magic;
+pub use magic::{assert_magic, MagicMismatch};
+pub use matches::PatternMismatch;
+pub use message::{goof, Goof, InlineGoof};
+pub use multi::{partition_results, try_collect_all, MultiError};
+pub use numeric::{
+    assert_multiple_of, assert_nonzero, assert_positive, CheckedRem, NotMultipleOf, NotPositive,
+    Zero,
+};
+#[cfg(feature = "std")]
+pub use panic::{catch, PanicLocation, Panicked};
+pub use partial::{assert_in_partial, NotComparable, RangeGoof, Total, TotalOrder};
+pub use path::{At, Path, Pointer, Segment};
+pub use permission::{assert_permitted, InsufficientPermission, Permissions};
+pub use predicate::{assert_that, FailedPredicate};
+pub use range::{assert_range, EmptyRange};
+#[doc(hidden)]
+pub use refined::__refine;
+pub use refined::{Bounded, NonEmpty};
+#[cfg(feature = "std")]
+pub use report::MainGoof;
+pub use report::{Explained, Pretty, Report};
+pub use roles::{Actual, Expected, IntoActual, IntoExpected};
+pub use secret::Secret;
+#[cfg(feature = "sentry")]
+pub use sentry::ToSentryEvent;
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serde as __serde;
+pub use set::{assert_disjoint, assert_subset, assert_superset, NotDisjoint, NotSubset};
+pub use severity::{Diagnostic, Severity};
+pub use span::{Position, Span, Spanned};
+pub use state::{assert_transition, InvalidTransition};
+pub use style::{Compact, JsonLike, MessageStyle, Stylable, Styled, Terse, Verbose};
+pub use text::{assert_utf8, BadText};
+#[cfg(feature = "std")]
+pub use time::assert_within;
+pub use time::Timeout;
+pub use token::{assert_one_of, ExpectedOneOf, ParseGoof};
+#[cfg(feature = "tracing")]
+pub use trace::TraceGoofExt;
+pub use transience::{Transience, WithTransience};
+pub use tree::{Aggregate, Child, Glyphs, Tree};
+pub use truncate::{Truncate, Truncated, Truncation};
+#[cfg(feature = "wasm")]
+pub use wasm::ToJsError;
+
+/// Machine-readable view of an error, independent of how its
+/// `Display` implementation phrases it.
+pub trait Fields {
+    /// Stable, snake-case name of the kind of error, e.g. `"mismatch"`.
+    fn kind(&self) -> &'static str;
+
+    /// Call `visit` with the name and value of every payload field,
+    /// always in the same order.
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display));
+
+    /// A short hint on how to fix the error, for diagnostic renderers
+    /// such as `miette` that show it separately from the message.
+    fn help(&self) -> Option<String> {
+        None
+    }
+
+    /// This error as an [`Aggregate`] of other errors, if it is one,
+    /// so that it is still drawn as a [`Tree`] after its type was
+    /// erased.
+    fn as_aggregate(&self) -> Option<&dyn Aggregate> {
+        None
+    }
+
+    /// How severe the error is, for renderers that rank problems:
+    /// [`Severity::Error`] unless it says otherwise, as a [`Diagnostic`]
+    /// does.
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    /// The long-form help of this error, if it has one, so that it is
+    /// still available after its type was erased.
+    fn as_explain(&self) -> Option<&dyn Explain> {
+        None
+    }
+}
+
 /// Assert that the object is exactly equal to the provided test value.
 ///
 /// # Motivation
@@ -27,19 +273,41 @@ use core::fmt::{Debug, Display};
 ///
 /// assert_eq!(fallible_func(&[]).unwrap_err(), assert_eq(&32, &0).unwrap_err())
 /// ```
-pub fn assert_eq<T: Copy + Eq>(actual: &T, expected: &T) -> Result<T, Mismatch<T>> {
-    if expected.eq(&actual) {
-        Ok(*expected)
+///
+/// Wrapping the arguments in [`Expected`] and [`Actual`] makes
+/// passing them the wrong way around a type error.
+///
+/// # Argument order
+///
+/// The expected value comes first.  Up to 0.2 it came second, even
+/// though the examples already put it first, so calls written against
+/// 0.2 compile unchanged but report the two values the wrong way
+/// around.  Wrapping them in [`Expected`] and [`Actual`] when
+/// upgrading catches every such call.
+pub fn assert_eq<T: Copy + Eq>(
+    expected: impl IntoExpected<T>,
+    actual: impl IntoActual<T>,
+) -> Result<T, Mismatch<T>> {
+    let (expected, actual) = (expected.into_expected(), actual.into_actual());
+    if expected == actual {
+        Ok(expected)
     } else {
+        hook::observe("mismatch", code::MISMATCH, "assert_eq");
         Err(Mismatch {
-            expected: *expected,
-            actual: *actual,
+            expected,
+            actual,
+            provenance: "assert_eq",
         })
     }
 }
 
-/// Assert that the object is exactly within the boundaries given by
-/// the `range` operand.
+/// Assert that the object is within the boundaries given by the
+/// `range` operand: greater than `range.start` and at most
+/// `range.end`, as in [`Interval::LeftOpen`].
+///
+/// Unlike `Range::contains`, this rejects the start and accepts the
+/// end.  Use [`assert_in_with`] to choose which ends are allowed, and
+/// [`assert_range`] to tell an empty range apart from a bad value.
 ///
 /// # Motivation
 ///
@@ -60,32 +328,96 @@ pub fn assert_eq<T: Copy + Eq>(actual: &T, expected: &T) -> Result<T, Mismatch<T
 ///     Ok(())
 /// }
 ///
-/// assert_eq!(fallible_func(&vec![0; 32]).unwrap_err(), assert_in(&32, &0).unwrap_err())
+/// assert_eq!(fallible_func(&vec![0; 32]).unwrap_err(), assert_in(&32, &(32..64)).unwrap_err())
 /// ```
 pub fn assert_in<T: Ord + Copy>(value: &T, range: &core::ops::Range<T>) -> Result<T, Outside<T>> {
-    if value > &range.start && value <= &range.end {
-        Ok(*value)
+    check_in(*value, range, Interval::LeftOpen, "assert_in")
+}
+
+/// Assert that `value` is in `range`, with `interval` deciding
+/// whether its ends are allowed.  The error says which rule was
+/// applied.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::{assert_in_with, Interval};
+///
+/// assert_eq!(assert_in_with(&1, &(1..5), Interval::HalfOpen), Ok(1));
+/// assert_eq!(
+///     assert_in_with(&5, &(1..5), Interval::HalfOpen).unwrap_err().to_string(),
+///     "Value 5 is outside the allowed range 1..5 (must be less than 5)"
+/// );
+/// assert_eq!(
+///     assert_in_with(&1, &(1..5), Interval::Open).unwrap_err().to_string(),
+///     "Value 1 is outside the allowed range (1, 5) (must be greater than 1)"
+/// );
+/// assert_eq!(assert_in_with(&5, &(1..5), Interval::Closed), Ok(5));
+///
+/// let error = assert_in_with(&3, &(3..3), Interval::Open).unwrap_err();
+/// assert!(error.is_empty_range());
+/// ```
+pub fn assert_in_with<T: Ord + Copy>(
+    value: &T,
+    range: &core::ops::Range<T>,
+    interval: Interval,
+) -> Result<T, Outside<T>> {
+    check_in(*value, range, interval, "assert_in_with")
+}
+
+/// [`assert_in_with`], on behalf of `provenance`.
+pub(crate) fn check_in<T: Ord + Copy>(
+    value: T,
+    range: &core::ops::Range<T>,
+    interval: Interval,
+    provenance: &'static str,
+) -> Result<T, Outside<T>> {
+    if interval.contains(range, &value) {
+        Ok(value)
     } else {
+        hook::observe("outside", code::OUTSIDE, provenance);
         // TODO: isn't Range<T> supposed to be Copy?
         Err(Outside {
             range: range.clone(),
-            value: *value,
+            interval,
+            value,
+            provenance,
         })
     }
 }
 
 /// This structure should be used in cases where a value must be
 /// exactly equal to another value for the process to be valid.
-#[derive(PartialEq, Eq, Clone, Copy)]
+#[derive(Clone, Copy)]
 pub struct Mismatch<T: Copy + Eq> {
     /// The expected return type
     pub(crate) expected: T,
     /// What was actually received
     pub(crate) actual: T,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl<T: Copy + Eq> Mismatch<T> {
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_eq"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
 }
 
+// Where an error came from is not part of what it says, so it is not
+// compared.
+impl<T: Copy + Eq> PartialEq for Mismatch<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.expected == other.expected && self.actual == other.actual
+    }
+}
+
+impl<T: Copy + Eq> Eq for Mismatch<T> {}
+
 impl<T: Debug + Copy + Eq> Debug for Mismatch<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("Mismatch")
             .field("expected", &self.expected)
             .field("actual", &self.actual)
@@ -93,62 +425,294 @@ impl<T: Debug + Copy + Eq> Debug for Mismatch<T> {
     }
 }
 
+/// With the `diff` feature, `{:#}` shows a line diff instead if either
+/// value spans several lines.
 impl<T: Display + Copy + Eq> Display for Mismatch<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Expected {}, but got {}", self.expected, self.actual)
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        #[cfg(feature = "diff")]
+        if f.alternate() {
+            let expected = render(&self.expected, false);
+            let actual = render(&self.actual, false);
+            if expected.contains('\n') || actual.contains('\n') {
+                return diff::write_diff(f, &expected, &actual);
+            }
+        }
+        self.fmt_truncated(f, Truncation::current())
+    }
+}
+
+impl<T: Display + Copy + Eq> Truncate for Mismatch<T> {
+    fn fmt_truncated(
+        &self,
+        f: &mut core::fmt::Formatter<'_>,
+        truncation: Truncation,
+    ) -> core::fmt::Result {
+        f.write_str("Expected ")?;
+        truncation.write_value(f, &self.expected)?;
+        f.write_str(", but got ")?;
+        truncation.write_value(f, &self.actual)
+    }
+}
+
+impl<T: Debug + Display + Copy + Eq> core::error::Error for Mismatch<T> {}
+
+impl<T: Display + Copy + Eq> Fields for Mismatch<T> {
+    fn kind(&self) -> &'static str {
+        "mismatch"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("expected", &self.expected);
+        visit("actual", &self.actual);
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(format!("use {} instead", self.expected))
+    }
+
+    fn as_explain(&self) -> Option<&dyn Explain> {
+        Some(self)
+    }
+}
+
+/// Which ends of the range of an [`Outside`] are allowed values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Interval {
+    /// `start..=end`, both ends allowed.
+    Closed,
+    /// `(start, end]`, `start` excluded and `end` included, as
+    /// [`assert_in`] checks.
+    LeftOpen,
+    /// `start..end`, `start` included and `end` excluded, as
+    /// `Range::contains` checks.
+    HalfOpen,
+    /// `(start, end)`, neither end allowed.
+    Open,
+}
+
+impl Interval {
+    pub const ALL: [Interval; 4] = [
+        Interval::Closed,
+        Interval::LeftOpen,
+        Interval::HalfOpen,
+        Interval::Open,
+    ];
+
+    /// Whether `value` is in `range` under this policy.
+    pub fn contains<T: PartialOrd>(self, range: &core::ops::Range<T>, value: &T) -> bool {
+        let above_start = if self.includes_start() {
+            *value >= range.start
+        } else {
+            *value > range.start
+        };
+        let below_end = if self.includes_end() {
+            *value <= range.end
+        } else {
+            *value < range.end
+        };
+        above_start && below_end
+    }
+
+    /// Whether no value is in `range` under this policy, because it is
+    /// inverted or has only one point, which is excluded.
+    pub fn is_empty<T: PartialOrd>(self, range: &core::ops::Range<T>) -> bool {
+        range.start > range.end || (range.start == range.end && self != Interval::Closed)
+    }
+
+    /// A stable, snake-case name for the policy, e.g. `"left_open"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Interval::Closed => "closed",
+            Interval::LeftOpen => "left_open",
+            Interval::HalfOpen => "half_open",
+            Interval::Open => "open",
+        }
+    }
+
+    fn includes_start(self) -> bool {
+        matches!(self, Interval::Closed | Interval::HalfOpen)
+    }
+
+    fn includes_end(self) -> bool {
+        matches!(self, Interval::Closed | Interval::LeftOpen)
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// `Display` of a range in the notation that matches its [`Interval`].
+struct Notation<'a, T>(&'a core::ops::Range<T>, Interval);
+
+impl<T: Display> Display for Notation<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let Notation(range, interval) = self;
+        match interval {
+            Interval::Closed => write!(f, "{}..={}", range.start, range.end),
+            Interval::LeftOpen => write!(f, "({}, {}]", range.start, range.end),
+            Interval::HalfOpen => write!(f, "{}..{}", range.start, range.end),
+            Interval::Open => write!(f, "({}, {})", range.start, range.end),
+        }
     }
 }
 
 /// This structure should be used in cases where a value must lie
 /// within a specific range
 #[derive(Clone)]
-pub struct Outside<T: Ord + Copy> {
-    /// The inclusive range into which the value must enter.
+pub struct Outside<T: PartialOrd + Copy> {
+    /// The range into which the value must enter.
     pub(crate) range: core::ops::Range<T>,
+    /// Which ends of `range` are allowed.
+    pub(crate) interval: Interval,
     /// The value that failed to be included into the range.
     pub(crate) value: T,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
 }
 
-impl<T: Ord + Copy + Debug> Debug for Outside<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<T: PartialOrd + Copy> Outside<T> {
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_in"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+
+    /// Which ends of the range were allowed.
+    pub fn interval(&self) -> Interval {
+        self.interval
+    }
+
+    /// Whether the range allows no value at all, so that the range is
+    /// to blame rather than the value, as [`assert_range`] would report.
+    pub fn is_empty_range(&self) -> bool {
+        self.interval.is_empty(&self.range)
+    }
+
+    fn below(&self) -> bool {
+        self.value < self.range.start
+            || (self.value == self.range.start && !self.interval.includes_start())
+    }
+
+    fn above(&self) -> bool {
+        self.value > self.range.end
+            || (self.value == self.range.end && !self.interval.includes_end())
+    }
+}
+
+impl<T: PartialOrd + Copy + Debug> Debug for Outside<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("Outside")
             .field("range", &self.range)
+            .field("interval", &self.interval)
             .field("value", &self.value)
             .finish()
     }
 }
 
-impl<T: Ord + Copy + Display> Display for Outside<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.value >= self.range.end {
-            write!(f, "Value {} exceeds maximum {}", self.value, self.range.end)
-        } else if self.value < self.range.start {
-            write!(f, "Value {} below minimum {}", self.value, self.range.start)
+impl<T: PartialOrd + Copy + Display> Display for Outside<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(
+            f,
+            "Value {} is outside the allowed range {}",
+            self.value,
+            Notation(&self.range, self.interval)
+        )?;
+        let (start, end) = (&self.range.start, &self.range.end);
+        // An inverted range rejects every value, so neither bound is
+        // the one to blame; error rendering must never panic.
+        if self.is_empty_range() {
+            f.write_str(" (the range is empty)")
+        } else if self.below() {
+            if self.interval.includes_start() {
+                write!(f, " (must be at least {})", start)
+            } else {
+                write!(f, " (must be greater than {})", start)
+            }
+        } else if self.above() {
+            if self.interval.includes_end() {
+                write!(f, " (must be at most {})", end)
+            } else {
+                write!(f, " (must be less than {})", end)
+            }
         } else {
-            panic!("An invalid instance of outside was created. Aborting")
+            Ok(())
         }
     }
 }
 
-impl<T: PartialEq + Ord + Copy> PartialEq for Outside<T> {
+impl<T: PartialOrd + Copy> PartialEq for Outside<T> {
     fn eq(&self, other: &Self) -> bool {
-        self.range == other.range && self.value == other.value
+        self.range == other.range && self.interval == other.interval && self.value == other.value
+    }
+}
+
+impl<T: PartialOrd + Copy + Debug + Display> core::error::Error for Outside<T> {}
+
+impl<T: PartialOrd + Copy + Display> Fields for Outside<T> {
+    fn kind(&self) -> &'static str {
+        "outside"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("start", &self.range.start);
+        visit("end", &self.range.end);
+        visit("value", &self.value);
+        visit("interval", &self.interval);
+    }
+
+    fn help(&self) -> Option<String> {
+        let range = Notation(&self.range, self.interval);
+        if self.is_empty_range() {
+            Some(format!(
+                "{} allows no value; fix the range, not the value",
+                range
+            ))
+        } else {
+            Some(format!("use a value in the range {}", range))
+        }
+    }
+
+    fn as_explain(&self) -> Option<&dyn Explain> {
+        Some(self)
     }
 }
 
 /// A thing is not a known value from a list
-#[derive(PartialEq, Eq, Clone)]
-pub struct Unknown<'a, T: Eq>{
+#[derive(Clone)]
+pub struct Unknown<'a, T: Eq> {
     /// The collection of things arranged in a linear sequence
     pub(crate) knowns: Option<&'a [T]>,
     /// The value that is not in the list
     pub(crate) value: T,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl<T: Eq> Unknown<'_, T> {
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_known"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
 }
 
+impl<T: Eq> PartialEq for Unknown<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.knowns == other.knowns && self.value == other.value
+    }
+}
+
+impl<T: Eq> Eq for Unknown<'_, T> {}
+
 impl<'a, T: Eq + Copy> Copy for Unknown<'a, T> {}
 
 impl<T: Eq + Debug> Debug for Unknown<'_, T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("Unknown")
             .field("knowns", &self.knowns)
             .field("value", &self.value)
@@ -157,56 +721,199 @@ impl<T: Eq + Debug> Debug for Unknown<'_, T> {
 }
 
 impl<T: Eq + Display> Display for Unknown<'_, T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "The value {} is not known", self.value)?;
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        self.fmt_truncated(f, Truncation::current())
+    }
+}
+
+impl<T: Eq + Display> Truncate for Unknown<'_, T> {
+    fn fmt_truncated(
+        &self,
+        f: &mut core::fmt::Formatter<'_>,
+        truncation: Truncation,
+    ) -> core::fmt::Result {
+        f.write_str("The value ")?;
+        truncation.write_value(f, &self.value)?;
+        f.write_str(" is not known")?;
         if let Some(knowns) = self.knowns {
-            write!(f, "Because it's not one of [{}]", join(&knowns, ", ")?)
+            f.write_str(", because it's not one of [")?;
+            truncation.write_list(f, knowns)?;
+            f.write_str("]")
         } else {
             f.write_str(".")
         }
     }
 }
 
+impl<T: Eq + Debug + Display> core::error::Error for Unknown<'_, T> {}
+
+impl<T: Eq + Display> Fields for Unknown<'_, T> {
+    fn kind(&self) -> &'static str {
+        "unknown"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("value", &self.value);
+        if let Some(knowns) = self.knowns {
+            if let Ok(knowns) = join(knowns, ", ") {
+                visit("knowns", &knowns);
+            }
+        }
+    }
+
+    fn help(&self) -> Option<String> {
+        let knowns = truncate::List(Truncation::current(), self.knowns?);
+        Some(format!("use one of [{}]", render(&knowns, false)))
+    }
+
+    fn as_explain(&self) -> Option<&dyn Explain> {
+        Some(self)
+    }
+}
+
 pub fn join<T: Display>(items: &[T], separator: &'static str) -> Result<String, core::fmt::Error> {
     use core::fmt::Write;
 
-    let first_element = items[0].to_string();
-    let mut buffer = String::with_capacity(
-        (items.len() - 1) * (separator.len() + first_element.len()) + first_element.len(),
-    );
-    for idx in 1..items.len() {
-        buffer.push_str(separator);
-        buffer.write_str(&items[idx].to_string())?;
+    let mut buffer = String::new();
+    for (idx, item) in items.iter().enumerate() {
+        if idx > 0 {
+            buffer.push_str(separator);
+        }
+        write!(buffer, "{}", item)?;
     }
     Ok(buffer)
 }
 
+/// The `Display` of every error with the `compact` feature: the code
+/// and the raw fields, e.g. `#1 expected=32 actual=0`.
+pub(crate) fn write_compact(
+    f: &mut core::fmt::Formatter<'_>,
+    error: &(impl Fields + ErrorCode + ?Sized),
+) -> core::fmt::Result {
+    write!(f, "#{}", error.code())?;
+    let mut result = Ok(());
+    error.for_each_field(&mut |name, value| {
+        if result.is_ok() {
+            result = write!(f, " {}={}", name, value);
+        }
+    });
+    result
+}
+
+/// Like `to_string`, but keeps whatever was written if a `Display`
+/// implementation fails, instead of panicking.
+pub(crate) fn render(value: &(impl Display + ?Sized), alternate: bool) -> String {
+    use core::fmt::Write;
+
+    let mut buffer = String::new();
+    let _ = if alternate {
+        write!(buffer, "{:#}", value)
+    } else {
+        write!(buffer, "{}", value)
+    };
+    buffer
+}
+
 pub fn assert_known_enum<'a, T: Eq>(knowns: &'a [T], value: T) -> Result<T, Unknown<'a, T>> {
     if knowns.contains(&value) {
         Ok(value)
     } else {
+        hook::observe("unknown", code::UNKNOWN, "assert_known_enum");
         Err(Unknown {
             knowns: Some(knowns),
             value,
+            provenance: "assert_known_enum",
         })
     }
 }
 
-pub fn assert_known<'a, T: Eq>(knowns: &'a [T], value: T) -> Result<T, Unknown<'_, T>> {
+pub fn assert_known<T: Eq>(knowns: &[T], value: T) -> Result<T, Unknown<'_, T>> {
     if knowns.contains(&value) {
         Ok(value)
     } else {
+        hook::observe("unknown", code::UNKNOWN, "assert_known");
         Err(Unknown {
             knowns: None,
             value,
+            provenance: "assert_known",
         })
     }
 }
 
+/// A type with a fixed set of textual spellings, such as an enum of
+/// subcommands or configuration options.
+pub trait Knowns {
+    /// Every spelling that `FromStr` accepts.
+    const KNOWNS: &'static [&'static str];
+}
+
+/// Parse `input`, and if that fails, report it as [`Unknown`] among
+/// the valid spellings of `T`.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::{parse_known, Knowns};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Level {
+///     Debug,
+///     Info,
+/// }
+///
+/// impl Knowns for Level {
+///     const KNOWNS: &'static [&'static str] = &["debug", "info"];
+/// }
+///
+/// impl std::str::FromStr for Level {
+///     type Err = ();
+///
+///     fn from_str(s: &str) -> Result<Self, ()> {
+///         match s {
+///             "debug" => Ok(Level::Debug),
+///             "info" => Ok(Level::Info),
+///             _ => Err(()),
+///         }
+///     }
+/// }
+///
+/// assert_eq!(parse_known::<Level>("info"), Ok(Level::Info));
+/// assert_eq!(
+///     parse_known::<Level>("loud").unwrap_err().to_string(),
+///     "The value loud is not known, because it's not one of [debug, info]"
+/// );
+/// ```
+pub fn parse_known<T: core::str::FromStr + Knowns>(input: &str) -> Result<T, Unknown<'_, &str>> {
+    input.parse().map_err(|_| {
+        hook::observe("unknown", code::UNKNOWN, "parse_known");
+        Unknown {
+            knowns: Some(T::KNOWNS),
+            value: input,
+            provenance: "parse_known",
+        }
+    })
+}
+
 #[cfg(test)]
 pub mod tests {
     use crate::{Mismatch, Outside, Unknown};
 
+    #[test]
+    #[cfg(feature = "compact")]
+    fn compact_messages() {
+        let error = crate::assert_eq(&32, &0).unwrap_err();
+        assert_eq!(error.to_string(), "#1 expected=32 actual=0");
+        let error = crate::assert_in(&7, &(1..5)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "#2 start=1 end=5 value=7 interval=left_open"
+        );
+        let mut errors = crate::MultiError::with_limit(0);
+        errors.push(error);
+        assert_eq!(errors.to_string(), "#44 count=1 omitted=1");
+    }
+
     #[test]
     fn usage_of_assert_eq() {
         assert_eq!(crate::assert_eq(&32_u32, &32), Ok(32));
@@ -214,7 +921,8 @@ pub mod tests {
             crate::assert_eq(&32_u32, &33),
             Err(Mismatch {
                 expected: 32,
-                actual: 33
+                actual: 33,
+                provenance: "assert_eq"
             })
         );
     }
@@ -227,18 +935,95 @@ pub mod tests {
             crate::assert_in(&6, &(1..5)),
             Err(Outside {
                 range: 1..5,
-                value: 6
+                interval: crate::Interval::LeftOpen,
+                value: 6,
+                provenance: "assert_in"
             })
         );
         assert_eq!(
             crate::assert_in(&0, &(1..5)),
             Err(Outside {
                 range: 1..5,
-                value: 0
+                interval: crate::Interval::LeftOpen,
+                value: 0,
+                provenance: "assert_in"
             })
         );
     }
 
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn display_never_panics() {
+        let at_start = crate::assert_in(&1, &(1..5)).unwrap_err();
+        assert_eq!(
+            at_start.to_string(),
+            "Value 1 is outside the allowed range (1, 5] (must be greater than 1)"
+        );
+        let inverted = crate::assert_in(&3, &core::ops::Range { start: 5, end: 1 }).unwrap_err();
+        assert_eq!(
+            inverted.to_string(),
+            "Value 3 is outside the allowed range (5, 1] (the range is empty)"
+        );
+        let empty: [u32; 0] = [];
+        let unknown = crate::assert_known_enum(&empty, 3).unwrap_err();
+        assert_eq!(
+            unknown.to_string(),
+            "The value 3 is not known, because it's not one of []"
+        );
+    }
+
+    #[test]
+    fn help_text() {
+        use crate::Fields;
+
+        assert_eq!(
+            crate::assert_in(&7, &(1..5)).unwrap_err().help().as_deref(),
+            Some("use a value in the range (1, 5]")
+        );
+        let inverted = crate::assert_in(&3, &core::ops::Range { start: 5, end: 1 }).unwrap_err();
+        assert!(inverted.is_empty_range());
+        assert_eq!(
+            inverted.help().as_deref(),
+            Some("(5, 1] allows no value; fix the range, not the value")
+        );
+        assert!(!crate::assert_in(&7, &(1..5)).unwrap_err().is_empty_range());
+        assert_eq!(
+            crate::assert_known_enum(&[1, 2], 3)
+                .unwrap_err()
+                .help()
+                .as_deref(),
+            Some("use one of [1, 2]")
+        );
+        assert_eq!(crate::assert_known(&[1, 2], 3).unwrap_err().help(), None);
+    }
+
+    #[test]
+    fn errors_are_thread_safe_contexts() {
+        // The bound of `error_stack::Context` and of `anyhow::Error: From<E>`.
+        fn context<C: core::error::Error + Send + Sync + 'static>() {}
+
+        context::<Mismatch<u32>>();
+        context::<Outside<u32>>();
+        context::<Unknown<'static, &'static str>>();
+        context::<crate::Caused<Mismatch<u32>>>();
+    }
+
+    #[test]
+    fn provenance() {
+        assert_eq!(
+            crate::assert_eq(&1, &2).unwrap_err().provenance(),
+            "assert_eq"
+        );
+        assert_eq!(
+            crate::assert_in(&0, &(1..2)).unwrap_err().provenance(),
+            "assert_in"
+        );
+        assert_eq!(
+            crate::assert_known(&[1], 2).unwrap_err().provenance(),
+            "assert_known"
+        );
+    }
+
     #[test]
     fn usage_of_unknown() {
         let knowns = vec![1, 2, 4, 6, 7, 20_u32];
@@ -247,7 +1032,8 @@ pub mod tests {
             crate::assert_known_enum(&knowns, 3),
             Err(Unknown {
                 knowns: Some(&knowns),
-                value: 3
+                value: 3,
+                provenance: "assert_known_enum"
             })
         );
         assert_eq!(crate::assert_known(&knowns, 2), Ok(2));
@@ -255,7 +1041,8 @@ pub mod tests {
             crate::assert_known(&knowns, 3),
             Err(Unknown {
                 knowns: None,
-                value: 3
+                value: 3,
+                provenance: "assert_known"
             })
         );
     }
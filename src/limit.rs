@@ -0,0 +1,249 @@
+//! Errors about capacities, quotas and other limits.
+
+use crate::time::Human;
+use crate::{code, hook, ErrorCode, Fields};
+use alloc::format;
+use alloc::string::String;
+use core::fmt::{Debug, Display};
+use core::ops::Sub;
+use core::time::Duration;
+
+/// Assert that `requested` does not exceed `limit`, e.g. for buffer
+/// sizes, quotas and batch sizes.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_capacity;
+///
+/// assert_eq!(assert_capacity(800_usize, 1000), Ok(800));
+/// assert_eq!(
+///     assert_capacity(1500_usize, 1000).unwrap_err().to_string(),
+///     "Requested 1500, which is 500 over the limit of 1000"
+/// );
+/// ```
+pub fn assert_capacity<T>(requested: T, limit: T) -> Result<T, Exceeded<T>>
+where
+    T: PartialOrd + Sub<Output = T> + Copy,
+{
+    if requested <= limit {
+        Ok(requested)
+    } else {
+        hook::observe("exceeded", code::EXCEEDED, "assert_capacity");
+        Err(Exceeded {
+            requested,
+            limit,
+            over: requested - limit,
+            provenance: "assert_capacity",
+        })
+    }
+}
+
+/// More was requested than a limit allows.
+#[derive(Clone, Copy)]
+pub struct Exceeded<T> {
+    pub(crate) requested: T,
+    pub(crate) limit: T,
+    /// How much `requested` is over `limit`.
+    pub(crate) over: T,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl<T> Exceeded<T> {
+    pub fn requested(&self) -> &T {
+        &self.requested
+    }
+
+    pub fn limit(&self) -> &T {
+        &self.limit
+    }
+
+    /// How much more was requested than allowed.
+    pub fn over(&self) -> &T {
+        &self.over
+    }
+
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_capacity"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl<T: PartialEq> PartialEq for Exceeded<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.requested == other.requested && self.limit == other.limit
+    }
+}
+
+impl<T: Eq> Eq for Exceeded<T> {}
+
+impl<T: Debug> Debug for Exceeded<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Exceeded")
+            .field("requested", &self.requested)
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
+
+impl<T: Display> Display for Exceeded<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(
+            f,
+            "Requested {}, which is {} over the limit of {}",
+            self.requested, self.over, self.limit
+        )
+    }
+}
+
+impl<T: Debug + Display> core::error::Error for Exceeded<T> {}
+
+impl<T: Display> Fields for Exceeded<T> {
+    fn kind(&self) -> &'static str {
+        "exceeded"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("requested", &self.requested);
+        visit("limit", &self.limit);
+        visit("over", &self.over);
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(format!("request at most {}", self.limit))
+    }
+}
+
+impl<T> ErrorCode for Exceeded<T> {
+    fn code(&self) -> u32 {
+        code::EXCEEDED
+    }
+}
+
+/// Too many requests were made in a time window.
+///
+/// As an [`crate::HttpGoof`], this is a `429 Too Many Requests` with a
+/// `Retry-After` of [`RateLimited::retry_after`].
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::{HttpGoof, RateLimited};
+/// use std::time::Duration;
+///
+/// let error = RateLimited::new(100, Duration::from_secs(60), Some(Duration::from_secs(5)));
+/// assert_eq!(
+///     error.to_string(),
+///     "More than 100 requests in 60s, retry after 5s"
+/// );
+/// assert_eq!(error.status(), 429);
+/// assert_eq!(HttpGoof::retry_after(&error), Some(Duration::from_secs(5)));
+/// ```
+#[derive(Clone, Copy)]
+pub struct RateLimited {
+    pub(crate) limit: u64,
+    pub(crate) window: Duration,
+    pub(crate) retry_after: Option<Duration>,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl RateLimited {
+    /// At most `limit` requests are allowed in every `window`, and the
+    /// next one will be after `retry_after`, if known.
+    pub fn new(limit: u64, window: Duration, retry_after: Option<Duration>) -> Self {
+        hook::observe("rate_limited", code::RATE_LIMITED, "RateLimited::new");
+        Self {
+            limit,
+            window,
+            retry_after,
+            provenance: "RateLimited::new",
+        }
+    }
+
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// How long to wait before the next request can succeed.
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.retry_after
+    }
+
+    /// The name of the function that created this error, e.g.
+    /// `"RateLimited::new"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl PartialEq for RateLimited {
+    fn eq(&self, other: &Self) -> bool {
+        self.limit == other.limit
+            && self.window == other.window
+            && self.retry_after == other.retry_after
+    }
+}
+
+impl Eq for RateLimited {}
+
+impl Debug for RateLimited {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RateLimited")
+            .field("limit", &self.limit)
+            .field("window", &self.window)
+            .field("retry_after", &self.retry_after)
+            .finish()
+    }
+}
+
+impl Display for RateLimited {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(f, "More than {} requests in {:?}", self.limit, self.window)?;
+        if let Some(retry_after) = self.retry_after {
+            write!(f, ", retry after {:?}", retry_after)?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for RateLimited {}
+
+impl Fields for RateLimited {
+    fn kind(&self) -> &'static str {
+        "rate_limited"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("limit", &self.limit);
+        visit("window", &Human(self.window));
+        if let Some(retry_after) = self.retry_after {
+            visit("retry_after", &Human(retry_after));
+        }
+    }
+}
+
+impl ErrorCode for RateLimited {
+    fn code(&self) -> u32 {
+        code::RATE_LIMITED
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::assert_capacity;
+
+    #[test]
+    fn limit_is_inclusive() {
+        assert_eq!(assert_capacity(10, 10), Ok(10));
+        assert_eq!(assert_capacity(1.5, 1.0).unwrap_err().over(), &0.5);
+    }
+}
@@ -0,0 +1,113 @@
+//! Logging errors through the `log` crate, for code bases that have not
+//! moved to `tracing`.
+
+use core::fmt::Display;
+use core::panic::Location;
+use log::{Level, Record};
+
+/// Logs the error of a `Result` and carries on.
+///
+/// The message is the `Display` of the error.  The record carries the
+/// file and line of the call, and `goof` as its module path and
+/// target, so that a logger can filter these errors as a whole.
+///
+/// # Examples
+/// ```rust
+/// use goof::LogGoofExt;
+/// use log::Level;
+///
+/// let port = goof::assert_in(&7, &(1..5)).log_err(Level::Warn);
+/// assert!(port.is_err());
+///
+/// let port = goof::assert_in(&2, &(1..5)).log_and_ignore();
+/// assert_eq!(port, Some(2));
+/// ```
+pub trait LogGoofExt<T> {
+    /// Log the error, if there is one, at `level` and return `self`
+    /// unchanged.
+    #[track_caller]
+    fn log_err(self, level: Level) -> Self;
+
+    /// Log the error, if there is one, as an error and discard it.
+    #[track_caller]
+    fn log_and_ignore(self) -> Option<T>;
+}
+
+impl<T, E: Display> LogGoofExt<T> for Result<T, E> {
+    #[track_caller]
+    fn log_err(self, level: Level) -> Self {
+        if let Err(error) = &self {
+            log(error, level, Location::caller());
+        }
+        self
+    }
+
+    #[track_caller]
+    fn log_and_ignore(self) -> Option<T> {
+        self.log_err(Level::Error).ok()
+    }
+}
+
+fn log(error: &dyn Display, level: Level, caller: &'static Location<'static>) {
+    if level > log::max_level() {
+        return;
+    }
+    log::logger().log(
+        &Record::builder()
+            .args(format_args!("{}", error))
+            .level(level)
+            .target(module_path!())
+            .module_path_static(Some(module_path!()))
+            .file_static(Some(caller.file()))
+            .line(Some(caller.line()))
+            .build(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LogGoofExt;
+    use log::{Level, LevelFilter, Log, Metadata, Record};
+    use std::sync::Mutex;
+
+    struct Logger(Mutex<Vec<(Level, String, String)>>);
+
+    impl Log for Logger {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record<'_>) {
+            let at = format!("{}:{}", record.file().unwrap(), record.line().unwrap());
+            let message = record.args().to_string();
+            self.0.lock().unwrap().push((record.level(), at, message));
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: Logger = Logger(Mutex::new(Vec::new()));
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn logs_and_passes_through() {
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(LevelFilter::Warn);
+
+        assert!(crate::assert_in(&7, &(1..5)).log_err(Level::Warn).is_err());
+        assert!(crate::assert_eq(&1, &2).log_err(Level::Info).is_err());
+        assert_eq!(crate::assert_eq(&1, &2).log_and_ignore(), None);
+        assert_eq!(crate::assert_eq(&1, &1).log_and_ignore(), Some(1));
+
+        let records = LOGGER.0.lock().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].0, Level::Warn);
+        assert!(records[0].1.starts_with("src/logging.rs:"));
+        assert_eq!(
+            records[0].2,
+            "Value 7 is outside the allowed range (1, 5] (must be at most 5)"
+        );
+        assert_eq!(records[1].0, Level::Error);
+        assert_eq!(records[1].2, "Expected 1, but got 2");
+    }
+}
@@ -0,0 +1,209 @@
+//! Diagnostics of the Language Server Protocol, so that a language
+//! server can publish the errors of goof-based validation as they
+//! are.
+//!
+//! They render as the JSON of an LSP `Diagnostic`, which is what
+//! `serde_json::from_str::<lsp_types::Diagnostic>` expects, without
+//! this crate depending on either.  The `lsp-types` feature converts
+//! them into an `lsp_types::Diagnostic` directly.
+
+use crate::envelope::write_escaped;
+use crate::{render, ErrorCode, Fields, Severity, Spanned};
+use alloc::string::String;
+use core::fmt::{Display, Formatter};
+
+/// A position as LSP counts: lines and UTF-16 code units, from zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LspPosition {
+    /// The line, from zero.
+    pub line: u32,
+    /// The UTF-16 code unit in the line, from zero.
+    pub character: u32,
+}
+
+impl LspPosition {
+    /// Where the byte at `offset` is in `source`.  An offset past the
+    /// end is clamped to it, and one in the middle of a character
+    /// counts as that character.
+    pub fn of(source: &str, offset: usize) -> Self {
+        let mut position = Self {
+            line: 0,
+            character: 0,
+        };
+        for (index, c) in source.char_indices() {
+            if index + c.len_utf8() > offset {
+                break;
+            }
+            if c == '\n' {
+                position.line += 1;
+                position.character = 0;
+            } else {
+                position.character += c.len_utf16() as u32;
+            }
+        }
+        position
+    }
+}
+
+/// An LSP diagnostic, built from a [`Spanned`] error and the text it
+/// was found in.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::lsp::LspDiagnostic;
+/// use goof::{Span, Spanned};
+///
+/// let source = "[server]\nport = 0\n";
+/// let error = goof::assert_in(&0, &(1..65535)).unwrap_err();
+/// let diagnostic = LspDiagnostic::new(&Spanned::new(error, Span::new(16, 1)), source);
+/// assert_eq!(
+///     diagnostic.to_string(),
+///     r#"{"range":{"start":{"line":1,"character":7},"end":{"line":1,"character":8}},"#.to_owned()
+///         + r#""severity":1,"code":2,"source":"goof","#
+///         + r#""message":"Value 0 is outside the allowed range (1, 65535] (must be greater than 1)"}"#
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LspDiagnostic {
+    /// Where the span of the error starts.
+    pub start: LspPosition,
+    /// Just past the end of the span of the error.
+    pub end: LspPosition,
+    pub severity: Severity,
+    /// The code of the error, as in [`ErrorCode`].
+    pub code: u32,
+    /// The name of the tool, `"goof"` unless changed.
+    pub source: &'static str,
+    /// The message of the error.
+    pub message: String,
+}
+
+impl LspDiagnostic {
+    /// A diagnostic of severity [`Severity::Error`] over the span of
+    /// `error` in `source`.
+    pub fn new<E: Fields + ErrorCode + Display>(error: &Spanned<E>, source: &str) -> Self {
+        let span = error.span();
+        Self {
+            start: LspPosition::of(source, span.offset),
+            end: LspPosition::of(source, span.offset.saturating_add(span.len)),
+            severity: Severity::Error,
+            code: error.error().code(),
+            source: "goof",
+            message: render(error.error(), false),
+        }
+    }
+
+    pub fn severity(self, severity: Severity) -> Self {
+        Self { severity, ..self }
+    }
+
+    pub fn source(self, source: &'static str) -> Self {
+        Self { source, ..self }
+    }
+}
+
+impl Display for LspDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            r#"{{"range":{{"start":{{"line":{},"character":{}}},"end":{{"line":{},"character":{}}}}},"severity":{},"code":{},"source":"#,
+            self.start.line,
+            self.start.character,
+            self.end.line,
+            self.end.character,
+            severity(self.severity),
+            self.code
+        )?;
+        write_escaped(f, self.source)?;
+        f.write_str(r#","message":"#)?;
+        write_escaped(f, &self.message)?;
+        f.write_str("}")
+    }
+}
+
+/// As in `DiagnosticSeverity`, from 1 for errors to 4 for hints.
+fn severity(severity: Severity) -> u8 {
+    match severity {
+        Severity::Fatal | Severity::Error => 1,
+        Severity::Warning => 2,
+        Severity::Hint => 4,
+    }
+}
+
+#[cfg(feature = "lsp-types")]
+impl From<LspPosition> for lsp_types::Position {
+    fn from(position: LspPosition) -> Self {
+        lsp_types::Position::new(position.line, position.character)
+    }
+}
+
+#[cfg(feature = "lsp-types")]
+impl From<LspDiagnostic> for lsp_types::Diagnostic {
+    fn from(diagnostic: LspDiagnostic) -> Self {
+        let severity = match diagnostic.severity {
+            Severity::Fatal | Severity::Error => lsp_types::DiagnosticSeverity::ERROR,
+            Severity::Warning => lsp_types::DiagnosticSeverity::WARNING,
+            Severity::Hint => lsp_types::DiagnosticSeverity::HINT,
+        };
+        lsp_types::Diagnostic {
+            range: lsp_types::Range::new(diagnostic.start.into(), diagnostic.end.into()),
+            severity: Some(severity),
+            code: Some(lsp_types::NumberOrString::Number(diagnostic.code as i32)),
+            source: Some(diagnostic.source.into()),
+            message: diagnostic.message,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LspPosition;
+
+    #[test]
+    fn characters_are_utf16() {
+        let source = "é😀x\ny";
+        assert_eq!(
+            LspPosition::of(source, 6),
+            LspPosition {
+                line: 0,
+                character: 3
+            }
+        );
+        assert_eq!(
+            LspPosition::of(source, 4),
+            LspPosition {
+                line: 0,
+                character: 1
+            }
+        );
+        assert_eq!(
+            LspPosition::of(source, 100),
+            LspPosition {
+                line: 1,
+                character: 1
+            }
+        );
+    }
+
+    #[cfg(feature = "lsp-types")]
+    #[test]
+    fn into_lsp_types() {
+        let source = "port = 0\n";
+        let error = crate::assert_in(&0, &(1..65535)).unwrap_err();
+        let diagnostic =
+            super::LspDiagnostic::new(&crate::Spanned::new(error, crate::Span::new(7, 1)), source);
+        let json = diagnostic.to_string();
+        let diagnostic = lsp_types::Diagnostic::from(diagnostic);
+        assert_eq!(
+            diagnostic.range,
+            lsp_types::Range::new(
+                lsp_types::Position::new(0, 7),
+                lsp_types::Position::new(0, 8)
+            )
+        );
+        assert_eq!(diagnostic.code, Some(lsp_types::NumberOrString::Number(2)));
+        assert_eq!(diagnostic, serde_json::from_str(&json).unwrap());
+    }
+}
@@ -0,0 +1,161 @@
+//! Checking the leading bytes that identify a binary format.
+
+use crate::{code, hook, ErrorCode, Fields, Hex};
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display};
+
+/// Assert that `data` starts with `magic`, returning the rest of it.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_magic;
+///
+/// assert_eq!(assert_magic(b"\x89PNG\r\n\x1a\n...", b"\x89PNG\r\n\x1a\n"), Ok(&b"..."[..]));
+/// assert_eq!(
+///     assert_magic(b"GIF89a", b"\x89PNG").unwrap_err().to_string(),
+///     r#"Expected magic 89504e47 ("\x89PNG"), but got 47494638 ("GIF8")"#
+/// );
+/// ```
+pub fn assert_magic<'a>(data: &'a [u8], magic: &'static [u8]) -> Result<&'a [u8], MagicMismatch> {
+    match data.strip_prefix(magic) {
+        Some(rest) => Ok(rest),
+        None => Err(MagicMismatch::observed(
+            magic,
+            &data[..data.len().min(magic.len())],
+            "assert_magic",
+        )),
+    }
+}
+
+/// Like [`assert_magic`], but reads the magic from `reader`, leaving
+/// it positioned right after it.
+///
+/// A mismatch, including running out of input, is reported as an
+/// `InvalidData` error wrapping a [`MagicMismatch`].
+///
+/// # Examples
+/// ```rust
+/// use goof::{read_magic, MagicMismatch};
+///
+/// let error = read_magic(&mut &b"PK"[..], b"\x7fELF").unwrap_err();
+/// let mismatch = error.get_ref().unwrap().downcast_ref::<MagicMismatch>().unwrap();
+/// assert_eq!(mismatch.actual(), b"PK");
+/// ```
+#[cfg(feature = "std")]
+pub fn read_magic(reader: &mut impl std::io::Read, magic: &'static [u8]) -> std::io::Result<()> {
+    use std::io::Read;
+
+    let mut actual = Vec::with_capacity(magic.len());
+    reader.take(magic.len() as u64).read_to_end(&mut actual)?;
+    if actual == magic {
+        Ok(())
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            MagicMismatch::observed(magic, &actual, "read_magic"),
+        ))
+    }
+}
+
+/// Data did not start with the magic number of its format.
+#[derive(Clone)]
+pub struct MagicMismatch {
+    pub(crate) expected: &'static [u8],
+    /// As many leading bytes as the magic is long, or fewer if the
+    /// data was shorter.
+    pub(crate) actual: Vec<u8>,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl MagicMismatch {
+    fn observed(expected: &'static [u8], actual: &[u8], provenance: &'static str) -> Self {
+        hook::observe("magic_mismatch", code::MAGIC_MISMATCH, provenance);
+        Self {
+            expected,
+            actual: actual.to_vec(),
+            provenance,
+        }
+    }
+
+    pub fn expected(&self) -> &'static [u8] {
+        self.expected
+    }
+
+    pub fn actual(&self) -> &[u8] {
+        &self.actual
+    }
+
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_magic"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl PartialEq for MagicMismatch {
+    fn eq(&self, other: &Self) -> bool {
+        self.expected == other.expected && self.actual == other.actual
+    }
+}
+
+impl Eq for MagicMismatch {}
+
+impl Debug for MagicMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MagicMismatch")
+            .field("expected", &Hex(self.expected))
+            .field("actual", &Hex(&self.actual[..]))
+            .finish()
+    }
+}
+
+impl Display for MagicMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(
+            f,
+            "Expected magic {} (\"{}\"), but got {} (\"{}\")",
+            Hex(self.expected),
+            self.expected.escape_ascii(),
+            Hex(&self.actual[..]),
+            self.actual.escape_ascii()
+        )
+    }
+}
+
+impl core::error::Error for MagicMismatch {}
+
+impl Fields for MagicMismatch {
+    fn kind(&self) -> &'static str {
+        "magic_mismatch"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("expected", &Hex(self.expected));
+        visit("actual", &Hex(&self.actual[..]));
+    }
+}
+
+impl ErrorCode for MagicMismatch {
+    fn code(&self) -> u32 {
+        code::MAGIC_MISMATCH
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::assert_magic;
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn short_data_is_a_mismatch() {
+        let error = assert_magic(b"\x7fE", b"\x7fELF").unwrap_err();
+        assert_eq!(error.actual(), b"\x7fE");
+        assert_eq!(
+            error.to_string(),
+            r#"Expected magic 7f454c46 ("\x7fELF"), but got 7f45 ("\x7fE")"#
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! Asserting that a value has a certain shape.
+
+use crate::{code, hook, ErrorCode, Fields};
+use alloc::string::String;
+use core::fmt::{Debug, Display, Write};
+
+/// A value did not match the pattern given to [`assert_matches!`].
+///
+/// The value is kept only as its `Debug` rendering, so the error does
+/// not borrow from, or require anything else of, the value.
+#[derive(Clone)]
+pub struct PatternMismatch {
+    /// The pattern, as written in the source.
+    pub(crate) pattern: &'static str,
+    /// The `Debug` rendering of the value that did not match.
+    pub(crate) value: String,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl PatternMismatch {
+    #[doc(hidden)]
+    pub fn __failed(pattern: &'static str, value: &dyn Debug) -> Self {
+        hook::observe("pattern_mismatch", code::PATTERN_MISMATCH, "assert_matches");
+        let mut rendered = String::new();
+        let _ = write!(rendered, "{:?}", value);
+        Self {
+            pattern,
+            value: rendered,
+            provenance: "assert_matches",
+        }
+    }
+
+    pub fn pattern(&self) -> &'static str {
+        self.pattern
+    }
+
+    /// The `Debug` rendering of the value.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_matches"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl PartialEq for PatternMismatch {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern == other.pattern && self.value == other.value
+    }
+}
+
+impl Eq for PatternMismatch {}
+
+impl Debug for PatternMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PatternMismatch")
+            .field("pattern", &self.pattern)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl Display for PatternMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(
+            f,
+            "Expected a value matching {}, but got {}",
+            self.pattern, self.value
+        )
+    }
+}
+
+impl core::error::Error for PatternMismatch {}
+
+impl Fields for PatternMismatch {
+    fn kind(&self) -> &'static str {
+        "pattern_mismatch"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("pattern", &self.pattern);
+        visit("value", &self.value);
+    }
+}
+
+impl ErrorCode for PatternMismatch {
+    fn code(&self) -> u32 {
+        code::PATTERN_MISMATCH
+    }
+}
+
+/// Assert that a value matches a pattern, optionally with an `if`
+/// guard, like `matches!` does.
+///
+/// Evaluates to `Ok(())` if it does, and to an
+/// `Err(`[`PatternMismatch`]`)` with the `Debug` of the value if it
+/// does not.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::{assert_matches, PatternMismatch};
+///
+/// #[derive(Debug)]
+/// enum State {
+///     Idle,
+///     Busy(u32),
+/// }
+///
+/// fn start(state: &State) -> Result<(), PatternMismatch> {
+///     assert_matches!(state, State::Idle)?;
+///     Ok(())
+/// }
+///
+/// assert!(start(&State::Idle).is_ok());
+/// assert_eq!(
+///     start(&State::Busy(3)).unwrap_err().to_string(),
+///     "Expected a value matching State::Idle, but got Busy(3)"
+/// );
+/// assert!(assert_matches!(State::Busy(3), State::Busy(jobs) if jobs < 4).is_ok());
+/// ```
+#[macro_export]
+macro_rules! assert_matches {
+    ($value:expr, $pattern:pat $(if $guard:expr)? $(,)?) => {
+        match $value {
+            $pattern $(if $guard)? => ::core::result::Result::<(), $crate::PatternMismatch>::Ok(()),
+            ref value => ::core::result::Result::Err($crate::PatternMismatch::__failed(
+                ::core::stringify!($pattern $(if $guard)?),
+                value,
+            )),
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ErrorCode, Fields};
+
+    #[test]
+    fn mismatch_records_pattern_and_value() {
+        let error = assert_matches!(Some(3), None).unwrap_err();
+        assert_eq!(error.pattern(), "None");
+        assert_eq!(error.value(), "Some(3)");
+        assert_eq!(error.kind(), "pattern_mismatch");
+        assert_eq!(error.code(), crate::code::PATTERN_MISMATCH);
+        assert_eq!(
+            assert_matches!(Some(3), Some(x) if x > 4)
+                .unwrap_err()
+                .pattern(),
+            "Some(x) if x > 4"
+        );
+    }
+}
@@ -0,0 +1,283 @@
+//! Errors that are nothing but a message.
+
+use crate::{code, hook, ErrorCode, Exceeded, Fields};
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::fmt::{Debug, Display};
+
+/// An error that is just a message, borrowed or owned.
+///
+/// It is the simplest way to fail with a reason that no caller is
+/// expected to inspect; [`goof!`](crate::goof!) formats one.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::{goof, Goof};
+///
+/// fn parse(header: &str) -> Result<u32, Goof<'static>> {
+///     header.parse().map_err(|_| goof!("bad header {:?}", header))
+/// }
+///
+/// assert_eq!(parse("x").unwrap_err().to_string(), r#"bad header "x""#);
+/// assert_eq!(Goof::from("plain").message(), "plain");
+/// ```
+#[derive(Clone)]
+pub struct Goof<'a> {
+    pub(crate) message: Cow<'a, str>,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl<'a> Goof<'a> {
+    pub fn new(message: impl Into<Cow<'a, str>>) -> Self {
+        Self::observed(message.into(), "Goof::new")
+    }
+
+    fn observed(message: Cow<'a, str>, provenance: &'static str) -> Self {
+        hook::observe("goof", code::GOOF, provenance);
+        Self {
+            message,
+            provenance,
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Copy a borrowed message, so that the error outlives it.
+    pub fn into_owned(self) -> Goof<'static> {
+        Goof {
+            message: Cow::Owned(self.message.into_owned()),
+            provenance: self.provenance,
+        }
+    }
+
+    /// The name of the function that created this error, e.g.
+    /// `"goof!"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+/// Fail with `message`; shorthand for [`Goof::new`].
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::goof;
+///
+/// let name = String::from("Content-Length");
+/// assert_eq!(goof::goof(&name).to_string(), "Content-Length");
+/// assert_eq!(goof::goof(&name).provenance(), "goof");
+/// ```
+pub fn goof<'a>(message: impl Into<Cow<'a, str>>) -> Goof<'a> {
+    Goof::observed(message.into(), "goof")
+}
+
+impl Goof<'static> {
+    #[doc(hidden)]
+    pub fn __format(arguments: core::fmt::Arguments<'_>) -> Self {
+        let message = match arguments.as_str() {
+            Some(message) => Cow::Borrowed(message),
+            None => Cow::Owned(alloc::fmt::format(arguments)),
+        };
+        Self::observed(message, "goof!")
+    }
+}
+
+impl<'a> From<&'a str> for Goof<'a> {
+    fn from(message: &'a str) -> Self {
+        Self::observed(Cow::Borrowed(message), "From<&str>")
+    }
+}
+
+impl From<String> for Goof<'static> {
+    fn from(message: String) -> Self {
+        Self::observed(Cow::Owned(message), "From<String>")
+    }
+}
+
+impl PartialEq for Goof<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.message == other.message
+    }
+}
+
+impl Eq for Goof<'_> {}
+
+impl Debug for Goof<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Goof").field(&self.message()).finish()
+    }
+}
+
+impl Display for Goof<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        f.write_str(self.message())
+    }
+}
+
+impl core::error::Error for Goof<'_> {}
+
+impl Fields for Goof<'_> {
+    fn kind(&self) -> &'static str {
+        "goof"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("message", &self.message());
+    }
+}
+
+impl ErrorCode for Goof<'_> {
+    fn code(&self) -> u32 {
+        code::GOOF
+    }
+}
+
+/// Format a [`Goof`], like `format!`.  A message without arguments
+/// is not copied.
+#[macro_export]
+macro_rules! goof {
+    ($($argument:tt)+) => {
+        $crate::Goof::__format(::core::format_args!($($argument)+))
+    };
+}
+
+/// A message of at most `N` bytes, stored inline, so that it can be
+/// built and returned without an allocator.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::InlineGoof;
+///
+/// let error = InlineGoof::<16>::try_from("bad header").unwrap();
+/// assert_eq!(error.to_string(), "bad header");
+///
+/// let too_long = InlineGoof::<4>::try_from("bad header").unwrap_err();
+/// assert_eq!(
+///     too_long.to_string(),
+///     "Requested 10, which is 6 over the limit of 4"
+/// );
+/// ```
+#[derive(Clone, Copy)]
+pub struct InlineGoof<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+    /// The name of the function that created this error
+    provenance: &'static str,
+}
+
+impl<const N: usize> InlineGoof<N> {
+    pub fn as_str(&self) -> &str {
+        // Only ever filled from a `str`, in one piece.
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
+    }
+
+    /// The name of the function that created this error, e.g.
+    /// `"InlineGoof::try_from"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for InlineGoof<N> {
+    type Error = Exceeded<usize>;
+
+    fn try_from(message: &str) -> Result<Self, Self::Error> {
+        let len = message.len();
+        if len > N {
+            hook::observe("exceeded", code::EXCEEDED, "InlineGoof::try_from");
+            return Err(Exceeded {
+                requested: len,
+                limit: N,
+                over: len - N,
+                provenance: "InlineGoof::try_from",
+            });
+        }
+        hook::observe("goof", code::GOOF, "InlineGoof::try_from");
+        let mut bytes = [0; N];
+        bytes[..len].copy_from_slice(message.as_bytes());
+        Ok(Self {
+            bytes,
+            len,
+            provenance: "InlineGoof::try_from",
+        })
+    }
+}
+
+impl<const N: usize> TryFrom<String> for InlineGoof<N> {
+    type Error = Exceeded<usize>;
+
+    fn try_from(message: String) -> Result<Self, Self::Error> {
+        Self::try_from(message.as_str())
+    }
+}
+
+impl<const N: usize> PartialEq for InlineGoof<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<const N: usize> Eq for InlineGoof<N> {}
+
+impl<const N: usize> Debug for InlineGoof<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("InlineGoof").field(&self.as_str()).finish()
+    }
+}
+
+impl<const N: usize> Display for InlineGoof<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        f.write_str(self.as_str())
+    }
+}
+
+impl<const N: usize> core::error::Error for InlineGoof<N> {}
+
+impl<const N: usize> Fields for InlineGoof<N> {
+    fn kind(&self) -> &'static str {
+        "goof"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("message", &self.as_str());
+    }
+}
+
+impl<const N: usize> ErrorCode for InlineGoof<N> {
+    fn code(&self) -> u32 {
+        code::GOOF
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Goof, InlineGoof};
+    use alloc::borrow::Cow;
+    use alloc::string::String;
+
+    #[test]
+    fn literal_is_borrowed() {
+        let error = goof!("no arguments");
+        assert!(matches!(error.message, Cow::Borrowed(_)));
+        assert_eq!(error.provenance(), "goof!");
+        let owned = Goof::new(String::from("owned"));
+        assert_eq!(owned.clone().into_owned(), owned);
+        assert_eq!(goof!("{}-{}", 1, 2), Goof::from("1-2"));
+    }
+
+    #[test]
+    fn fits_exactly() {
+        let error = InlineGoof::<5>::try_from(String::from("héll")).unwrap();
+        assert_eq!(error.as_str(), "héll");
+        assert_eq!(*InlineGoof::<4>::try_from("héll").unwrap_err().over(), 1);
+        assert_eq!(InlineGoof::<0>::try_from("").unwrap().as_str(), "");
+    }
+}
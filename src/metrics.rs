@@ -0,0 +1,124 @@
+//! Counters of how often each kind of assertion has failed, for
+//! exporting to whatever metrics system the application uses.
+
+use crate::code::Registry;
+use crate::ErrorCode;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+// Indexed by the built-in codes.  Leaves room for built-in codes to be
+// added without touching this file.  Slot 0 is not a code, so it
+// counts every code without a slot of its own.
+const SLOTS: usize = 128;
+const OTHER: usize = 0;
+static COUNTERS: [AtomicU64; SLOTS] = [const { AtomicU64::new(0) }; SLOTS];
+
+/// How many errors of one kind were created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Count {
+    pub kind: &'static str,
+    pub code: u32,
+    pub count: u64,
+}
+
+pub(crate) fn increment(code: u32) {
+    let slot = match code as usize {
+        slot if slot < SLOTS => slot,
+        _ => OTHER,
+    };
+    COUNTERS[slot].fetch_add(1, Ordering::Relaxed);
+}
+
+/// Count an error of the application's own, so that it shows up in
+/// the [`snapshot`] with the built-in ones.
+///
+/// Codes from [`crate::code::FIRST_FREE`] upwards are all counted
+/// together, under the kind `"other"`.
+pub fn record(error: &impl ErrorCode) {
+    increment(error.code());
+}
+
+/// The number of errors created by the assertions since the start of
+/// the process, or the last [`reset`], for every kind of error, and
+/// then the number of errors with any other code, under the kind
+/// `"other"` and the code 0.
+///
+/// # Examples
+/// ```rust
+/// let _ = goof::assert_in(&0, &(1..10));
+///
+/// let outside = goof::metrics::snapshot()
+///     .into_iter()
+///     .find(|count| count.kind == "outside")
+///     .unwrap();
+/// assert_eq!(outside.count, 1);
+/// ```
+pub fn snapshot() -> Vec<Count> {
+    Registry::new()
+        .iter()
+        .filter_map(|(code, kind)| {
+            let counter = COUNTERS.get(code as usize)?;
+            Some(Count {
+                kind,
+                code,
+                count: counter.load(Ordering::Relaxed),
+            })
+        })
+        .chain([Count {
+            kind: "other",
+            code: OTHER as u32,
+            count: COUNTERS[OTHER].load(Ordering::Relaxed),
+        }])
+        .collect()
+}
+
+/// Set every counter back to zero.
+pub fn reset() {
+    for counter in COUNTERS.iter() {
+        counter.store(0, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{snapshot, OTHER, SLOTS};
+    use crate::code::Registry;
+
+    #[test]
+    fn every_builtin_code_has_a_counter() {
+        assert!(Registry::new()
+            .iter()
+            .all(|(code, _)| (code as usize) < SLOTS && code as usize != OTHER));
+    }
+
+    #[test]
+    fn application_codes_are_counted_as_other() {
+        struct Mine;
+        impl crate::ErrorCode for Mine {
+            fn code(&self) -> u32 {
+                crate::code::FIRST_FREE + 7
+            }
+        }
+        let other = || snapshot().last().unwrap().count;
+        let before = other();
+        super::record(&Mine);
+        assert!(other() > before);
+        assert_eq!(snapshot().last().unwrap().kind, "other");
+    }
+
+    #[test]
+    fn counts_failed_assertions() {
+        let count = |kind| {
+            snapshot()
+                .into_iter()
+                .find(|count| count.kind == kind)
+                .unwrap()
+                .count
+        };
+        let before = count("unknown");
+        let _ = crate::assert_known(&[1], 2);
+        let _ = crate::assert_known(&[1], 1);
+        // Other tests may fail assertions concurrently.
+        assert!(count("unknown") > before);
+    }
+}
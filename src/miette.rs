@@ -0,0 +1,182 @@
+//! `miette::Diagnostic` for every error, so that command-line tools
+//! built on `miette` get its reports without any glue.
+//!
+//! The code of a diagnostic is the [`Fields::kind`] of the error and
+//! its help is [`Fields::help`].  A [`Spanned`] error labels its span,
+//! which `miette` shows once the report is given the source text, and
+//! a [`MultiError`] reports each of its errors as related.
+
+use crate::arith::{ArithGoof, DivisionByZero, Overflow, Underflow};
+use crate::config::ConfigGoof;
+use crate::env::{EnvGoof, InvalidEnv, MissingEnv};
+use crate::http::WithStatus;
+use crate::retry::RetriesExhausted;
+use crate::schema::{DeserGoof, DuplicateKey, InvalidData, MissingField, UnknownField};
+use crate::{
+    ArityMismatch, AsSource, At, BadText, BoundGoof, Caused, Duplicate, EmptyRange,
+    EndiannessMismatch, Exceeded, ExpectedOneOf, FailedPredicate, Fields, Goof, InlineGoof,
+    InsufficientPermission, InvalidTransition, IoGoof, LengthMismatch, LengthOutside,
+    MagicMismatch, Misaligned, Mismatch, MultiError, NotComparable, NotDisjoint, NotMultipleOf,
+    NotPositive, NotSubset, Outside, Panicked, ParseGoof, PatternMismatch, Permissions, RangeGoof,
+    RateLimited, Severity, SliceMismatch, Spanned, Timeout, ToHex, TooLarge, TooShort, TooSmall,
+    Unknown, UnknownBits, UnsortedAt, WithTransience, Zero,
+};
+use ::miette::{Diagnostic, LabeledSpan};
+use alloc::boxed::Box;
+use core::error::Error;
+use core::fmt::{Debug, Display};
+
+macro_rules! diagnostic {
+    ($([$($generics:tt)*] $error:ty,)*) => {
+        $(impl<$($generics)*> Diagnostic for $error {
+            fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+                Some(Box::new(self.kind()))
+            }
+
+            fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+                Fields::help(self).map(|help| Box::new(help) as Box<dyn Display>)
+            }
+        })*
+    };
+}
+
+diagnostic! {
+    [T: Debug + Display + Copy + Eq] Mismatch<T>,
+    [T: PartialOrd + Copy + Debug + Display] Outside<T>,
+    [T: Eq + Debug + Display] Unknown<'_, T>,
+    [] Panicked,
+    [] PatternMismatch,
+    [T: Debug + Display] FailedPredicate<T>,
+    [] LengthMismatch,
+    [] LengthOutside,
+    [T: Debug + Display] UnsortedAt<T>,
+    [T: Debug + Display] Duplicate<T>,
+    [T: Debug + Display] NotSubset<T>,
+    [T: Debug + Display] NotDisjoint<T>,
+    [T: Debug + Display] SliceMismatch<T>,
+    [] MagicMismatch,
+    [T: Debug + Display] ExpectedOneOf<'_, T>,
+    [I: Debug + Display] ParseGoof<I>,
+    [] MissingField,
+    [] UnknownField,
+    [] DuplicateKey,
+    [] InvalidData,
+    [] DeserGoof,
+    [] ConfigGoof,
+    [] MissingEnv,
+    [E: Error + 'static] InvalidEnv<E>,
+    [E: Error + 'static] EnvGoof<E>,
+    [] IoGoof,
+    [T: Debug + Display] Overflow<T>,
+    [T: Debug + Display] Underflow<T>,
+    [T: Debug + Display] DivisionByZero<T>,
+    [T: Debug + Display] ArithGoof<T>,
+    [T: Debug + Display] NotPositive<T>,
+    [] Zero,
+    [T: Debug + Display] NotMultipleOf<T>,
+    [] Timeout,
+    [E: Error + 'static] RetriesExhausted<E>,
+    [] crate::Cancelled,
+    [S: Debug + Display] InvalidTransition<'_, S>,
+    [P: Permissions + Debug] InsufficientPermission<P>,
+    [T: Debug + Display] Exceeded<T>,
+    [] RateLimited,
+    [] ArityMismatch,
+    [] Misaligned,
+    [] EndiannessMismatch,
+    [T: ToHex + Debug] UnknownBits<T>,
+    [] TooShort,
+    [] BadText,
+    [] Goof<'_>,
+    [const N: usize] InlineGoof<N>,
+    [T: Debug + Display] EmptyRange<T>,
+    [T: Debug + Display] NotComparable<T>,
+    [T: Debug + Display] TooLarge<T>,
+    [T: Debug + Display] TooSmall<T>,
+    [T: PartialOrd + Copy + Debug + Display] RangeGoof<T>,
+    [T: PartialOrd + Copy + Debug + Display] BoundGoof<T>,
+    [E: Error + Fields, S: AsSource + Debug + ?Sized] Caused<E, S>,
+    [E: Error + Fields + Display] At<E>,
+    [E: Error + Fields] WithStatus<E>,
+    [E: Error + Fields] WithTransience<E>,
+}
+
+#[cfg(feature = "semver")]
+diagnostic! {
+    [] crate::semver::VersionOutside,
+}
+
+impl<E: Error + Fields> Diagnostic for Spanned<E> {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(self.kind()))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Fields::help(self).map(|help| Box::new(help) as Box<dyn Display>)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let span = self.span();
+        let label = LabeledSpan::underline(span.offset..span.offset + span.len);
+        Some(Box::new(core::iter::once(label)))
+    }
+}
+
+/// The severity of a [`crate::Diagnostic`] carries over, with a
+/// [`Severity::Hint`] as advice and a [`Severity::Fatal`] as an error.
+impl<E: Error + Fields> Diagnostic for crate::Diagnostic<E> {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(self.kind()))
+    }
+
+    fn severity(&self) -> Option<::miette::Severity> {
+        Some(match self.severity() {
+            Severity::Hint => ::miette::Severity::Advice,
+            Severity::Warning => ::miette::Severity::Warning,
+            Severity::Error | Severity::Fatal => ::miette::Severity::Error,
+        })
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Fields::help(self).map(|help| Box::new(help) as Box<dyn Display>)
+    }
+}
+
+impl<E: Diagnostic + Fields + 'static> Diagnostic for MultiError<E> {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(self.kind()))
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>> {
+        Some(Box::new(
+            self.errors().iter().map(|error| error as &dyn Diagnostic),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Span, Spanned};
+    use ::miette::Diagnostic;
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn codes_help_and_labels() {
+        let error = crate::assert_in(&7, &(1..5)).unwrap_err();
+        assert_eq!(error.code().unwrap().to_string(), "outside");
+        assert_eq!(
+            error.help().unwrap().to_string(),
+            "use a value in the range (1, 5]"
+        );
+
+        let spanned = Spanned::new(error, Span::new(4, 2));
+        let labels: Vec<_> = spanned.labels().unwrap().collect();
+        assert_eq!((labels[0].offset(), labels[0].len()), (4, 2));
+
+        let multi = crate::MultiError::from(vec![
+            crate::assert_eq(&1, &2).unwrap_err(),
+            crate::assert_eq(&3, &4).unwrap_err(),
+        ]);
+        assert_eq!(multi.related().unwrap().count(), 2);
+    }
+}
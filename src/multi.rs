@@ -0,0 +1,445 @@
+//! Collecting every error of a validation instead of stopping at the
+//! first.
+
+use crate::{code, Aggregate, Child, Diagnostic, ErrorCode, Fields, Report, Severity};
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt::Display;
+
+/// Split `results` into the values and the errors, keeping the order
+/// of each.
+///
+/// # Examples
+/// ```rust
+/// use goof::partition_results;
+///
+/// let results = [3, 0, 7].iter().map(|port| goof::assert_in(port, &(1..65535)));
+/// let (valid, invalid) = partition_results(results);
+/// assert_eq!(valid, [3, 7]);
+/// assert_eq!(invalid.len(), 1);
+/// ```
+pub fn partition_results<T, E>(
+    results: impl IntoIterator<Item = Result<T, E>>,
+) -> (Vec<T>, Vec<E>) {
+    let mut values = Vec::new();
+    let mut errors = Vec::new();
+    for result in results {
+        match result {
+            Ok(value) => values.push(value),
+            Err(error) => errors.push(error),
+        }
+    }
+    (values, errors)
+}
+
+/// Collect the values of `results` if there are no errors, and every
+/// error otherwise.
+///
+/// Unlike collecting into a `Result<Vec<T>, E>`, this does not stop at
+/// the first error, so that all of them can be reported at once.  The
+/// hook set with `set_hook` was already told about each of them when
+/// it was created, so it is not told about the [`MultiError`] too.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::try_collect_all;
+///
+/// let ports = [3, 0, 70000].iter().map(|port| goof::assert_in(port, &(1..65535)));
+/// let error = try_collect_all(ports).unwrap_err();
+/// assert_eq!(error.len(), 2);
+/// assert!(error.to_string().starts_with("2 errors:\n    Value 0 is outside"));
+///
+/// assert_eq!(try_collect_all([Ok::<_, goof::Cancelled>(1), Ok(2)]), Ok(vec![1, 2]));
+/// ```
+pub fn try_collect_all<T, E>(
+    results: impl IntoIterator<Item = Result<T, E>>,
+) -> Result<Vec<T>, MultiError<E>> {
+    let (values, errors) = partition_results(results);
+    if errors.is_empty() {
+        Ok(values)
+    } else {
+        Err(MultiError::from(errors))
+    }
+}
+
+/// Every error found by a validation, in the order they were found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiError<E> {
+    errors: Vec<E>,
+    /// How many times each of `errors` was recorded.
+    repeats: Vec<usize>,
+    limit: usize,
+    omitted: usize,
+}
+
+impl<E> MultiError<E> {
+    pub const fn new() -> Self {
+        Self::with_limit(usize::MAX)
+    }
+
+    /// Keep only the first `limit` errors, and count the rest, so that
+    /// pathological inputs cannot exhaust memory.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # if cfg!(feature = "compact") { return; }
+    /// use goof::MultiError;
+    ///
+    /// let mut errors = MultiError::with_limit(1);
+    /// for row in 0..4_239 {
+    ///     errors.push(goof::assert_eq(&-1, &row).unwrap_err());
+    /// }
+    /// assert_eq!((errors.len(), errors.omitted()), (1, 4_238));
+    /// assert_eq!(
+    ///     errors.to_string(),
+    ///     "4,239 errors:\n    Expected -1, but got 0\n    …and 4,238 more errors"
+    /// );
+    /// ```
+    pub const fn with_limit(limit: usize) -> Self {
+        Self {
+            errors: Vec::new(),
+            repeats: Vec::new(),
+            limit,
+            omitted: 0,
+        }
+    }
+
+    /// Record `error`, or only count it if the limit was reached.
+    pub fn push(&mut self, error: E) -> &mut Self {
+        if self.errors.len() < self.limit {
+            self.errors.push(error);
+            self.repeats.push(1);
+        } else {
+            self.omitted += 1;
+        }
+        self
+    }
+
+    /// Record `error`, or only count it if an equal error was already
+    /// recorded, so that a failure repeated on every row is shown
+    /// once.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # if cfg!(feature = "compact") { return; }
+    /// use goof::MultiError;
+    ///
+    /// let mut errors = MultiError::new();
+    /// for row in [0, 0, 1, 0] {
+    ///     errors.push_dedup(goof::assert_eq(&32, &row).unwrap_err());
+    /// }
+    /// assert_eq!(
+    ///     errors.to_string(),
+    ///     "4 errors:\n    Expected 32, but got 0 (×3)\n    Expected 32, but got 1"
+    /// );
+    /// ```
+    pub fn push_dedup(&mut self, error: E) -> &mut Self
+    where
+        E: PartialEq,
+    {
+        match self.errors.iter().position(|known| *known == error) {
+            Some(index) => {
+                self.repeats[index] += 1;
+                self
+            }
+            None => self.push(error),
+        }
+    }
+
+    /// Merge the errors that are equal into the first of them, as if
+    /// they had all been recorded with [`MultiError::push_dedup`].
+    pub fn dedup(&mut self)
+    where
+        E: PartialEq,
+    {
+        let errors = core::mem::take(&mut self.errors);
+        let repeats = core::mem::take(&mut self.repeats);
+        for (error, repeats) in errors.into_iter().zip(repeats) {
+            match self.errors.iter().position(|known| *known == error) {
+                Some(index) => self.repeats[index] += repeats,
+                None => {
+                    self.errors.push(error);
+                    self.repeats.push(repeats);
+                }
+            }
+        }
+    }
+
+    /// Every kept error, with how many times it was recorded.
+    pub fn counted(&self) -> impl Iterator<Item = (&E, usize)> {
+        self.errors.iter().zip(self.repeats.iter().copied())
+    }
+
+    /// How many errors were counted but not kept.
+    pub fn omitted(&self) -> usize {
+        self.omitted
+    }
+
+    /// How many errors there were, kept or not.
+    pub fn total(&self) -> usize {
+        self.repeats.iter().sum::<usize>() + self.omitted
+    }
+
+    pub fn errors(&self) -> &[E] {
+        &self.errors
+    }
+
+    pub fn into_errors(self) -> Vec<E> {
+        self.errors
+    }
+
+    /// How many errors were kept, which is less than
+    /// [`MultiError::total`] past the limit or after deduplication.
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Whether no error was recorded at all, kept or not.
+    pub fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+}
+
+impl<E> MultiError<Diagnostic<E>> {
+    /// The diagnostics that are at least as severe as `minimum`, for
+    /// showing only the problems that matter.  Omitted diagnostics are
+    /// left out, since their severity was not kept.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # if cfg!(feature = "compact") { return; }
+    /// use goof::{Diagnostic, MultiError, Severity};
+    ///
+    /// let errors: MultiError<_> = [
+    ///     Diagnostic::new(Severity::Hint, goof::assert_eq(&4, &2).unwrap_err()),
+    ///     Diagnostic::new(Severity::Error, goof::assert_eq(&1, &2).unwrap_err()),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    /// assert_eq!(
+    ///     errors.at_least(Severity::Warning).to_string(),
+    ///     "1 error:\n    error: Expected 1, but got 2"
+    /// );
+    /// ```
+    pub fn at_least(&self, minimum: Severity) -> MultiError<&Diagnostic<E>> {
+        self.filtered(|diagnostic| diagnostic.severity() >= minimum)
+    }
+}
+
+impl MultiError<Report> {
+    /// The reports that are at least as severe as `minimum`, as in
+    /// [`Report::at_least`].
+    pub fn at_least(&self, minimum: Severity) -> MultiError<&Report> {
+        self.filtered(|report| report.severity() >= minimum)
+    }
+}
+
+impl<E> MultiError<E> {
+    fn filtered(&self, keep: impl Fn(&E) -> bool) -> MultiError<&E> {
+        let mut filtered = MultiError::new();
+        for (error, repeats) in self.counted().filter(|(error, _)| keep(error)) {
+            filtered.errors.push(error);
+            filtered.repeats.push(repeats);
+        }
+        filtered
+    }
+}
+
+impl<E> Default for MultiError<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> From<Vec<E>> for MultiError<E> {
+    fn from(errors: Vec<E>) -> Self {
+        Self {
+            repeats: alloc::vec![1; errors.len()],
+            errors,
+            ..Self::new()
+        }
+    }
+}
+
+impl<E> FromIterator<E> for MultiError<E> {
+    fn from_iter<I: IntoIterator<Item = E>>(errors: I) -> Self {
+        Self::from(errors.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<E> Extend<E> for MultiError<E> {
+    fn extend<I: IntoIterator<Item = E>>(&mut self, errors: I) {
+        for error in errors {
+            self.push(error);
+        }
+    }
+}
+
+impl<E> IntoIterator for MultiError<E> {
+    type Item = E;
+    type IntoIter = alloc::vec::IntoIter<E>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+impl<E: Display> Display for MultiError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(&Counts(self), f);
+        self.fmt_count(f)?;
+        f.write_str(":")?;
+        for (error, repeats) in self.counted() {
+            write!(f, "\n    {}", error)?;
+            if repeats > 1 {
+                write!(f, " (×{})", Grouped(repeats))?;
+            }
+        }
+        match self.omitted {
+            0 => Ok(()),
+            1 => f.write_str("\n    …and 1 more error"),
+            omitted => write!(f, "\n    …and {} more errors", Grouped(omitted)),
+        }
+    }
+}
+
+impl<E: Error> Error for MultiError<E> {}
+
+impl<E> MultiError<E> {
+    fn fmt_count(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.total() {
+            1 => f.write_str("1 error"),
+            count => write!(f, "{} errors", Grouped(count)),
+        }
+    }
+}
+
+/// A count with commas between the groups of thousands.
+struct Grouped(usize);
+
+impl Display for Grouped {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.0 < 1000 {
+            return write!(f, "{}", self.0);
+        }
+        write!(f, "{},{:03}", Grouped(self.0 / 1000), self.0 % 1000)
+    }
+}
+
+impl<E: Error + Fields + 'static> Aggregate for MultiError<E> {
+    /// Counts the errors that were not kept, too.
+    fn fmt_summary(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_count(f)
+    }
+
+    fn for_each_child<'a>(&'a self, visit: &mut dyn FnMut(Option<&dyn Display>, Child<'a>)) {
+        for error in &self.errors {
+            visit(None, Child::new(error));
+        }
+    }
+}
+
+impl<E: Error + Fields + 'static> Fields for MultiError<E> {
+    fn kind(&self) -> &'static str {
+        Counts(self).kind()
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        Counts(self).for_each_field(visit)
+    }
+
+    fn as_aggregate(&self) -> Option<&dyn Aggregate> {
+        Some(self)
+    }
+}
+
+/// The fields of a [`MultiError`], which do not depend on what its
+/// errors are, so that they can be shown for any of them.
+struct Counts<'a, E>(&'a MultiError<E>);
+
+impl<E> Fields for Counts<'_, E> {
+    fn kind(&self) -> &'static str {
+        "multi"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("count", &self.0.total());
+        if self.0.omitted > 0 {
+            visit("omitted", &self.0.omitted);
+        }
+    }
+}
+
+impl<E> ErrorCode for Counts<'_, E> {
+    fn code(&self) -> u32 {
+        code::MULTI
+    }
+}
+
+impl<E> ErrorCode for MultiError<E> {
+    fn code(&self) -> u32 {
+        code::MULTI
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Grouped, MultiError};
+    use crate::Aggregate;
+    use alloc::string::ToString;
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn tree_lists_every_error() {
+        let error: MultiError<_> = [crate::Goof::from("first"), crate::Goof::from("second")]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            error.tree().ascii().to_string(),
+            "2 errors\n+- first\n`- second"
+        );
+        assert_eq!(error.to_string(), "2 errors:\n    first\n    second");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn limit_counts_the_rest() {
+        let mut error = MultiError::with_limit(0);
+        assert!(error.is_empty());
+        error.push(crate::Cancelled::new());
+        assert!(!error.is_empty());
+        assert_eq!(error.to_string(), "1 error:\n    …and 1 more error");
+        assert_eq!(Grouped(1_002_003).to_string(), "1,002,003");
+        assert_eq!(Grouped(999).to_string(), "999");
+    }
+
+    #[test]
+    fn dedup_keeps_the_first_and_sums() {
+        let mut error = MultiError::from(alloc::vec![1, 2, 1]);
+        error.push_dedup(2);
+        error.dedup();
+        assert_eq!(
+            error.counted().collect::<alloc::vec::Vec<_>>(),
+            [(&1, 2), (&2, 2)]
+        );
+        assert_eq!(error.total(), 4);
+    }
+
+    #[test]
+    fn at_least_keeps_the_repeats() {
+        use crate::{Diagnostic, Report, Severity};
+
+        let mut error = MultiError::new();
+        for severity in [Severity::Fatal, Severity::Hint, Severity::Fatal] {
+            error.push_dedup(Diagnostic::new(severity, crate::Cancelled::new()));
+        }
+        let shown = error.at_least(Severity::Error);
+        assert_eq!((shown.len(), shown.total()), (1, 2));
+        assert!(error.at_least(Severity::Fatal).errors()[0].severity() == Severity::Fatal);
+
+        let reports: MultiError<_> = [Report::new(crate::Cancelled::new())].into_iter().collect();
+        assert!(reports.at_least(Severity::Fatal).is_empty());
+        assert_eq!(reports.at_least(Severity::Error).len(), 1);
+    }
+}
@@ -0,0 +1,337 @@
+//! Assertions about properties of numbers that are not ranges.
+//!
+//! Zero is taken to be `T::default()`, which it is for every
+//! primitive number.
+
+use crate::{code, hook, ErrorCode, Fields};
+use alloc::format;
+use alloc::string::String;
+use core::fmt::{Debug, Display};
+use core::ops::Rem;
+
+/// Assert that `value` is greater than zero.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_positive;
+///
+/// assert_eq!(assert_positive(0.5), Ok(0.5));
+/// assert_eq!(assert_positive(-3).unwrap_err().to_string(), "Value -3 is not positive");
+/// ```
+pub fn assert_positive<T: PartialOrd + Default + Copy>(value: T) -> Result<T, NotPositive<T>> {
+    if value > T::default() {
+        Ok(value)
+    } else {
+        hook::observe("not_positive", code::NOT_POSITIVE, "assert_positive");
+        Err(NotPositive {
+            value,
+            provenance: "assert_positive",
+        })
+    }
+}
+
+/// Assert that `value` is not zero, e.g. before dividing by it.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_nonzero;
+///
+/// assert_eq!(assert_nonzero(4_u32), Ok(4));
+/// assert_eq!(assert_nonzero(0_u32).unwrap_err().to_string(), "Value must not be zero");
+/// ```
+pub fn assert_nonzero<T: PartialEq + Default + Copy>(value: T) -> Result<T, Zero> {
+    if value != T::default() {
+        Ok(value)
+    } else {
+        hook::observe("zero", code::ZERO, "assert_nonzero");
+        Err(Zero {
+            provenance: "assert_nonzero",
+        })
+    }
+}
+
+/// The remainder that [`assert_multiple_of`] takes, which is `None`
+/// where `%` would overflow, e.g. for `i32::MIN % -1`.
+pub trait CheckedRem: Rem<Output = Self> + Sized {
+    fn checked_rem(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! checked_rem {
+    ($($t:ty),*) => {
+        $(impl CheckedRem for $t {
+            fn checked_rem(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_rem(self, rhs)
+            }
+        })*
+    };
+}
+
+checked_rem!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl CheckedRem for f32 {
+    fn checked_rem(self, rhs: Self) -> Option<Self> {
+        Some(self % rhs)
+    }
+}
+
+impl CheckedRem for f64 {
+    fn checked_rem(self, rhs: Self) -> Option<Self> {
+        Some(self % rhs)
+    }
+}
+
+/// Assert that `value` is a multiple of `factor`.  Only zero is a
+/// multiple of zero.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_multiple_of;
+///
+/// assert_eq!(assert_multiple_of(4096_u64, 512), Ok(4096));
+/// assert_eq!(
+///     assert_multiple_of(1000_u64, 512).unwrap_err().to_string(),
+///     "Value 1000 is not a multiple of 512"
+/// );
+/// ```
+pub fn assert_multiple_of<T>(value: T, factor: T) -> Result<T, NotMultipleOf<T>>
+where
+    T: CheckedRem + PartialEq + Default + Copy,
+{
+    let zero = T::default();
+    let multiple = if factor == zero {
+        value == zero
+    } else {
+        // Only overflows for a factor of -1, of which everything is a
+        // multiple.
+        value
+            .checked_rem(factor)
+            .is_none_or(|remainder| remainder == zero)
+    };
+    if multiple {
+        Ok(value)
+    } else {
+        hook::observe(
+            "not_multiple_of",
+            code::NOT_MULTIPLE_OF,
+            "assert_multiple_of",
+        );
+        Err(NotMultipleOf {
+            value,
+            factor,
+            provenance: "assert_multiple_of",
+        })
+    }
+}
+
+/// A value that had to be greater than zero was not.
+#[derive(Clone, Copy)]
+pub struct NotPositive<T> {
+    pub(crate) value: T,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl<T> NotPositive<T> {
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_positive"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl<T: PartialEq> PartialEq for NotPositive<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq> Eq for NotPositive<T> {}
+
+impl<T: Debug> Debug for NotPositive<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("NotPositive")
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<T: Display> Display for NotPositive<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(f, "Value {} is not positive", self.value)
+    }
+}
+
+impl<T: Debug + Display> core::error::Error for NotPositive<T> {}
+
+impl<T: Display> Fields for NotPositive<T> {
+    fn kind(&self) -> &'static str {
+        "not_positive"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("value", &self.value);
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(String::from("use a value greater than zero"))
+    }
+}
+
+impl<T> ErrorCode for NotPositive<T> {
+    fn code(&self) -> u32 {
+        code::NOT_POSITIVE
+    }
+}
+
+/// A value that had to be non-zero was zero.
+#[derive(Clone, Copy)]
+pub struct Zero {
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl Zero {
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_nonzero"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl PartialEq for Zero {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Zero {}
+
+impl Debug for Zero {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Zero")
+    }
+}
+
+impl Display for Zero {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        f.write_str("Value must not be zero")
+    }
+}
+
+impl core::error::Error for Zero {}
+
+impl Fields for Zero {
+    fn kind(&self) -> &'static str {
+        "zero"
+    }
+
+    fn for_each_field(&self, _: &mut dyn FnMut(&'static str, &dyn Display)) {}
+}
+
+impl ErrorCode for Zero {
+    fn code(&self) -> u32 {
+        code::ZERO
+    }
+}
+
+/// A value that had to be a multiple of a factor was not.
+#[derive(Clone, Copy)]
+pub struct NotMultipleOf<T> {
+    pub(crate) value: T,
+    pub(crate) factor: T,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl<T> NotMultipleOf<T> {
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    pub fn factor(&self) -> &T {
+        &self.factor
+    }
+
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_multiple_of"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl<T: PartialEq> PartialEq for NotMultipleOf<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.factor == other.factor
+    }
+}
+
+impl<T: Eq> Eq for NotMultipleOf<T> {}
+
+impl<T: Debug> Debug for NotMultipleOf<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("NotMultipleOf")
+            .field("value", &self.value)
+            .field("factor", &self.factor)
+            .finish()
+    }
+}
+
+impl<T: Display> Display for NotMultipleOf<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(
+            f,
+            "Value {} is not a multiple of {}",
+            self.value, self.factor
+        )
+    }
+}
+
+impl<T: Debug + Display> core::error::Error for NotMultipleOf<T> {}
+
+impl<T: Display> Fields for NotMultipleOf<T> {
+    fn kind(&self) -> &'static str {
+        "not_multiple_of"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("value", &self.value);
+        visit("factor", &self.factor);
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(format!("use a multiple of {}", self.factor))
+    }
+}
+
+impl<T> ErrorCode for NotMultipleOf<T> {
+    fn code(&self) -> u32 {
+        code::NOT_MULTIPLE_OF
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{assert_multiple_of, assert_positive};
+
+    #[test]
+    fn edge_cases() {
+        assert!(assert_positive(f64::NAN).is_err());
+        assert!(assert_positive(0).is_err());
+        assert_eq!(assert_multiple_of(0, 0), Ok(0));
+        assert!(assert_multiple_of(3, 0).is_err());
+        assert_eq!(assert_multiple_of(-6, 3), Ok(-6));
+        assert_eq!(assert_multiple_of(i32::MIN, -1), Ok(i32::MIN));
+        assert_eq!(assert_multiple_of(i8::MIN, -1), Ok(i8::MIN));
+        assert!(assert_multiple_of(7.5, 2.0).is_err());
+    }
+}
@@ -0,0 +1,174 @@
+//! Turning panics of code one does not control into errors.
+
+use crate::{code, hook, ErrorCode, Fields};
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::cell::{Cell, RefCell};
+use core::fmt::Display;
+use std::panic::{PanicHookInfo, UnwindSafe};
+use std::sync::Once;
+
+/// Where a panic happened.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PanicLocation {
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl Display for PanicLocation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+/// A panic caught by [`catch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Panicked {
+    pub(crate) message: String,
+    pub(crate) location: Option<PanicLocation>,
+}
+
+impl Panicked {
+    /// The message the code panicked with, if it was a string.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Where the code panicked.  Only known if no other panic hook was
+    /// installed after the first call to [`catch`].
+    pub fn location(&self) -> Option<&PanicLocation> {
+        self.location.as_ref()
+    }
+}
+
+impl Display for Panicked {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        match &self.location {
+            Some(location) => write!(f, "Panicked at {}: {}", location, self.message),
+            None => write!(f, "Panicked: {}", self.message),
+        }
+    }
+}
+
+impl core::error::Error for Panicked {}
+
+impl Fields for Panicked {
+    fn kind(&self) -> &'static str {
+        "panicked"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("message", &self.message);
+        if let Some(location) = &self.location {
+            visit("location", location);
+        }
+    }
+}
+
+impl ErrorCode for Panicked {
+    fn code(&self) -> u32 {
+        code::PANICKED
+    }
+}
+
+std::thread_local! {
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    static LOCATION: RefCell<Option<PanicLocation>> = const { RefCell::new(None) };
+}
+
+static INSTALL: Once = Once::new();
+
+fn install_hook() {
+    INSTALL.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info: &PanicHookInfo<'_>| {
+            if CATCHING.with(Cell::get) {
+                let location = info.location().map(|location| PanicLocation {
+                    file: location.file().into(),
+                    line: location.line(),
+                    column: location.column(),
+                });
+                LOCATION.with(|slot| *slot.borrow_mut() = location);
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// Run `f`, turning a panic into a [`Panicked`] error with the panic
+/// message and, if possible, its location.
+///
+/// A panic caught here is not printed: it is returned, and the hook
+/// set with [`crate::set_hook`] is told about it like any other error.
+/// Panics outside of `catch` still go to the panic hook as usual.
+///
+/// The first call replaces the process-wide panic hook with one that
+/// records the location of panics inside `catch` and hands every
+/// other panic to the hook it replaced.  A panic hook that the
+/// application installs afterwards replaces it in turn, and then
+/// [`Panicked::location`] is `None` and caught panics are reported by
+/// that hook.
+///
+/// # Examples
+/// ```rust
+/// let error = goof::catch(|| -> u32 { panic!("the flux capacitor is {}", "off") }).unwrap_err();
+/// assert_eq!(error.message(), "the flux capacitor is off");
+/// assert_eq!(goof::catch(|| 42), Ok(42));
+/// ```
+pub fn catch<T>(f: impl FnOnce() -> T + UnwindSafe) -> Result<T, Panicked> {
+    install_hook();
+    let was_catching = CATCHING.with(|catching| catching.replace(true));
+    let result = std::panic::catch_unwind(f);
+    CATCHING.with(|catching| catching.set(was_catching));
+    result.map_err(|payload| {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            String::from(*message)
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            String::from("Box<dyn Any>")
+        };
+        hook::observe("panicked", code::PANICKED, "catch");
+        Panicked {
+            message,
+            location: LOCATION.with(|slot| slot.borrow_mut().take()),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::catch;
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn records_message_and_location() {
+        let error = catch(|| -> () { panic!("boom") }).unwrap_err();
+        assert_eq!(error.message(), "boom");
+        let location = error.location().unwrap();
+        assert!(location.file.ends_with("panic.rs"));
+        assert_eq!(error.to_string(), format!("Panicked at {}: boom", location));
+    }
+
+    #[test]
+    fn caught_panics_are_observed() {
+        use crate::hook::{set_hook, take_hook, ErrorMeta, HOOK_LOCK};
+        use std::sync::Mutex;
+
+        let _lock = HOOK_LOCK
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        static SEEN: Mutex<Vec<ErrorMeta>> = Mutex::new(Vec::new());
+        set_hook(|meta| SEEN.lock().unwrap().push(*meta));
+        assert!(catch(|| -> () { panic!("boom") }).is_err());
+        assert!(take_hook());
+        assert!(SEEN.lock().unwrap().contains(&ErrorMeta {
+            kind: "panicked",
+            code: crate::code::PANICKED,
+            provenance: "catch",
+        }));
+    }
+}
@@ -0,0 +1,77 @@
+//! Validating large slices on every core with `rayon`, for bulk
+//! imports.
+
+use crate::{At, MultiError};
+use alloc::vec::Vec;
+use rayon::prelude::*;
+
+/// Check every element of `items` with `validator` on the global
+/// `rayon` pool, and collect every error at its index.
+///
+/// The errors are in the order of the elements, however the work was
+/// split, so the result is the same from one run to the next.  Like
+/// [`crate::try_collect_all`], this does not tell the hook about the
+/// [`MultiError`], since each error was reported when it was created.
+///
+/// # Examples
+/// ```rust
+/// use goof::parallel::par_validate;
+///
+/// let ports: Vec<u32> = (0..10_000).collect();
+/// let error = par_validate(&ports, |port| goof::assert_in(port, &(0..9_998))).unwrap_err();
+/// let paths: Vec<_> = error.errors().iter().map(|error| error.path().to_string()).collect();
+/// assert_eq!(paths, ["[0]", "[9999]"]);
+/// ```
+pub fn par_validate<T, U, E, F>(items: &[T], validator: F) -> Result<(), MultiError<At<E>>>
+where
+    T: Sync,
+    E: Send,
+    F: Fn(&T) -> Result<U, E> + Sync,
+{
+    let errors: Vec<At<E>> = items
+        .par_iter()
+        .enumerate()
+        .filter_map(|(index, item)| {
+            validator(item)
+                .err()
+                .map(|error| At::new(error).index(index))
+        })
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(MultiError::from(errors))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::par_validate;
+    use alloc::format;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn empty_and_valid() {
+        assert!(par_validate(&[] as &[u8], |_| Err::<(), _>(crate::Cancelled::new())).is_ok());
+        assert!(par_validate(&[1, 1, 1], |n| crate::assert_eq(&1, n)).is_ok());
+    }
+
+    #[test]
+    fn errors_in_input_order() {
+        // Far more items than cores, so that they span many chunks.
+        let items: Vec<usize> = (0..100_000).collect();
+        let error =
+            par_validate(&items, |&n| if n % 9_973 == 0 { Err(n) } else { Ok(()) }).unwrap_err();
+        let errors: Vec<_> = error
+            .errors()
+            .iter()
+            .map(|error| (error.path().to_string(), *error.error()))
+            .collect();
+        let expected: Vec<_> = (0..items.len())
+            .step_by(9_973)
+            .map(|index| (format!("[{}]", index), index))
+            .collect();
+        assert_eq!(errors, expected);
+    }
+}
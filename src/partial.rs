@@ -0,0 +1,338 @@
+//! Range checks for values that are only partially ordered, such as
+//! floating-point numbers, where NaN is neither in nor out of a range.
+
+use crate::compare::Limit;
+use crate::{code, hook, ErrorCode, Fields, Interval, Notation, Outside};
+use alloc::format;
+use alloc::string::String;
+use core::cmp::Ordering;
+use core::fmt::{Debug, Display};
+use core::ops::Range;
+
+/// Like [`crate::assert_in`], but for values that are only
+/// [`PartialOrd`], which fails with a [`NotComparable`] rather than an
+/// [`Outside`] when `value` cannot be compared with the range, e.g.
+/// because it is NaN.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::{assert_in_partial, RangeGoof};
+///
+/// assert_eq!(assert_in_partial(&0.5, &(0.0..1.0)), Ok(0.5));
+/// assert!(matches!(
+///     assert_in_partial(&1.5, &(0.0..1.0)),
+///     Err(RangeGoof::Outside(_))
+/// ));
+/// assert_eq!(
+///     assert_in_partial(&f64::NAN, &(0.0..1.0)).unwrap_err().to_string(),
+///     "Value NaN cannot be compared with the range (0, 1]"
+/// );
+/// ```
+pub fn assert_in_partial<T: PartialOrd + Copy>(
+    value: &T,
+    range: &Range<T>,
+) -> Result<T, RangeGoof<T>> {
+    let interval = Interval::LeftOpen;
+    if value.partial_cmp(&range.start).is_none() || value.partial_cmp(&range.end).is_none() {
+        hook::observe("not_comparable", code::NOT_COMPARABLE, "assert_in_partial");
+        Err(RangeGoof::NotComparable(NotComparable {
+            value: *value,
+            against: Against::Range(range.clone(), interval),
+            provenance: "assert_in_partial",
+        }))
+    } else if interval.contains(range, value) {
+        Ok(*value)
+    } else {
+        hook::observe("outside", code::OUTSIDE, "assert_in_partial");
+        Err(RangeGoof::Outside(Outside {
+            range: range.clone(),
+            interval,
+            value: *value,
+            provenance: "assert_in_partial",
+        }))
+    }
+}
+
+/// The ways [`assert_in_partial`] can fail.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RangeGoof<T: PartialOrd + Copy> {
+    Outside(Outside<T>),
+    NotComparable(NotComparable<T>),
+}
+
+impl<T: PartialOrd + Copy> From<Outside<T>> for RangeGoof<T> {
+    fn from(error: Outside<T>) -> Self {
+        Self::Outside(error)
+    }
+}
+
+impl<T: PartialOrd + Copy> From<NotComparable<T>> for RangeGoof<T> {
+    fn from(error: NotComparable<T>) -> Self {
+        Self::NotComparable(error)
+    }
+}
+
+impl<T: PartialOrd + Copy + Display> Display for RangeGoof<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            RangeGoof::Outside(error) => Display::fmt(error, f),
+            RangeGoof::NotComparable(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl<T: PartialOrd + Copy + Debug + Display> core::error::Error for RangeGoof<T> {}
+
+impl<T: PartialOrd + Copy + Display> Fields for RangeGoof<T> {
+    fn kind(&self) -> &'static str {
+        match self {
+            RangeGoof::Outside(error) => error.kind(),
+            RangeGoof::NotComparable(error) => error.kind(),
+        }
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        match self {
+            RangeGoof::Outside(error) => error.for_each_field(visit),
+            RangeGoof::NotComparable(error) => error.for_each_field(visit),
+        }
+    }
+
+    fn help(&self) -> Option<String> {
+        match self {
+            RangeGoof::Outside(error) => error.help(),
+            RangeGoof::NotComparable(error) => error.help(),
+        }
+    }
+
+    fn as_explain(&self) -> Option<&dyn crate::Explain> {
+        match self {
+            RangeGoof::Outside(error) => error.as_explain(),
+            RangeGoof::NotComparable(error) => error.as_explain(),
+        }
+    }
+}
+
+impl<T: PartialOrd + Copy> ErrorCode for RangeGoof<T> {
+    fn code(&self) -> u32 {
+        match self {
+            RangeGoof::Outside(error) => error.code(),
+            RangeGoof::NotComparable(error) => error.code(),
+        }
+    }
+}
+
+/// A value could not be compared with the range or the bound it had
+/// to be in.
+#[derive(Clone)]
+pub struct NotComparable<T> {
+    pub(crate) value: T,
+    pub(crate) against: Against<T>,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+/// What a [`NotComparable`] value was compared with.
+#[derive(Debug, Clone)]
+pub(crate) enum Against<T> {
+    Range(Range<T>, Interval),
+    Limit(Limit<T>),
+}
+
+impl<T> NotComparable<T> {
+    pub(crate) fn beyond(value: T, limit: Limit<T>, provenance: &'static str) -> Self {
+        Self {
+            value,
+            against: Against::Limit(limit),
+            provenance,
+        }
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_in_partial"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+// NaN is not equal to itself, but two errors about it are equal.
+impl<T: PartialOrd> PartialEq for NotComparable<T> {
+    fn eq(&self, other: &Self) -> bool {
+        fn same<T: PartialOrd>(a: &T, b: &T) -> bool {
+            a == b || (a.partial_cmp(a).is_none() && b.partial_cmp(b).is_none())
+        }
+        same(&self.value, &other.value)
+            && match (&self.against, &other.against) {
+                (Against::Range(a, a_interval), Against::Range(b, b_interval)) => {
+                    same(&a.start, &b.start) && same(&a.end, &b.end) && a_interval == b_interval
+                }
+                (Against::Limit(a), Against::Limit(b)) => {
+                    same(&a.bound, &b.bound) && a.upper == b.upper && a.strict == b.strict
+                }
+                _ => false,
+            }
+    }
+}
+
+impl<T: Debug> Debug for NotComparable<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut f = f.debug_struct("NotComparable");
+        f.field("value", &self.value);
+        match &self.against {
+            Against::Range(range, interval) => f.field("range", range).field("interval", interval),
+            Against::Limit(limit) => f.field("limit", limit),
+        };
+        f.finish()
+    }
+}
+
+impl<T: Display> Display for NotComparable<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        match &self.against {
+            Against::Range(range, interval) => write!(
+                f,
+                "Value {} cannot be compared with the range {}",
+                self.value,
+                Notation(range, *interval)
+            ),
+            Against::Limit(limit) => write!(
+                f,
+                "Value {} cannot be compared with the bound {}",
+                self.value, limit.bound
+            ),
+        }
+    }
+}
+
+impl<T: Debug + Display> core::error::Error for NotComparable<T> {}
+
+impl<T: Display> Fields for NotComparable<T> {
+    fn kind(&self) -> &'static str {
+        "not_comparable"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        match &self.against {
+            Against::Range(range, _) => {
+                visit("start", &range.start);
+                visit("end", &range.end);
+            }
+            Against::Limit(limit) if limit.upper => visit("max", &limit.bound),
+            Against::Limit(limit) => visit("min", &limit.bound),
+        }
+        visit("value", &self.value);
+        if let Against::Range(_, interval) = &self.against {
+            visit("interval", interval);
+        }
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(match &self.against {
+            Against::Range(range, interval) => {
+                format!("use a number in the range {}", Notation(range, *interval))
+            }
+            Against::Limit(limit) => format!("use a number {}", limit),
+        })
+    }
+}
+
+impl<T> ErrorCode for NotComparable<T> {
+    fn code(&self) -> u32 {
+        code::NOT_COMPARABLE
+    }
+}
+
+/// Floating-point numbers, which have a total order besides the
+/// partial one.
+pub trait TotalOrder: Copy {
+    fn total_cmp(&self, other: &Self) -> Ordering;
+}
+
+impl TotalOrder for f32 {
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        f32::total_cmp(self, other)
+    }
+}
+
+impl TotalOrder for f64 {
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        f64::total_cmp(self, other)
+    }
+}
+
+/// A number compared by its total order, in which NaN is greater than
+/// infinity, so that it can be checked with [`crate::assert_in`].
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::Total;
+///
+/// let range = Total(0.0)..Total(1.0);
+/// assert_eq!(goof::assert_in(&Total(0.5), &range), Ok(Total(0.5)));
+/// assert_eq!(
+///     goof::assert_in(&Total(f64::NAN), &range).unwrap_err().to_string(),
+///     "Value NaN is outside the allowed range (0, 1] (must be at most 1)"
+/// );
+/// ```
+#[derive(Clone, Copy)]
+pub struct Total<T>(pub T);
+
+impl<T: TotalOrder> PartialEq for Total<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.total_cmp(&other.0) == Ordering::Equal
+    }
+}
+
+impl<T: TotalOrder> Eq for Total<T> {}
+
+impl<T: TotalOrder> PartialOrd for Total<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: TotalOrder> Ord for Total<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl<T: Debug> Debug for Total<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl<T: Display> Display for Total<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{assert_in_partial, RangeGoof};
+    use crate::ErrorCode;
+
+    #[test]
+    fn nan_bounds_are_not_comparable() {
+        let error = assert_in_partial(&0.5_f32, &(f32::NAN..1.0)).unwrap_err();
+        assert!(matches!(error, RangeGoof::NotComparable(_)));
+        assert_eq!(error.code(), crate::code::NOT_COMPARABLE);
+        assert_eq!(
+            assert_in_partial(&f64::NAN, &(0.0..1.0)),
+            assert_in_partial(&f64::NAN, &(0.0..1.0))
+        );
+        assert_ne!(
+            assert_in_partial(&1.0, &(f64::NAN..1.0)),
+            assert_in_partial(&2.0, &(f64::NAN..1.0))
+        );
+    }
+}
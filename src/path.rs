@@ -0,0 +1,297 @@
+//! Where in a nested structure an error was found.
+
+use crate::{Aggregate, Child, Fields};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt::{Debug, Display};
+
+/// One step into a nested structure.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Segment {
+    /// A field of a struct, or a key of a map.
+    Field(String),
+    /// An element of a sequence.
+    Index(usize),
+}
+
+/// A path into a nested structure, such as `servers[2].port`.
+///
+/// # Examples
+/// ```rust
+/// use goof::Path;
+///
+/// let path = Path::new().field("servers").index(2).field("port");
+/// assert_eq!(path.to_string(), "servers[2].port");
+/// assert_eq!(path.pointer().to_string(), "/servers/2/port");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Path {
+    segments: Vec<Segment>,
+}
+
+impl Path {
+    /// The empty path, pointing at the root.
+    pub const fn new() -> Self {
+        Self {
+            segments: Vec::new(),
+        }
+    }
+
+    /// Step into the field `name`.
+    pub fn field(mut self, name: impl Into<String>) -> Self {
+        self.segments.push(Segment::Field(name.into()));
+        self
+    }
+
+    /// Step into the element at `index`.
+    pub fn index(mut self, index: usize) -> Self {
+        self.segments.push(Segment::Index(index));
+        self
+    }
+
+    /// Put `segment` in front, for when an error travels out of the
+    /// structure it was found in.
+    pub fn push_front(&mut self, segment: Segment) {
+        self.segments.insert(0, segment);
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Render as a JSON pointer (RFC 6901) instead.
+    pub fn pointer(&self) -> Pointer<'_> {
+        Pointer(self)
+    }
+}
+
+impl Display for Path {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (position, segment) in self.segments.iter().enumerate() {
+            match segment {
+                Segment::Field(name) if position == 0 => f.write_str(name)?,
+                Segment::Field(name) => write!(f, ".{}", name)?,
+                Segment::Index(index) => write!(f, "[{}]", index)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A path of just the field `name`.
+impl From<&str> for Path {
+    fn from(name: &str) -> Self {
+        Self::new().field(name)
+    }
+}
+
+/// `Display` adapter returned by [`Path::pointer`].
+pub struct Pointer<'a>(&'a Path);
+
+impl Display for Pointer<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for segment in &self.0.segments {
+            f.write_str("/")?;
+            match segment {
+                Segment::Field(name) => {
+                    for c in name.chars() {
+                        match c {
+                            '~' => f.write_str("~0")?,
+                            '/' => f.write_str("~1")?,
+                            c => write!(f, "{}", c)?,
+                        }
+                    }
+                }
+                Segment::Index(index) => write!(f, "{}", index)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An error `E` found at a [`Path`].
+///
+/// Nested validation wraps the error once, where it is found, and
+/// each enclosing level then adds its own step in front with
+/// [`At::field`] or [`At::index`].
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::{At, Outside};
+///
+/// fn port(port: u32) -> Result<u32, At<Outside<u32>>> {
+///     goof::assert_in(&port, &(0..65535)).map_err(|error| At::from(error).field("port"))
+/// }
+///
+/// fn servers(ports: &[u32]) -> Result<(), At<Outside<u32>>> {
+///     for (index, p) in ports.iter().enumerate() {
+///         port(*p).map_err(|error| error.index(index).field("servers"))?;
+///     }
+///     Ok(())
+/// }
+///
+/// let error = servers(&[80, 443, 70000]).unwrap_err();
+/// assert_eq!(error.to_string(), "servers[2].port: Value 70000 is outside the allowed range (0, 65535] (must be at most 65535)");
+/// ```
+pub struct At<E> {
+    error: E,
+    path: Path,
+}
+
+impl<E> At<E> {
+    /// Wrap `error`, found at the root.
+    pub const fn new(error: E) -> Self {
+        Self {
+            error,
+            path: Path::new(),
+        }
+    }
+
+    /// The error was found within the field `name`.
+    pub fn field(mut self, name: impl Into<String>) -> Self {
+        self.path.push_front(Segment::Field(name.into()));
+        self
+    }
+
+    /// The error was found within the element at `index`.
+    pub fn index(mut self, index: usize) -> Self {
+        self.path.push_front(Segment::Index(index));
+        self
+    }
+
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+}
+
+impl<E> From<E> for At<E> {
+    fn from(error: E) -> Self {
+        Self::new(error)
+    }
+}
+
+impl<E: Clone> Clone for At<E> {
+    fn clone(&self) -> Self {
+        Self {
+            error: self.error.clone(),
+            path: self.path.clone(),
+        }
+    }
+}
+
+impl<E: PartialEq> PartialEq for At<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.error == other.error && self.path == other.path
+    }
+}
+
+impl<E: Eq> Eq for At<E> {}
+
+impl<E: Debug> Debug for At<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("At")
+            .field("error", &self.error)
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl<E: Display> Display for At<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if cfg!(feature = "compact") {
+            Display::fmt(&self.error, f)?;
+            return write!(f, " path={}", self.path);
+        }
+        if !self.path.is_empty() {
+            write!(f, "{}: ", self.path)?;
+        }
+        Display::fmt(&self.error, f)
+    }
+}
+
+impl<E: Error> Error for At<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+}
+
+impl<E: Fields + Display> Fields for At<E> {
+    fn kind(&self) -> &'static str {
+        self.error.kind()
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        self.error.for_each_field(visit);
+        visit("path", &self.path);
+    }
+
+    fn help(&self) -> Option<String> {
+        self.error.help()
+    }
+
+    fn as_aggregate(&self) -> Option<&dyn Aggregate> {
+        self.error.as_aggregate().map(|_| self as &dyn Aggregate)
+    }
+
+    fn severity(&self) -> crate::Severity {
+        self.error.severity()
+    }
+
+    fn as_explain(&self) -> Option<&dyn crate::Explain> {
+        self.error.as_explain()
+    }
+}
+
+/// An aggregate found at a path is drawn as a subtree headed by the
+/// path.
+impl<E: Fields + Display> Aggregate for At<E> {
+    fn fmt_summary(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if !self.path.is_empty() {
+            write!(f, "{}: ", self.path)?;
+        }
+        match self.error.as_aggregate() {
+            Some(inner) => inner.fmt_summary(f),
+            None => Display::fmt(&self.error, f),
+        }
+    }
+
+    fn for_each_child<'a>(&'a self, visit: &mut dyn FnMut(Option<&dyn Display>, Child<'a>)) {
+        if let Some(inner) = self.error.as_aggregate() {
+            inner.for_each_child(visit);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{At, Path};
+
+    #[test]
+    fn pointer_escapes() {
+        let path = Path::new().field("a/b").field("m~n").index(0);
+        assert_eq!(path.pointer().to_string(), "/a~1b/m~0n/0");
+        assert_eq!(path.to_string(), "a/b.m~n[0]");
+        assert_eq!(Path::new().index(1).field("x").to_string(), "[1].x");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn root_has_no_prefix() {
+        let error = At::new(crate::assert_eq(&1, &2).unwrap_err());
+        assert_eq!(error.to_string(), "Expected 1, but got 2");
+    }
+}
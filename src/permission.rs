@@ -0,0 +1,252 @@
+//! Errors for authorization checks.
+
+use crate::{code, hook, ErrorCode, Fields};
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display, Formatter};
+
+/// A set of permissions or capabilities, such as a bitmask or a
+/// collection of enum variants.
+///
+/// Implemented for the unsigned integers, as bitmasks, and for `Vec`
+/// and `BTreeSet`.  A `bitflags` type needs a few lines:
+/// ```rust
+/// use core::fmt::Formatter;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// struct Flags(u8);
+///
+/// impl goof::Permissions for Flags {
+///     fn missing(&self, held: &Self) -> Option<Self> {
+///         self.0.missing(&held.0).map(Flags)
+///     }
+///
+///     fn fmt_list(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+///         self.0.fmt_list(f)
+///     }
+/// }
+/// ```
+pub trait Permissions: Sized {
+    /// The permissions in `self` that are not in `held`, if any.
+    fn missing(&self, held: &Self) -> Option<Self>;
+
+    /// Write every permission, separated by `", "`.
+    fn fmt_list(&self, f: &mut Formatter<'_>) -> core::fmt::Result;
+}
+
+macro_rules! bitmask {
+    ($($t:ty)*) => {
+        $(
+            /// Every set bit is a permission, listed as a hexadecimal
+            /// mask.
+            impl Permissions for $t {
+                fn missing(&self, held: &Self) -> Option<Self> {
+                    Some(self & !held).filter(|missing| *missing != 0)
+                }
+
+                fn fmt_list(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                    let mut first = true;
+                    for bit in 0..<$t>::BITS {
+                        let flag = (1 as $t) << bit;
+                        if self & flag != 0 {
+                            if !first {
+                                f.write_str(", ")?;
+                            }
+                            write!(f, "{:#x}", flag)?;
+                            first = false;
+                        }
+                    }
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+bitmask!(u8 u16 u32 u64 u128 usize);
+
+fn fmt_items<T: Display>(
+    items: impl IntoIterator<Item = T>,
+    f: &mut Formatter<'_>,
+) -> core::fmt::Result {
+    for (index, item) in items.into_iter().enumerate() {
+        if index > 0 {
+            f.write_str(", ")?;
+        }
+        Display::fmt(&item, f)?;
+    }
+    Ok(())
+}
+
+impl<T: PartialEq + Clone + Display> Permissions for Vec<T> {
+    fn missing(&self, held: &Self) -> Option<Self> {
+        let missing: Vec<T> = self
+            .iter()
+            .filter(|permission| !held.contains(permission))
+            .cloned()
+            .collect();
+        Some(missing).filter(|missing| !missing.is_empty())
+    }
+
+    fn fmt_list(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        fmt_items(self, f)
+    }
+}
+
+impl<T: Ord + Clone + Display> Permissions for BTreeSet<T> {
+    fn missing(&self, held: &Self) -> Option<Self> {
+        let missing: BTreeSet<T> = self.difference(held).cloned().collect();
+        Some(missing).filter(|missing| !missing.is_empty())
+    }
+
+    fn fmt_list(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        fmt_items(self, f)
+    }
+}
+
+/// `Display` of a [`Permissions`] as a list.
+struct List<'a, P>(&'a P);
+
+impl<P: Permissions> Display for List<'_, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.0.fmt_list(f)
+    }
+}
+
+/// Assert that `held` includes every permission in `required`.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_permitted;
+///
+/// let held = vec!["read", "write"];
+/// assert!(assert_permitted(vec!["read"], held.clone()).is_ok());
+///
+/// let error = assert_permitted(vec!["read", "delete", "admin"], held).unwrap_err();
+/// assert_eq!(error.missing(), &["delete", "admin"]);
+/// assert_eq!(error.to_string(), "Not permitted without delete, admin");
+///
+/// assert_eq!(
+///     assert_permitted(0b110_u8, 0b010).unwrap_err().to_string(),
+///     "Not permitted without 0x4"
+/// );
+/// ```
+pub fn assert_permitted<P: Permissions>(
+    required: P,
+    held: P,
+) -> Result<(), InsufficientPermission<P>> {
+    match required.missing(&held) {
+        None => Ok(()),
+        Some(missing) => {
+            hook::observe(
+                "insufficient_permission",
+                code::INSUFFICIENT_PERMISSION,
+                "assert_permitted",
+            );
+            Err(InsufficientPermission {
+                required,
+                held,
+                missing,
+                provenance: "assert_permitted",
+            })
+        }
+    }
+}
+
+/// An action needed permissions that were not held.
+#[derive(Clone)]
+pub struct InsufficientPermission<P> {
+    pub(crate) required: P,
+    pub(crate) held: P,
+    /// The part of `required` that is not in `held`.
+    pub(crate) missing: P,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl<P> InsufficientPermission<P> {
+    pub fn required(&self) -> &P {
+        &self.required
+    }
+
+    pub fn held(&self) -> &P {
+        &self.held
+    }
+
+    pub fn missing(&self) -> &P {
+        &self.missing
+    }
+
+    /// The name of the function that created this error, e.g.
+    /// `"assert_permitted"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl<P: PartialEq> PartialEq for InsufficientPermission<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.required == other.required && self.held == other.held
+    }
+}
+
+impl<P: Eq> Eq for InsufficientPermission<P> {}
+
+impl<P: Debug> Debug for InsufficientPermission<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("InsufficientPermission")
+            .field("required", &self.required)
+            .field("held", &self.held)
+            .field("missing", &self.missing)
+            .finish()
+    }
+}
+
+impl<P: Permissions> Display for InsufficientPermission<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(f, "Not permitted without {}", List(&self.missing))
+    }
+}
+
+impl<P: Permissions + Debug> core::error::Error for InsufficientPermission<P> {}
+
+impl<P: Permissions> Fields for InsufficientPermission<P> {
+    fn kind(&self) -> &'static str {
+        "insufficient_permission"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("required", &List(&self.required));
+        visit("held", &List(&self.held));
+        visit("missing", &List(&self.missing));
+    }
+}
+
+impl<P> ErrorCode for InsufficientPermission<P> {
+    fn code(&self) -> u32 {
+        code::INSUFFICIENT_PERMISSION
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::assert_permitted;
+    use alloc::collections::BTreeSet;
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn sets_and_masks() {
+        let set = |items: &[&'static str]| items.iter().copied().collect::<BTreeSet<_>>();
+        let error = assert_permitted(set(&["b", "a", "c"]), set(&["b"])).unwrap_err();
+        assert_eq!(error.to_string(), "Not permitted without a, c");
+        assert!(assert_permitted(0_u32, 0).is_ok());
+        assert_eq!(
+            assert_permitted(u128::MAX, u128::MAX >> 1)
+                .unwrap_err()
+                .missing(),
+            &(1 << 127)
+        );
+    }
+}
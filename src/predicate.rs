@@ -0,0 +1,146 @@
+//! Asserting arbitrary properties of a value.
+
+use crate::{code, hook, ErrorCode, Fields};
+use alloc::format;
+use alloc::string::String;
+use core::fmt::{Debug, Display};
+
+/// Assert that `predicate` holds for `value`, described for humans by
+/// `description`.
+///
+/// This covers the checks that are not equality, ranges or
+/// membership, while still producing an error that says what was
+/// checked.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::{assert_that, FailedPredicate};
+///
+/// fn batch_size(size: u32) -> Result<u32, FailedPredicate<u32>> {
+///     assert_that(size, |size| size.is_power_of_two(), "is a power of two")
+/// }
+///
+/// assert_eq!(batch_size(64), Ok(64));
+/// assert_eq!(
+///     batch_size(48).unwrap_err().to_string(),
+///     "Value 48 fails the check: is a power of two"
+/// );
+/// ```
+pub fn assert_that<T>(
+    value: T,
+    predicate: impl FnOnce(&T) -> bool,
+    description: &'static str,
+) -> Result<T, FailedPredicate<T>> {
+    if predicate(&value) {
+        Ok(value)
+    } else {
+        hook::observe("failed_predicate", code::FAILED_PREDICATE, "assert_that");
+        Err(FailedPredicate {
+            value,
+            description,
+            provenance: "assert_that",
+        })
+    }
+}
+
+/// A value for which the predicate given to [`assert_that`] did not
+/// hold.
+#[derive(Clone, Copy)]
+pub struct FailedPredicate<T> {
+    /// The value that failed the check.
+    pub(crate) value: T,
+    /// What the check was, e.g. `"is a power of two"`.
+    pub(crate) description: &'static str,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl<T> FailedPredicate<T> {
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    pub fn description(&self) -> &'static str {
+        self.description
+    }
+
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_that"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+
+    pub fn into_value(self) -> T {
+        self.value
+    }
+}
+
+impl<T: PartialEq> PartialEq for FailedPredicate<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.description == other.description
+    }
+}
+
+impl<T: Eq> Eq for FailedPredicate<T> {}
+
+impl<T: Debug> Debug for FailedPredicate<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FailedPredicate")
+            .field("value", &self.value)
+            .field("description", &self.description)
+            .finish()
+    }
+}
+
+impl<T: Display> Display for FailedPredicate<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(
+            f,
+            "Value {} fails the check: {}",
+            self.value, self.description
+        )
+    }
+}
+
+impl<T: Debug + Display> core::error::Error for FailedPredicate<T> {}
+
+impl<T: Display> Fields for FailedPredicate<T> {
+    fn kind(&self) -> &'static str {
+        "failed_predicate"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("value", &self.value);
+        visit("description", &self.description);
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(format!("use a value that {}", self.description))
+    }
+}
+
+impl<T> ErrorCode for FailedPredicate<T> {
+    fn code(&self) -> u32 {
+        code::FAILED_PREDICATE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::assert_that;
+    use crate::Fields;
+
+    #[test]
+    fn keeps_value_and_description() {
+        let error =
+            assert_that(String::from("ab"), |s| s.len() > 2, "is longer than 2").unwrap_err();
+        assert_eq!(error.description(), "is longer than 2");
+        assert_eq!(
+            error.help().as_deref(),
+            Some("use a value that is longer than 2")
+        );
+        assert_eq!(error.into_value(), "ab");
+    }
+}
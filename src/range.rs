@@ -0,0 +1,160 @@
+//! Checking the ranges themselves, before checking values against
+//! them.
+
+use crate::{code, hook, ErrorCode, Fields, Interval, Notation};
+use alloc::format;
+use alloc::string::String;
+use core::fmt::{Debug, Display};
+use core::ops::Range;
+
+/// Assert that `range` allows at least one value under `interval`,
+/// returning it.
+///
+/// An empty or inverted range, e.g. `5..1`, makes [`crate::assert_in`]
+/// reject every value, which is a mistake of whoever built the range
+/// rather than of the value, so ranges that come from configuration
+/// or user input are best checked with this first.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::{assert_range, Interval};
+///
+/// assert_eq!(assert_range(&(1..5), Interval::LeftOpen), Ok(1..5));
+/// assert_eq!(
+///     assert_range(&(5..1), Interval::LeftOpen).unwrap_err().to_string(),
+///     "The range (5, 1] is empty, so no value can be in it"
+/// );
+/// assert!(assert_range(&(3..3), Interval::HalfOpen).is_err());
+/// assert!(assert_range(&(3..3), Interval::Closed).is_ok());
+/// ```
+pub fn assert_range<T: Ord + Copy>(
+    range: &Range<T>,
+    interval: Interval,
+) -> Result<Range<T>, EmptyRange<T>> {
+    if interval.is_empty(range) {
+        hook::observe("empty_range", code::EMPTY_RANGE, "assert_range");
+        Err(EmptyRange {
+            range: range.clone(),
+            interval,
+            provenance: "assert_range",
+        })
+    } else {
+        Ok(range.clone())
+    }
+}
+
+/// A range that values were to be checked against allows none.
+#[derive(Clone)]
+pub struct EmptyRange<T> {
+    pub(crate) range: Range<T>,
+    pub(crate) interval: Interval,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl<T> EmptyRange<T> {
+    pub fn range(&self) -> &Range<T> {
+        &self.range
+    }
+
+    pub fn interval(&self) -> Interval {
+        self.interval
+    }
+
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_range"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl<T: PartialEq> PartialEq for EmptyRange<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.range == other.range && self.interval == other.interval
+    }
+}
+
+impl<T: Eq> Eq for EmptyRange<T> {}
+
+impl<T: Debug> Debug for EmptyRange<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("EmptyRange")
+            .field("range", &self.range)
+            .field("interval", &self.interval)
+            .finish()
+    }
+}
+
+impl<T: Display> Display for EmptyRange<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(
+            f,
+            "The range {} is empty, so no value can be in it",
+            Notation(&self.range, self.interval)
+        )
+    }
+}
+
+impl<T: Debug + Display> core::error::Error for EmptyRange<T> {}
+
+impl<T: Display> Fields for EmptyRange<T> {
+    fn kind(&self) -> &'static str {
+        "empty_range"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("start", &self.range.start);
+        visit("end", &self.range.end);
+        visit("interval", &self.interval);
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(format!(
+            "{} allows no value; widen it",
+            Notation(&self.range, self.interval)
+        ))
+    }
+}
+
+impl<T> ErrorCode for EmptyRange<T> {
+    fn code(&self) -> u32 {
+        code::EMPTY_RANGE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::assert_range;
+    use crate::{Fields, Interval};
+    use core::ops::Range;
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn single_points() {
+        for interval in [Interval::LeftOpen, Interval::HalfOpen, Interval::Open] {
+            assert!(assert_range(&(2..2), interval).is_err());
+        }
+        assert_eq!(assert_range(&(2..2), Interval::Closed), Ok(2..2));
+        assert_eq!(
+            assert_range(&Range { start: 9, end: 2 }, Interval::Closed)
+                .unwrap_err()
+                .to_string(),
+            "The range 9..=2 is empty, so no value can be in it"
+        );
+    }
+
+    #[test]
+    fn help_names_the_interval() {
+        let help = |interval| assert_range(&(3..3), interval).unwrap_err().help();
+        assert_eq!(
+            help(Interval::HalfOpen).as_deref(),
+            Some("3..3 allows no value; widen it")
+        );
+        assert_eq!(
+            help(Interval::Open).as_deref(),
+            Some("(3, 3) allows no value; widen it")
+        );
+    }
+}
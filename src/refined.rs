@@ -0,0 +1,375 @@
+//! Values that carry the fact that they were validated in their type.
+
+use crate::arith::Limits;
+use crate::collection::Bounds;
+use crate::{code, hook, Interval, LengthOutside, Outside};
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display};
+use core::ops::{Bound, Deref};
+
+/// A `T` that is known to lie within `MIN..=MAX`.
+///
+/// The bounds are `i128` so that they can be written as plain
+/// literals for every integer type; bounds that `T` cannot hold are
+/// clamped to its limits.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::Bounded;
+///
+/// type Percent = Bounded<u8, 0, 100>;
+///
+/// let half = Percent::new(50).unwrap();
+/// assert_eq!(*half + 1, 51);
+/// assert_eq!(
+///     Percent::new(101).unwrap_err().to_string(),
+///     "Value 101 is outside the allowed range 0..=100 (must be at most 100)"
+/// );
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bounded<T, const MIN: i128, const MAX: i128>(T);
+
+impl<T, const MIN: i128, const MAX: i128> Bounded<T, MIN, MAX>
+where
+    T: Limits + TryFrom<i128>,
+{
+    /// The smallest value allowed, as a `T`.
+    pub fn min() -> T {
+        T::try_from(MIN).unwrap_or(if MIN < 0 { T::MIN } else { T::MAX })
+    }
+
+    /// The largest value allowed, as a `T`.
+    pub fn max() -> T {
+        T::try_from(MAX).unwrap_or(if MAX < 0 { T::MIN } else { T::MAX })
+    }
+
+    /// Check that `value` is within the bounds.
+    pub fn new(value: T) -> Result<Self, Outside<T>> {
+        let (min, max) = (Self::min(), Self::max());
+        if min <= value && value <= max {
+            Ok(Self(value))
+        } else {
+            hook::observe("outside", code::OUTSIDE, "Bounded::new");
+            Err(Outside {
+                range: min..max,
+                interval: Interval::Closed,
+                value,
+                provenance: "Bounded::new",
+            })
+        }
+    }
+}
+
+impl<T: Copy, const MIN: i128, const MAX: i128> Bounded<T, MIN, MAX> {
+    pub fn get(self) -> T {
+        self.0
+    }
+}
+
+impl<T, const MIN: i128, const MAX: i128> Deref for Bounded<T, MIN, MAX> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Debug, const MIN: i128, const MAX: i128> Debug for Bounded<T, MIN, MAX> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl<T: Display, const MIN: i128, const MAX: i128> Display for Bounded<T, MIN, MAX> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+#[doc(hidden)]
+pub fn __refine<T: Ord + Copy>(
+    value: T,
+    min: T,
+    max: T,
+    provenance: &'static str,
+) -> Result<T, Outside<T>> {
+    if min <= value && value <= max {
+        Ok(value)
+    } else {
+        hook::observe("outside", code::OUTSIDE, provenance);
+        Err(Outside {
+            range: min..max,
+            interval: Interval::Closed,
+            value,
+            provenance,
+        })
+    }
+}
+
+/// Define a newtype over a primitive that can only hold values in an
+/// inclusive range.
+///
+/// The type gets a `new` constructor that fails with an [`Outside`],
+/// a `get` accessor, `Deref` to the primitive, `Display`, and
+/// `TryFrom` the primitive.  With the `serde` feature of `goof` it is
+/// also `Serialize` and `Deserialize` as the primitive, and values out
+/// of range fail to deserialize.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// goof::refine! {
+///     /// A TCP port that is not zero.
+///     pub Port = u16 where 1..=65535
+/// }
+///
+/// let port = Port::new(8080).unwrap();
+/// assert_eq!(*port, 8080);
+/// assert_eq!(Port::MIN, 1);
+/// assert_eq!(
+///     Port::new(0).unwrap_err().to_string(),
+///     "Value 0 is outside the allowed range 1..=65535 (must be at least 1)"
+/// );
+/// assert_eq!(Port::new(0).unwrap_err().provenance(), "Port::new");
+/// ```
+#[macro_export]
+macro_rules! refine {
+    ($(#[$meta:meta])* $vis:vis $name:ident = $t:ty where $($range:tt)+) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        $vis struct $name($t);
+
+        impl $name {
+            const RANGE: ::core::ops::RangeInclusive<$t> = $($range)+;
+            /// The smallest value allowed.
+            pub const MIN: $t = *Self::RANGE.start();
+            /// The largest value allowed.
+            pub const MAX: $t = *Self::RANGE.end();
+
+            pub fn new(value: $t) -> ::core::result::Result<Self, $crate::Outside<$t>> {
+                $crate::__refine(value, Self::MIN, Self::MAX, ::core::concat!(::core::stringify!($name), "::new"))
+                    .map(Self)
+            }
+
+            pub fn get(self) -> $t {
+                self.0
+            }
+        }
+
+        impl ::core::ops::Deref for $name {
+            type Target = $t;
+
+            fn deref(&self) -> &$t {
+                &self.0
+            }
+        }
+
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        impl ::core::convert::TryFrom<$t> for $name {
+            type Error = $crate::Outside<$t>;
+
+            fn try_from(value: $t) -> ::core::result::Result<Self, Self::Error> {
+                Self::new(value)
+            }
+        }
+
+        impl ::core::convert::From<$name> for $t {
+            fn from(value: $name) -> $t {
+                value.0
+            }
+        }
+
+        $crate::__refine_serde!($name = $t);
+    };
+}
+
+/// The `serde` impls of a [`refine!`] type, which only exist when
+/// `goof` itself has the `serde` feature.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __refine_serde {
+    ($name:ident = $t:ty) => {
+        impl $crate::__serde::Serialize for $name {
+            fn serialize<S: $crate::__serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> ::core::result::Result<S::Ok, S::Error> {
+                $crate::__serde::Serialize::serialize(&self.0, serializer)
+            }
+        }
+
+        impl<'de> $crate::__serde::Deserialize<'de> for $name {
+            fn deserialize<D: $crate::__serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> ::core::result::Result<Self, D::Error> {
+                let value = <$t as $crate::__serde::Deserialize<'de>>::deserialize(deserializer)?;
+                Self::new(value).map_err(<D::Error as $crate::__serde::de::Error>::custom)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __refine_serde {
+    ($name:ident = $t:ty) => {};
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use super::Bounded;
+    use crate::arith::Limits;
+    use ::serde::de::Error;
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use core::fmt::Display;
+
+    impl<T: Serialize, const MIN: i128, const MAX: i128> Serialize for Bounded<T, MIN, MAX> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.0.serialize(serializer)
+        }
+    }
+
+    impl<'de, T, const MIN: i128, const MAX: i128> Deserialize<'de> for Bounded<T, MIN, MAX>
+    where
+        T: Deserialize<'de> + Limits + TryFrom<i128> + Display,
+    {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            Self::new(T::deserialize(deserializer)?).map_err(D::Error::custom)
+        }
+    }
+}
+
+/// A `Vec` that is known to have at least one element.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::NonEmpty;
+///
+/// let hosts = NonEmpty::new(vec!["a.example", "b.example"]).unwrap();
+/// assert_eq!(*hosts.first(), "a.example");
+/// assert_eq!(
+///     NonEmpty::<u8>::new(vec![]).unwrap_err().to_string(),
+///     "Expected 1.. elements, but got 0"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NonEmpty<T>(Vec<T>);
+
+impl<T> NonEmpty<T> {
+    /// Check that `items` is not empty.
+    pub fn new(items: Vec<T>) -> Result<Self, LengthOutside> {
+        if items.is_empty() {
+            hook::observe("length_outside", code::LENGTH_OUTSIDE, "NonEmpty::new");
+            Err(LengthOutside {
+                range: Bounds(Bound::Included(1), Bound::Unbounded),
+                actual: 0,
+                provenance: "NonEmpty::new",
+            })
+        } else {
+            Ok(Self(items))
+        }
+    }
+
+    pub fn first(&self) -> &T {
+        &self.0[0]
+    }
+
+    pub fn last(&self) -> &T {
+        &self.0[self.0.len() - 1]
+    }
+
+    pub fn push(&mut self, item: T) {
+        self.0.push(item)
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T> Deref for NonEmpty<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T> TryFrom<Vec<T>> for NonEmpty<T> {
+    type Error = LengthOutside;
+
+    fn try_from(items: Vec<T>) -> Result<Self, LengthOutside> {
+        Self::new(items)
+    }
+}
+
+impl<T> From<NonEmpty<T>> for Vec<T> {
+    fn from(items: NonEmpty<T>) -> Self {
+        items.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Bounded;
+
+    crate::refine!(Celsius = i16 where -273..=1000);
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn refined_negative_bounds() {
+        assert_eq!(Celsius::new(-273).map(Celsius::get), Ok(-273));
+        assert_eq!(
+            Celsius::try_from(-300).unwrap_err().to_string(),
+            "Value -300 is outside the allowed range -273..=1000 (must be at least -273)"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn bounds_are_inclusive_and_clamped() {
+        assert!(Bounded::<u8, 1, 100>::new(1).is_ok());
+        assert!(Bounded::<u8, 1, 100>::new(100).is_ok());
+        assert_eq!(
+            Bounded::<u8, 1, 100>::new(0).unwrap_err().to_string(),
+            "Value 0 is outside the allowed range 1..=100 (must be at least 1)"
+        );
+        assert_eq!(Bounded::<u8, -5, 1000>::min(), 0);
+        assert_eq!(Bounded::<u8, -5, 1000>::max(), 255);
+        assert_eq!(
+            Bounded::<i8, -5, 5>::new(-6).unwrap_err().provenance(),
+            "Bounded::new"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn deserialized_through_new() {
+        let celsius: Celsius = serde_json::from_str("-40").unwrap();
+        assert_eq!(celsius.get(), -40);
+        assert_eq!(serde_json::to_string(&celsius).unwrap(), "-40");
+        assert_eq!(
+            serde_json::from_str::<Celsius>("-300")
+                .unwrap_err()
+                .to_string(),
+            "Value -300 is outside the allowed range -273..=1000 (must be at least -273)"
+        );
+        assert!(serde_json::from_str::<Bounded<u8, 1, 100>>("0").is_err());
+        assert_eq!(
+            serde_json::from_str::<Bounded<u8, 1, 100>>("100")
+                .map(Bounded::get)
+                .ok(),
+            Some(100)
+        );
+    }
+}
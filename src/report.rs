@@ -0,0 +1,637 @@
+use crate::{render, ErrorCode, Fields, Severity};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt::{Debug, Display};
+
+/// Any error, type-erased, for the top of an application where all
+/// that is left to do with an error is to show it.
+pub struct Report {
+    error: Box<dyn Error + Send + Sync + 'static>,
+    explanation: Option<(&'static str, Option<&'static str>)>,
+    severity: Severity,
+    context: Vec<String>,
+    /// The kind and fields of the error, if it was reported with
+    /// [`Report::detailed`].
+    details: Option<(&'static str, Vec<(&'static str, String)>)>,
+}
+
+impl Report {
+    /// Report `error`, with the default [`Severity::Error`] and no
+    /// context.
+    pub fn new<E: Error + Send + Sync + 'static>(error: E) -> Self {
+        Self::from_boxed(Box::new(error))
+    }
+
+    /// Report an error that was already boxed, e.g. by an API that
+    /// returns `Box<dyn Error + Send + Sync>`, without boxing it again,
+    /// so that its causes and [`Report::downcast_ref`] still work.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # if cfg!(feature = "compact") { return; }
+    /// use core::error::Error;
+    /// use goof::{Caused, Mismatch, Report};
+    ///
+    /// let cause = goof::Goof::from("short read");
+    /// let boxed: Box<dyn Error + Send + Sync> =
+    ///     Caused::new(goof::assert_eq(&4, &1).unwrap_err()).with_source(cause).into();
+    /// let report = Report::from_boxed(boxed).attach("while decoding");
+    /// assert!(report.is::<Caused<Mismatch<i32>>>());
+    /// assert_eq!(report.error().source().unwrap().to_string(), "short read");
+    ///
+    /// let boxed: Box<dyn Error> = report.into();
+    /// assert_eq!(boxed.to_string(), "Expected 4, but got 1");
+    /// ```
+    pub fn from_boxed(error: Box<dyn Error + Send + Sync + 'static>) -> Self {
+        Self {
+            error,
+            explanation: None,
+            severity: Severity::Error,
+            context: Vec::new(),
+            details: None,
+        }
+    }
+
+    /// Report `error` with what its [`Fields`] say about it: its kind
+    /// and fields for [`Report::to_markdown`] and [`Report::to_html`],
+    /// its [`Fields::severity`], and its long-form help for
+    /// [`Report::explain`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # if cfg!(feature = "compact") { return; }
+    /// use goof::{Diagnostic, Report, Severity};
+    ///
+    /// let error = goof::assert_eq(&4, &2).unwrap_err();
+    /// let report = Report::detailed(Diagnostic::new(Severity::Warning, error));
+    /// assert_eq!(report.severity(), Severity::Warning);
+    /// assert!(report.to_markdown().starts_with("**warning[mismatch]**: "));
+    /// ```
+    pub fn detailed<E>(error: E) -> Self
+    where
+        E: Error + Fields + ErrorCode + Send + Sync + 'static,
+    {
+        let mut values = Vec::new();
+        error.for_each_field(&mut |name, value| values.push((name, render(value, false))));
+        let details = Some((error.kind(), values));
+        let explanation = error
+            .as_explain()
+            .map(|explain| (explain.explanation(), explain.url()));
+        let severity = error.severity();
+        Self {
+            details,
+            explanation,
+            severity,
+            ..Self::new(error)
+        }
+    }
+
+    /// Report with `severity` rather than [`Severity::Error`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # if cfg!(feature = "compact") { return; }
+    /// use goof::{Report, Severity};
+    ///
+    /// let reports = [
+    ///     Report::new(goof::assert_eq(&1, &2).unwrap_err()),
+    ///     Report::new(goof::Cancelled::new()).with_severity(Severity::Hint),
+    /// ];
+    /// let shown: Vec<String> = reports
+    ///     .iter()
+    ///     .filter_map(|report| report.at_least(Severity::Warning))
+    ///     .map(ToString::to_string)
+    ///     .collect();
+    /// assert_eq!(shown, ["Expected 1, but got 2"]);
+    /// ```
+    pub fn with_severity(self, severity: Severity) -> Self {
+        Self { severity, ..self }
+    }
+
+    /// How severe the error is, [`Severity::Error`] unless the error
+    /// said otherwise to [`Report::detailed`] or it was set with
+    /// [`Report::with_severity`].
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// The report, if it is at least as severe as `minimum`, for
+    /// showing only the problems that matter.
+    pub fn at_least(&self, minimum: Severity) -> Option<&Self> {
+        (self.severity >= minimum).then_some(self)
+    }
+
+    /// The error being reported.
+    pub fn error(&self) -> &(dyn Error + Send + Sync + 'static) {
+        &*self.error
+    }
+
+    /// Whether the error being reported is an `E`.
+    pub fn is<E: Error + 'static>(&self) -> bool {
+        self.error.is::<E>()
+    }
+
+    /// The error being reported, if it is an `E`, e.g. to choose an
+    /// HTTP status by the type of the error.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use goof::{Mismatch, Outside, Report};
+    ///
+    /// let error = goof::assert_eq(&32_u32, &0).unwrap_err();
+    /// let report = Report::new(error);
+    /// assert!(!report.is::<Outside<usize>>());
+    /// assert_eq!(report.downcast_ref::<Mismatch<u32>>(), Some(&error));
+    ///
+    /// let report = report.downcast::<Outside<usize>>().unwrap_err();
+    /// assert_eq!(report.downcast::<Mismatch<u32>>().unwrap(), error);
+    /// ```
+    pub fn downcast_ref<E: Error + 'static>(&self) -> Option<&E> {
+        self.error.downcast_ref()
+    }
+
+    /// Like [`Report::downcast_ref`], but for changing the error in
+    /// place, e.g. to fill in what was only known further up.
+    pub fn downcast_mut<E: Error + 'static>(&mut self) -> Option<&mut E> {
+        self.error.downcast_mut()
+    }
+
+    /// The error being reported, if it is an `E`, and the report as it
+    /// was otherwise.  The context is lost with the report.
+    pub fn downcast<E: Error + 'static>(self) -> Result<E, Self> {
+        match self.error.downcast() {
+            Ok(error) => Ok(*error),
+            Err(error) => Err(Self { error, ..self }),
+        }
+    }
+
+    /// Note what was being done when the error happened, e.g. which
+    /// file was being read.
+    pub fn attach(mut self, context: impl Display) -> Self {
+        self.context.push(crate::render(&context, false));
+        self
+    }
+
+    /// The notes added with [`Report::attach`], oldest first.
+    pub fn context(&self) -> &[String] {
+        &self.context
+    }
+
+    /// Render the error, then its causes, numbered from 1 as in
+    /// [`Report::to_markdown`], then its context, each on its own
+    /// indented lines.  This is also what `{:?}` shows.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # if cfg!(feature = "compact") { return; }
+    /// use goof::{Caused, Report};
+    ///
+    /// let cause = goof::assert_in(&0, &(1..2)).unwrap_err();
+    /// let error = Caused::new(goof::assert_eq(&1, &2).unwrap_err()).with_source(cause);
+    /// let report = Report::new(error).attach("while reading config.toml");
+    /// assert_eq!(
+    ///     report.pretty().to_string(),
+    ///     "Expected 1, but got 2\n\
+    ///      \n\
+    ///      Caused by:\n\
+    ///      \x20   1: Value 0 is outside the allowed range (1, 2] (must be greater than 1)\n\
+    ///      \n\
+    ///      Context:\n\
+    ///      \x20   while reading config.toml"
+    /// );
+    /// ```
+    pub fn pretty(&self) -> Pretty<'_> {
+        Pretty(self)
+    }
+
+    /// Render as Markdown, for issue templates and pull request
+    /// comments: the message, a table of the fields if the report has
+    /// them, the causes in a collapsed block, and the context.
+    ///
+    /// The characters that Markdown would read as markup are escaped
+    /// with a backslash, and line breaks become `<br>`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # if cfg!(feature = "compact") { return; }
+    /// use goof::{Caused, Report};
+    ///
+    /// let cause = goof::Goof::from("disk *full*");
+    /// let mismatch = goof::assert_eq(&32, &0).unwrap_err();
+    /// let error = Caused::new(mismatch).with_source(cause);
+    /// let report = Report::detailed(error).attach("while saving");
+    /// assert_eq!(
+    ///     report.to_markdown(),
+    ///     "**error[mismatch]**: Expected 32, but got 0\n\
+    ///      \n\
+    ///      | Field | Value |\n\
+    ///      | --- | --- |\n\
+    ///      | `expected` | 32 |\n\
+    ///      | `actual` | 0 |\n\
+    ///      \n\
+    ///      <details>\n\
+    ///      <summary>Caused by</summary>\n\
+    ///      \n\
+    ///      1. disk \\*full\\*\n\
+    ///      \n\
+    ///      </details>\n\
+    ///      \n\
+    ///      - while saving"
+    /// );
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        use core::fmt::Write;
+
+        let mut out = String::new();
+        let _ = write!(out, "**{}", self.severity);
+        if let Some((kind, _)) = &self.details {
+            let _ = write!(out, "[{}]", kind);
+        }
+        let _ = write!(out, "**: {}", Markdown(&self.error));
+        if let Some((_, fields)) = self
+            .details
+            .as_ref()
+            .filter(|(_, fields)| !fields.is_empty())
+        {
+            out.push_str("\n\n| Field | Value |\n| --- | --- |");
+            for (name, value) in fields {
+                let _ = write!(out, "\n| `{}` | {} |", name, Markdown(value));
+            }
+        }
+        let mut source = self.error.source();
+        if source.is_some() {
+            out.push_str("\n\n<details>\n<summary>Caused by</summary>\n");
+            let mut index = 1;
+            while let Some(cause) = source {
+                let _ = write!(out, "\n{}. {}", index, Markdown(cause));
+                source = cause.source();
+                index += 1;
+            }
+            out.push_str("\n\n</details>");
+        }
+        if !self.context.is_empty() {
+            out.push('\n');
+        }
+        for context in &self.context {
+            let _ = write!(out, "\n- {}", Markdown(context));
+        }
+        out
+    }
+
+    /// Render as an HTML fragment, for dashboards, with the same parts
+    /// as [`Report::to_markdown`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # if cfg!(feature = "compact") { return; }
+    /// use goof::Report;
+    ///
+    /// let error = goof::assert_eq(&"<a>", &"b").unwrap_err();
+    /// let report = Report::detailed(error);
+    /// assert_eq!(
+    ///     report.to_html(),
+    ///     "<div class=\"goof goof-error\">\n\
+    ///      <p><strong>error[mismatch]</strong>: Expected &lt;a&gt;, but got b</p>\n\
+    ///      <table>\n\
+    ///      <tr><th>Field</th><th>Value</th></tr>\n\
+    ///      <tr><td><code>expected</code></td><td>&lt;a&gt;</td></tr>\n\
+    ///      <tr><td><code>actual</code></td><td>b</td></tr>\n\
+    ///      </table>\n\
+    ///      </div>"
+    /// );
+    /// ```
+    pub fn to_html(&self) -> String {
+        use core::fmt::Write;
+
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            "<div class=\"goof goof-{}\">\n<p><strong>{}",
+            self.severity, self.severity
+        );
+        if let Some((kind, _)) = &self.details {
+            let _ = write!(out, "[{}]", kind);
+        }
+        let _ = write!(out, "</strong>: {}</p>", Html(&self.error));
+        if let Some((_, fields)) = self
+            .details
+            .as_ref()
+            .filter(|(_, fields)| !fields.is_empty())
+        {
+            out.push_str("\n<table>\n<tr><th>Field</th><th>Value</th></tr>");
+            for (name, value) in fields {
+                let _ = write!(
+                    out,
+                    "\n<tr><td><code>{}</code></td><td>{}</td></tr>",
+                    name,
+                    Html(value)
+                );
+            }
+            out.push_str("\n</table>");
+        }
+        let mut source = self.error.source();
+        if source.is_some() {
+            out.push_str("\n<details>\n<summary>Caused by</summary>\n<ol>");
+            while let Some(cause) = source {
+                let _ = write!(out, "\n<li>{}</li>", Html(cause));
+                source = cause.source();
+            }
+            out.push_str("\n</ol>\n</details>");
+        }
+        if !self.context.is_empty() {
+            out.push_str("\n<ul class=\"goof-context\">");
+            for context in &self.context {
+                let _ = write!(out, "\n<li>{}</li>", Html(context));
+            }
+            out.push_str("\n</ul>");
+        }
+        out.push_str("\n</div>");
+        out
+    }
+
+    /// Render the error followed by its long-form help, if it has one
+    /// and was reported with [`Report::detailed`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # if cfg!(feature = "compact") { return; }
+    /// use goof::Report;
+    ///
+    /// let error = goof::assert_eq(&32, &0).unwrap_err();
+    /// let report = Report::detailed(error);
+    /// let rendered = report.explain().to_string();
+    /// assert!(rendered.starts_with("Expected 32, but got 0\n\nA value had to be exactly equal"));
+    /// assert!(rendered.ends_with("\n\nSee https://docs.rs/goof/latest/goof/struct.Mismatch.html"));
+    /// ```
+    pub fn explain(&self) -> Explained<'_> {
+        Explained(self)
+    }
+}
+
+impl<E: Error + Send + Sync + 'static> From<E> for Report {
+    fn from(error: E) -> Self {
+        Self::new(error)
+    }
+}
+
+/// The error being reported.  The context is not part of it, so it is
+/// lost.
+impl From<Report> for Box<dyn Error + Send + Sync + 'static> {
+    fn from(report: Report) -> Self {
+        report.error
+    }
+}
+
+impl From<Report> for Box<dyn Error + 'static> {
+    fn from(report: Report) -> Self {
+        report.error
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.error, f)
+    }
+}
+
+/// `{:?}` shows the error followed by the chain of its causes, one
+/// per line, which is what `fn main() -> Result<(), Report>` prints.
+/// `{:#?}` shows the `Debug` of the error instead.
+impl Debug for Report {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if f.alternate() {
+            return Debug::fmt(&self.error, f);
+        }
+        Display::fmt(&self.pretty(), f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Report {
+    /// The status to exit the process with: `101` for a
+    /// [`crate::Panicked`], like an uncaught panic, and `1` otherwise.
+    pub fn exit_code(&self) -> std::process::ExitCode {
+        if self.error.is::<crate::Panicked>() {
+            std::process::ExitCode::from(101)
+        } else {
+            std::process::ExitCode::FAILURE
+        }
+    }
+}
+
+/// Prints the report with its causes to standard error and exits with
+/// [`Report::exit_code`].
+#[cfg(feature = "std")]
+impl std::process::Termination for Report {
+    fn report(self) -> std::process::ExitCode {
+        std::eprintln!("Error: {:?}", self);
+        self.exit_code()
+    }
+}
+
+/// The return type for `main`, printing an error the way
+/// [`Report`]'s [`std::process::Termination`] does.
+///
+/// Unlike `Result<(), Report>`, the exit code depends on the error.
+///
+/// # Examples
+/// ```rust,no_run
+/// fn run() -> Result<(), goof::Mismatch<u32>> {
+///     goof::assert_eq(&32, &0)?;
+///     Ok(())
+/// }
+///
+/// fn main() -> goof::MainGoof {
+///     run().into()
+/// }
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct MainGoof(pub Result<(), Report>);
+
+#[cfg(feature = "std")]
+impl<E: Into<Report>> From<Result<(), E>> for MainGoof {
+    fn from(result: Result<(), E>) -> Self {
+        Self(result.map_err(Into::into))
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::process::Termination for MainGoof {
+    fn report(self) -> std::process::ExitCode {
+        match self.0 {
+            Ok(()) => std::process::ExitCode::SUCCESS,
+            Err(report) => report.report(),
+        }
+    }
+}
+
+/// `Display` adapter returned by [`Report::pretty`].
+pub struct Pretty<'a>(&'a Report);
+
+impl Display for Pretty<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.0.error, f)?;
+        let mut source = self.0.error.source();
+        if source.is_some() {
+            f.write_str("\n\nCaused by:")?;
+        }
+        let mut index = 1;
+        while let Some(cause) = source {
+            let prefix = crate::render(&format_args!("    {}: ", index), false);
+            write!(f, "\n{}", prefix)?;
+            write_indented(f, cause, prefix.len())?;
+            source = cause.source();
+            index += 1;
+        }
+        if !self.0.context.is_empty() {
+            f.write_str("\n\nContext:")?;
+        }
+        for context in &self.0.context {
+            f.write_str("\n    ")?;
+            write_indented(f, context, 4)?;
+        }
+        Ok(())
+    }
+}
+
+/// Write `message`, indenting every line after the first by `width`
+/// spaces, so that it lines up under its first line.
+fn write_indented(
+    f: &mut core::fmt::Formatter<'_>,
+    message: &(impl Display + ?Sized),
+    width: usize,
+) -> core::fmt::Result {
+    for (index, line) in crate::render(message, false).split('\n').enumerate() {
+        if index > 0 {
+            write!(f, "\n{:width$}", "", width = width)?;
+        }
+        f.write_str(line)?;
+    }
+    Ok(())
+}
+
+/// `Display` with the characters that are special in HTML escaped.
+struct Html<'a, T: ?Sized>(&'a T);
+
+impl<T: Display + ?Sized> Display for Html<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for c in render(self.0, false).chars() {
+            match c {
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '"' => f.write_str("&quot;")?,
+                c => write!(f, "{}", c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `Display` with the characters that are special in Markdown escaped,
+/// and line breaks as `<br>`, so that a message stays one paragraph,
+/// list item or table cell.
+struct Markdown<'a, T: ?Sized>(&'a T);
+
+impl<T: Display + ?Sized> Display for Markdown<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for c in render(self.0, false).chars() {
+            match c {
+                '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '#' | '~' => {
+                    write!(f, "\\{}", c)?
+                }
+                '\n' => f.write_str("<br>")?,
+                c => write!(f, "{}", c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `Display` adapter returned by [`Report::explain`].
+pub struct Explained<'a>(&'a Report);
+
+impl Display for Explained<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self.0, f)?;
+        if let Some((explanation, url)) = self.0.explanation {
+            write!(f, "\n\n{}", explanation)?;
+            if let Some(url) = url {
+                write!(f, "\n\nSee {}", url)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Report;
+
+    #[test]
+    fn explain_without_explanation_is_the_message() {
+        let report = Report::from(crate::assert_in(&0, &(1..2)).unwrap_err());
+        assert_eq!(report.explain().to_string(), report.to_string());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn html_causes_and_context() {
+        let error =
+            crate::Caused::new(crate::Cancelled::new()).with_source(crate::Goof::from("a & b"));
+        let html = Report::new(error).attach("<init>").to_html();
+        assert_eq!(
+            html,
+            "<div class=\"goof goof-error\">\n<p><strong>error</strong>: Cancelled</p>\n\
+             <details>\n<summary>Caused by</summary>\n<ol>\n<li>a &amp; b</li>\n</ol>\n</details>\n\
+             <ul class=\"goof-context\">\n<li>&lt;init&gt;</li>\n</ul>\n</div>"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn multi_line_messages_line_up() {
+        let report = Report::new(crate::Goof::from("first\nsecond"))
+            .attach("reading\nconfig.toml")
+            .attach("starting up");
+        assert_eq!(
+            format!("{:?}", report),
+            "first\nsecond\n\nContext:\n    reading\n    config.toml\n    starting up"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn debug_lists_causes() {
+        let error = crate::assert_in(&0, &(1..2)).unwrap_err();
+        let report = Report::new(
+            crate::Caused::new(crate::assert_eq(&1, &2).unwrap_err()).with_source(error),
+        );
+        assert_eq!(
+            format!("{:?}", report),
+            "Expected 1, but got 2\n\nCaused by:\n    1: Value 0 is outside the allowed range (1, 2] (must be greater than 1)"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn fields_severity_and_explanation_compose() {
+        let error = crate::assert_eq(&"a*b", &"`c`").unwrap_err();
+        let report = Report::detailed(error)
+            .with_severity(crate::Severity::Warning)
+            .attach("line\nbreak");
+        assert_eq!(
+            report.to_markdown(),
+            "**warning[mismatch]**: Expected a\\*b, but got \\`c\\`\n\n\
+             | Field | Value |\n| --- | --- |\n| `expected` | a\\*b |\n| `actual` | \\`c\\` |\n\n\
+             - line<br>break"
+        );
+        assert!(report
+            .explain()
+            .to_string()
+            .contains("A value had to be exactly equal"));
+    }
+}
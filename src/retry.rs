@@ -0,0 +1,315 @@
+//! Retrying operations that fail intermittently.
+
+use crate::{code, hook, ErrorCode, Fields, Transience};
+use core::error::Error;
+use core::fmt::{Debug, Display};
+use core::time::Duration;
+
+/// How often, and how patiently, to retry an operation.
+///
+/// The delay before the second attempt is `delay`, and it is
+/// multiplied by `factor` before each further attempt, up to
+/// `max_delay`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Policy {
+    /// The total number of attempts, including the first one.
+    pub attempts: u32,
+    pub delay: Duration,
+    pub factor: u32,
+    pub max_delay: Duration,
+}
+
+impl Policy {
+    /// Try `attempts` times, waiting 100ms, then 200ms and so on, but
+    /// never more than 10s between attempts.
+    pub const fn new(attempts: u32) -> Self {
+        Self {
+            attempts,
+            delay: Duration::from_millis(100),
+            factor: 2,
+            max_delay: Duration::from_secs(10),
+        }
+    }
+
+    pub fn delay(self, delay: Duration) -> Self {
+        Self { delay, ..self }
+    }
+
+    pub fn factor(self, factor: u32) -> Self {
+        Self { factor, ..self }
+    }
+
+    pub fn max_delay(self, max_delay: Duration) -> Self {
+        Self { max_delay, ..self }
+    }
+
+    /// How long to wait after the failed attempt number `attempt`,
+    /// counting from one.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use goof::retry::Policy;
+    /// use std::time::Duration;
+    ///
+    /// let policy = Policy::new(10).max_delay(Duration::from_millis(300));
+    /// assert_eq!(policy.backoff(1), Duration::from_millis(100));
+    /// assert_eq!(policy.backoff(2), Duration::from_millis(200));
+    /// assert_eq!(policy.backoff(3), Duration::from_millis(300));
+    /// ```
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = self
+            .factor
+            .checked_pow(attempt.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        self.delay
+            .checked_mul(factor)
+            .unwrap_or(Duration::MAX)
+            .min(self.max_delay)
+    }
+}
+
+/// Call `op` until it succeeds or `policy` runs out of attempts,
+/// sleeping in between.
+///
+/// # Examples
+/// ```rust
+/// use goof::retry::{retry, Policy};
+/// use std::time::Duration;
+///
+/// let mut calls = 0;
+/// let error = retry(Policy::new(3).delay(Duration::ZERO), || {
+///     calls += 1;
+///     goof::assert_eq(&200, &503)
+/// })
+/// .unwrap_err();
+/// assert_eq!((calls, error.attempts()), (3, 3));
+/// assert_eq!(error.last_error(), &goof::assert_eq(&200, &503).unwrap_err());
+/// ```
+#[cfg(feature = "std")]
+pub fn retry<T, E>(
+    policy: Policy,
+    op: impl FnMut() -> Result<T, E>,
+) -> Result<T, RetriesExhausted<E>> {
+    attempt(policy, std::thread::sleep, op, |_| true, "retry")
+}
+
+/// Like [`retry`], but waits with `sleep`, e.g. for platforms
+/// without threads.
+pub fn retry_with<T, E>(
+    policy: Policy,
+    sleep: impl FnMut(Duration),
+    op: impl FnMut() -> Result<T, E>,
+) -> Result<T, RetriesExhausted<E>> {
+    attempt(policy, sleep, op, |_| true, "retry_with")
+}
+
+/// Like [`retry`], but gives up at the first error that is not
+/// [`Transience::is_transient`], since trying again would fail the
+/// same way.
+///
+/// # Examples
+/// ```rust
+/// use goof::retry::{retry_transient, Policy};
+/// use std::time::Duration;
+///
+/// let mut calls = 0;
+/// let error = retry_transient(Policy::new(3).delay(Duration::ZERO), || {
+///     calls += 1;
+///     goof::assert_eq(&200, &503)
+/// })
+/// .unwrap_err();
+/// assert_eq!((calls, error.attempts()), (1, 1));
+/// ```
+#[cfg(feature = "std")]
+pub fn retry_transient<T, E: Transience>(
+    policy: Policy,
+    op: impl FnMut() -> Result<T, E>,
+) -> Result<T, RetriesExhausted<E>> {
+    attempt(
+        policy,
+        std::thread::sleep,
+        op,
+        E::is_transient,
+        "retry_transient",
+    )
+}
+
+/// Like [`retry_transient`], but waits with `sleep`, e.g. for
+/// platforms without threads.
+pub fn retry_transient_with<T, E: Transience>(
+    policy: Policy,
+    sleep: impl FnMut(Duration),
+    op: impl FnMut() -> Result<T, E>,
+) -> Result<T, RetriesExhausted<E>> {
+    attempt(policy, sleep, op, E::is_transient, "retry_transient_with")
+}
+
+fn attempt<T, E>(
+    policy: Policy,
+    mut sleep: impl FnMut(Duration),
+    mut op: impl FnMut() -> Result<T, E>,
+    retryable: impl Fn(&E) -> bool,
+    provenance: &'static str,
+) -> Result<T, RetriesExhausted<E>> {
+    let mut attempt = 1;
+    loop {
+        match op() {
+            Ok(value) => return Ok(value),
+            Err(last_error) if attempt >= policy.attempts || !retryable(&last_error) => {
+                hook::observe("retries_exhausted", code::RETRIES_EXHAUSTED, provenance);
+                return Err(RetriesExhausted {
+                    attempts: attempt,
+                    last_error,
+                    provenance,
+                });
+            }
+            Err(_) => {
+                sleep(policy.backoff(attempt));
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// An operation failed every time it was tried, or with an error that
+/// was not worth retrying.  The error of the last attempt is the
+/// [`Error::source`].
+#[derive(Clone)]
+pub struct RetriesExhausted<E> {
+    pub(crate) attempts: u32,
+    pub(crate) last_error: E,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl<E> RetriesExhausted<E> {
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn last_error(&self) -> &E {
+        &self.last_error
+    }
+
+    pub fn into_last_error(self) -> E {
+        self.last_error
+    }
+
+    /// The name of the function that created this error, e.g.
+    /// `"retry"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl<E: PartialEq> PartialEq for RetriesExhausted<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.attempts == other.attempts && self.last_error == other.last_error
+    }
+}
+
+impl<E: Eq> Eq for RetriesExhausted<E> {}
+
+impl<E: Debug> Debug for RetriesExhausted<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RetriesExhausted")
+            .field("attempts", &self.attempts)
+            .field("last_error", &self.last_error)
+            .finish()
+    }
+}
+
+impl<E> Display for RetriesExhausted<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        match self.attempts {
+            1 => f.write_str("Gave up after 1 attempt"),
+            attempts => write!(f, "Gave up after {} attempts", attempts),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for RetriesExhausted<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.last_error)
+    }
+}
+
+impl<E> Fields for RetriesExhausted<E> {
+    fn kind(&self) -> &'static str {
+        "retries_exhausted"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("attempts", &self.attempts);
+    }
+}
+
+impl<E> ErrorCode for RetriesExhausted<E> {
+    fn code(&self) -> u32 {
+        code::RETRIES_EXHAUSTED
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{retry_transient_with, retry_with, Policy};
+    use core::time::Duration;
+
+    #[test]
+    fn sleeps_between_attempts_only() {
+        let mut slept = Vec::new();
+        let mut calls = 0;
+        let result = retry_with(
+            Policy::new(4),
+            |d| slept.push(d),
+            || {
+                calls += 1;
+                if calls < 3 {
+                    Err(calls)
+                } else {
+                    Ok(calls)
+                }
+            },
+        );
+        assert_eq!(result, Ok(3));
+        assert_eq!(
+            slept,
+            [Duration::from_millis(100), Duration::from_millis(200)]
+        );
+        let error = retry_with(Policy::new(0), |_| {}, || Err::<(), _>(())).unwrap_err();
+        assert_eq!(error.attempts(), 1);
+        assert_eq!(error.provenance(), "retry_with");
+    }
+
+    #[test]
+    fn only_transient_errors_are_retried() {
+        let mut calls = 0;
+        let error = retry_transient_with(
+            Policy::new(5),
+            |_| {},
+            || {
+                calls += 1;
+                crate::assert_eq(&1, &2)
+            },
+        )
+        .unwrap_err();
+        assert_eq!((calls, error.attempts()), (1, 1));
+        assert_eq!(error.provenance(), "retry_transient_with");
+
+        let mut calls = 0;
+        let error = retry_transient_with(
+            Policy::new(5),
+            |_| {},
+            || {
+                calls += 1;
+                Err::<(), _>(crate::Timeout::new(
+                    Duration::from_secs(2),
+                    Duration::from_secs(1),
+                ))
+            },
+        )
+        .unwrap_err();
+        assert_eq!((calls, error.attempts()), (5, 5));
+    }
+}
@@ -0,0 +1,80 @@
+//! Naming the arguments of an assertion, so that they cannot be
+//! passed the wrong way around.
+
+/// The value an assertion wants.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::{assert_eq, Actual, Expected};
+///
+/// let header = [4_u8, 0];
+/// assert_eq!(
+///     assert_eq(Expected(4), Actual(header[1])).unwrap_err().to_string(),
+///     "Expected 4, but got 0"
+/// );
+/// ```
+///
+/// Swapping the two does not compile:
+/// ```rust,compile_fail
+/// use goof::{assert_eq, Actual, Expected};
+///
+/// let _ = assert_eq(Actual(0_u8), Expected(4));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Expected<T>(pub T);
+
+/// The value an assertion was given, as opposed to the [`Expected`]
+/// one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Actual<T>(pub T);
+
+/// What can be passed as the expected value of [`crate::assert_eq`]:
+/// a reference, or an [`Expected`].
+pub trait IntoExpected<T> {
+    fn into_expected(self) -> T;
+}
+
+/// What can be passed as the actual value of [`crate::assert_eq`]:
+/// a reference, or an [`Actual`].
+pub trait IntoActual<T> {
+    fn into_actual(self) -> T;
+}
+
+impl<T: Copy> IntoExpected<T> for &T {
+    fn into_expected(self) -> T {
+        *self
+    }
+}
+
+impl<T> IntoExpected<T> for Expected<T> {
+    fn into_expected(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Copy> IntoActual<T> for &T {
+    fn into_actual(self) -> T {
+        *self
+    }
+}
+
+impl<T> IntoActual<T> for Actual<T> {
+    fn into_actual(self) -> T {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Actual, Expected};
+
+    #[test]
+    fn mixes_with_references() {
+        assert_eq!(crate::assert_eq(&7, Actual(7)), Ok(7));
+        assert_eq!(
+            crate::assert_eq(Expected(7_u64), &8).unwrap_err(),
+            crate::assert_eq(&7, &8).unwrap_err()
+        );
+    }
+}
@@ -0,0 +1,268 @@
+//! SARIF 2.1 logs, the format code-scanning services accept, so
+//! that validators built on this crate can show their findings
+//! next to the code.
+//!
+//! Every kind of error becomes a rule, with its [`ErrorCode`] as a
+//! property, and every error a result of that rule.
+
+use crate::code::Registry;
+use crate::config::ConfigGoof;
+use crate::envelope::write_escaped;
+use crate::{render, Diagnostic, ErrorCode, Fields, Position, Report, Severity, Span, Spanned};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+/// Where in which file an error was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    uri: String,
+    region: Option<Region>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Region {
+    Position(Position),
+    Span(Span),
+}
+
+impl Location {
+    /// Somewhere in the file at `uri`, e.g. `"config/app.toml"`.
+    pub fn new(uri: impl Into<String>) -> Self {
+        Self {
+            uri: uri.into(),
+            region: None,
+        }
+    }
+
+    pub fn at(self, position: Position) -> Self {
+        Self {
+            region: Some(Region::Position(position)),
+            ..self
+        }
+    }
+
+    pub fn span(self, span: Span) -> Self {
+        Self {
+            region: Some(Region::Span(span)),
+            ..self
+        }
+    }
+
+    /// Where `spanned` is in the file at `uri`: its line and column if
+    /// they were worked out, and its bytes otherwise.
+    pub fn of<E>(uri: impl Into<String>, spanned: &Spanned<E>) -> Self {
+        let location = Self::new(uri);
+        match spanned.position() {
+            Some(position) => location.at(position),
+            None => location.span(spanned.span()),
+        }
+    }
+}
+
+struct Finding {
+    rule: Option<usize>,
+    level: &'static str,
+    message: String,
+    location: Option<Location>,
+}
+
+/// A SARIF log of one run of a tool, rendered by `Display`.
+///
+/// # Examples
+/// ```rust
+/// use goof::sarif::{Location, Sarif};
+/// use goof::{Span, Spanned};
+///
+/// let source = "[server]\nport = 0\n";
+/// let error = goof::assert_in(&0, &(1..65535)).unwrap_err();
+/// let error = Spanned::new(error, Span::new(16, 1)).located_in(source);
+///
+/// let mut log = Sarif::new("config-lint");
+/// log.error(error.error(), Some(Location::of("app.toml", &error)));
+/// let json = log.to_string();
+/// assert!(json.contains(r#""rules":[{"id":"outside","properties":{"code":2}}]"#));
+/// assert!(json.contains(r#""region":{"startLine":2,"startColumn":8}"#));
+/// ```
+pub struct Sarif {
+    tool: &'static str,
+    rules: Vec<(&'static str, Option<u32>)>,
+    findings: Vec<Finding>,
+}
+
+impl Sarif {
+    /// An empty log for the tool called `tool`.
+    pub fn new(tool: &'static str) -> Self {
+        Self {
+            tool,
+            rules: Vec::new(),
+            findings: Vec::new(),
+        }
+    }
+
+    /// Record `error` at the level `"error"`.
+    pub fn error<E: Fields + ErrorCode + Display>(
+        &mut self,
+        error: &E,
+        location: Option<Location>,
+    ) -> &mut Self {
+        let rule = self.rule(error.kind(), Some(error.code()));
+        self.push(Some(rule), Severity::Error, render(error, false), location)
+    }
+
+    /// Record the error in `diagnostic` at its severity, under its rule
+    /// if it has one and under its kind otherwise.
+    pub fn diagnostic<E: Fields + ErrorCode + Display>(
+        &mut self,
+        diagnostic: &Diagnostic<E>,
+        location: Option<Location>,
+    ) -> &mut Self {
+        let error = diagnostic.error();
+        let rule = match diagnostic.rule() {
+            Some(rule) => self.rule(rule, None),
+            None => self.rule(error.kind(), Some(error.code())),
+        };
+        let message = render(error, false);
+        self.push(Some(rule), diagnostic.severity(), message, location)
+    }
+
+    /// Record every problem in `config`, in the file it was loaded
+    /// from.
+    pub fn config(&mut self, config: &ConfigGoof) -> &mut Self {
+        let registry = Registry::new();
+        for problem in config.problems() {
+            let code = registry
+                .iter()
+                .find(|(_, name)| *name == problem.kind)
+                .map(|(code, _)| code);
+            let rule = self.rule(problem.kind, code);
+            let message = render(&format_args!("{}: {}", problem.path, problem.error), false);
+            let location = Location::new(config.source);
+            self.push(Some(rule), Severity::Error, message, Some(location));
+        }
+        self
+    }
+
+    /// Record `report`, which does not know its kind, without a rule.
+    pub fn report(&mut self, report: &Report, location: Option<Location>) -> &mut Self {
+        self.push(None, report.severity(), render(report, false), location)
+    }
+
+    fn rule(&mut self, id: &'static str, code: Option<u32>) -> usize {
+        match self.rules.iter().position(|(known, _)| *known == id) {
+            Some(index) => index,
+            None => {
+                self.rules.push((id, code));
+                self.rules.len() - 1
+            }
+        }
+    }
+
+    fn push(
+        &mut self,
+        rule: Option<usize>,
+        severity: Severity,
+        message: String,
+        location: Option<Location>,
+    ) -> &mut Self {
+        let level = match severity {
+            Severity::Hint => "note",
+            Severity::Warning => "warning",
+            Severity::Error | Severity::Fatal => "error",
+        };
+        self.findings.push(Finding {
+            rule,
+            level,
+            message,
+            location,
+        });
+        self
+    }
+}
+
+impl Display for Sarif {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(
+            r#"{"$schema":"https://json.schemastore.org/sarif-2.1.0.json","version":"2.1.0","runs":[{"tool":{"driver":{"name":"#,
+        )?;
+        write_escaped(f, self.tool)?;
+        f.write_str(r#","rules":["#)?;
+        for (index, (id, code)) in self.rules.iter().enumerate() {
+            if index > 0 {
+                f.write_str(",")?;
+            }
+            f.write_str(r#"{"id":"#)?;
+            write_escaped(f, id)?;
+            if let Some(code) = code {
+                write!(f, r#","properties":{{"code":{}}}"#, code)?;
+            }
+            f.write_str("}")?;
+        }
+        f.write_str(r#"]}},"results":["#)?;
+        for (index, finding) in self.findings.iter().enumerate() {
+            if index > 0 {
+                f.write_str(",")?;
+            }
+            f.write_str("{")?;
+            if let Some(rule) = finding.rule {
+                f.write_str(r#""ruleId":"#)?;
+                write_escaped(f, self.rules[rule].0)?;
+                write!(f, r#","ruleIndex":{},"#, rule)?;
+            }
+            write!(f, r#""level":"{}","message":{{"text":"#, finding.level)?;
+            write_escaped(f, &finding.message)?;
+            f.write_str("}")?;
+            if let Some(location) = &finding.location {
+                f.write_str(r#","locations":[{"physicalLocation":{"artifactLocation":{"uri":"#)?;
+                write_escaped(f, &location.uri)?;
+                f.write_str("}")?;
+                match location.region {
+                    Some(Region::Position(position)) => write!(
+                        f,
+                        r#","region":{{"startLine":{},"startColumn":{}}}"#,
+                        position.line, position.column
+                    )?,
+                    Some(Region::Span(span)) => write!(
+                        f,
+                        r#","region":{{"byteOffset":{},"byteLength":{}}}"#,
+                        span.offset, span.len
+                    )?,
+                    None => {}
+                }
+                f.write_str("}}]")?;
+            }
+            f.write_str("}")?;
+        }
+        f.write_str("]}]}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Location, Sarif};
+    use crate::config::ConfigGoof;
+    use crate::{Diagnostic, Report, Severity, Span};
+    use alloc::string::ToString;
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn rules_are_shared() {
+        let mut config = ConfigGoof::new("app.toml");
+        config.key_in_range("port", 0_u32, &(1..65535));
+        config.key_in_range("jobs", 0_u32, &(1..16));
+        let hint = Diagnostic::new(Severity::Hint, crate::assert_eq(&4, &2).unwrap_err())
+            .with_rule("tab-width");
+
+        let mut log = Sarif::new("lint");
+        log.config(&config)
+            .diagnostic(&hint, Some(Location::new("a.rs").span(Span::new(3, 1))))
+            .report(&Report::new(crate::Cancelled::new()), None);
+        let json = log.to_string();
+        assert!(json
+            .contains(r#""rules":[{"id":"outside","properties":{"code":2}},{"id":"tab-width"}]"#));
+        assert_eq!(json.matches(r#""ruleIndex":0"#).count(), 2);
+        assert!(json.contains(r#""level":"note","message":{"text":"Expected 4, but got 2"}"#));
+        assert!(json.contains(r#""region":{"byteOffset":3,"byteLength":1}"#));
+        assert!(json.ends_with(r#"{"level":"error","message":{"text":"Cancelled"}}]}]}"#));
+    }
+}
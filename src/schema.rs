@@ -0,0 +1,776 @@
+//! Errors found when validating the shape of deserialized data:
+//! fields that are missing, fields that are not expected, and keys
+//! that appear twice.
+//!
+//! Each of them names the field and the container it belongs to,
+//! e.g. the struct or table being deserialized.
+//!
+//! [`DeserGoof`] gathers them, with [`InvalidData`] and free-form
+//! messages, into one error for `Deserialize` implementations.  With
+//! the `serde` feature it is a `serde::de::Error`.
+
+use crate::{code, hook, ErrorCode, Fields, Goof};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt::{Debug, Display};
+
+/// Require that an optional field was present.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::schema::require_field;
+///
+/// let port: Option<u16> = None;
+/// assert_eq!(
+///     require_field("Server", "port", port).unwrap_err().to_string(),
+///     "Missing field `port` in Server"
+/// );
+/// ```
+pub fn require_field<T>(
+    container: &'static str,
+    field: &'static str,
+    value: Option<T>,
+) -> Result<T, MissingField> {
+    value.ok_or_else(|| {
+        hook::observe("missing_field", code::MISSING_FIELD, "require_field");
+        MissingField {
+            container,
+            field,
+            provenance: "require_field",
+        }
+    })
+}
+
+/// A map or set that can be asked whether it has a key, for
+/// [`assert_new_key`].
+pub trait Keyed<K> {
+    fn contains_key(&self, key: &K) -> bool;
+}
+
+impl<K: Ord, V> Keyed<K> for BTreeMap<K, V> {
+    fn contains_key(&self, key: &K) -> bool {
+        BTreeMap::contains_key(self, key)
+    }
+}
+
+impl<K: Ord> Keyed<K> for BTreeSet<K> {
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains(key)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + core::hash::Hash, V, S: core::hash::BuildHasher> Keyed<K>
+    for std::collections::HashMap<K, V, S>
+{
+    fn contains_key(&self, key: &K) -> bool {
+        std::collections::HashMap::contains_key(self, key)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + core::hash::Hash, S: core::hash::BuildHasher> Keyed<K>
+    for std::collections::HashSet<K, S>
+{
+    fn contains_key(&self, key: &K) -> bool {
+        self.contains(key)
+    }
+}
+
+/// Assert that `key` is not in `map` yet, before inserting it into a
+/// registry or cache that must not overwrite entries.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::schema::assert_new_key;
+/// use std::collections::BTreeMap;
+///
+/// let mut plugins = BTreeMap::new();
+/// let name = assert_new_key("plugins", &plugins, "gzip").unwrap();
+/// plugins.insert(name, 1);
+/// assert_eq!(
+///     assert_new_key("plugins", &plugins, "gzip").unwrap_err().to_string(),
+///     "Duplicate key `gzip` in plugins"
+/// );
+/// ```
+pub fn assert_new_key<K: Display, M: Keyed<K> + ?Sized>(
+    container: &'static str,
+    map: &M,
+    key: K,
+) -> Result<K, DuplicateKey> {
+    if map.contains_key(&key) {
+        hook::observe("duplicate_key", code::DUPLICATE_KEY, "assert_new_key");
+        Err(DuplicateKey {
+            container,
+            key: key.to_string(),
+            provenance: "assert_new_key",
+        })
+    } else {
+        Ok(key)
+    }
+}
+
+/// A required field was not there.
+#[derive(Clone, Copy)]
+pub struct MissingField {
+    pub(crate) container: &'static str,
+    pub(crate) field: &'static str,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl MissingField {
+    pub fn new(container: &'static str, field: &'static str) -> Self {
+        hook::observe("missing_field", code::MISSING_FIELD, "MissingField::new");
+        Self {
+            container,
+            field,
+            provenance: "MissingField::new",
+        }
+    }
+
+    pub fn container(&self) -> &'static str {
+        self.container
+    }
+
+    pub fn field(&self) -> &'static str {
+        self.field
+    }
+
+    /// The name of the function that created this error, e.g.
+    /// `"require_field"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl PartialEq for MissingField {
+    fn eq(&self, other: &Self) -> bool {
+        self.container == other.container && self.field == other.field
+    }
+}
+
+impl Eq for MissingField {}
+
+impl Debug for MissingField {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MissingField")
+            .field("container", &self.container)
+            .field("field", &self.field)
+            .finish()
+    }
+}
+
+impl Display for MissingField {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(f, "Missing field `{}` in {}", self.field, self.container)
+    }
+}
+
+impl core::error::Error for MissingField {}
+
+impl Fields for MissingField {
+    fn kind(&self) -> &'static str {
+        "missing_field"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("container", &self.container);
+        visit("field", &self.field);
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(format!("add `{}`", self.field))
+    }
+}
+
+impl ErrorCode for MissingField {
+    fn code(&self) -> u32 {
+        code::MISSING_FIELD
+    }
+}
+
+/// A field that the container does not have.
+#[derive(Clone)]
+pub struct UnknownField {
+    pub(crate) container: &'static str,
+    pub(crate) field: String,
+    /// Every field the container does have, if known.
+    pub(crate) expected: &'static [&'static str],
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl UnknownField {
+    /// # Examples
+    /// ```rust
+    /// # if cfg!(feature = "compact") { return; }
+    /// use goof::schema::UnknownField;
+    ///
+    /// let error = UnknownField::new("Server", "prot", &["host", "port"]);
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "Unknown field `prot` in Server, expected one of `host`, `port`"
+    /// );
+    /// ```
+    pub fn new(
+        container: &'static str,
+        field: impl Into<String>,
+        expected: &'static [&'static str],
+    ) -> Self {
+        hook::observe("unknown_field", code::UNKNOWN_FIELD, "UnknownField::new");
+        Self {
+            container,
+            field: field.into(),
+            expected,
+            provenance: "UnknownField::new",
+        }
+    }
+
+    pub fn container(&self) -> &'static str {
+        self.container
+    }
+
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    pub fn expected(&self) -> &'static [&'static str] {
+        self.expected
+    }
+
+    /// The name of the function that created this error, e.g.
+    /// `"UnknownField::new"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl PartialEq for UnknownField {
+    fn eq(&self, other: &Self) -> bool {
+        self.container == other.container
+            && self.field == other.field
+            && self.expected == other.expected
+    }
+}
+
+impl Eq for UnknownField {}
+
+impl Debug for UnknownField {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("UnknownField")
+            .field("container", &self.container)
+            .field("field", &self.field)
+            .field("expected", &self.expected)
+            .finish()
+    }
+}
+
+impl Display for UnknownField {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(f, "Unknown field `{}` in {}", self.field, self.container)?;
+        for (index, field) in self.expected.iter().enumerate() {
+            let separator = if index == 0 { ", expected one of" } else { "," };
+            write!(f, "{} `{}`", separator, field)?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for UnknownField {}
+
+impl Fields for UnknownField {
+    fn kind(&self) -> &'static str {
+        "unknown_field"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("container", &self.container);
+        visit("field", &self.field);
+        if !self.expected.is_empty() {
+            if let Ok(expected) = crate::join(self.expected, ", ") {
+                visit("expected", &expected);
+            }
+        }
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(format!("remove `{}`", self.field))
+    }
+}
+
+impl ErrorCode for UnknownField {
+    fn code(&self) -> u32 {
+        code::UNKNOWN_FIELD
+    }
+}
+
+/// A key that appeared more than once where keys must be unique.
+#[derive(Clone)]
+pub struct DuplicateKey {
+    pub(crate) container: &'static str,
+    pub(crate) key: String,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl DuplicateKey {
+    pub fn new(container: &'static str, key: impl Into<String>) -> Self {
+        hook::observe("duplicate_key", code::DUPLICATE_KEY, "DuplicateKey::new");
+        Self {
+            container,
+            key: key.into(),
+            provenance: "DuplicateKey::new",
+        }
+    }
+
+    pub fn container(&self) -> &'static str {
+        self.container
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// The name of the function that created this error, e.g.
+    /// `"DuplicateKey::new"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl PartialEq for DuplicateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.container == other.container && self.key == other.key
+    }
+}
+
+impl Eq for DuplicateKey {}
+
+impl Debug for DuplicateKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DuplicateKey")
+            .field("container", &self.container)
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+impl Display for DuplicateKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(f, "Duplicate key `{}` in {}", self.key, self.container)
+    }
+}
+
+impl core::error::Error for DuplicateKey {}
+
+impl Fields for DuplicateKey {
+    fn kind(&self) -> &'static str {
+        "duplicate_key"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("container", &self.container);
+        visit("key", &self.key);
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(format!("remove one of the `{}` entries", self.key))
+    }
+}
+
+impl ErrorCode for DuplicateKey {
+    fn code(&self) -> u32 {
+        code::DUPLICATE_KEY
+    }
+}
+
+/// Data that is not of the type or value that was expected.
+#[derive(Clone)]
+pub struct InvalidData {
+    /// What was expected, e.g. `"a port number"`.
+    pub(crate) expected: String,
+    /// What was there instead, e.g. `"string \"http\""`.
+    pub(crate) unexpected: String,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl InvalidData {
+    /// # Examples
+    /// ```rust
+    /// # if cfg!(feature = "compact") { return; }
+    /// use goof::schema::InvalidData;
+    ///
+    /// let error = InvalidData::new("a port number", "string \"http\"");
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "Expected a port number, but got string \"http\""
+    /// );
+    /// ```
+    pub fn new(expected: impl Into<String>, unexpected: impl Into<String>) -> Self {
+        Self::observed(expected.into(), unexpected.into(), "InvalidData::new")
+    }
+
+    fn observed(expected: String, unexpected: String, provenance: &'static str) -> Self {
+        hook::observe("invalid_data", code::INVALID_DATA, provenance);
+        Self {
+            expected,
+            unexpected,
+            provenance,
+        }
+    }
+
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+
+    pub fn unexpected(&self) -> &str {
+        &self.unexpected
+    }
+
+    /// The name of the function that created this error, e.g.
+    /// `"InvalidData::new"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl PartialEq for InvalidData {
+    fn eq(&self, other: &Self) -> bool {
+        self.expected == other.expected && self.unexpected == other.unexpected
+    }
+}
+
+impl Eq for InvalidData {}
+
+impl Debug for InvalidData {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("InvalidData")
+            .field("expected", &self.expected)
+            .field("unexpected", &self.unexpected)
+            .finish()
+    }
+}
+
+impl Display for InvalidData {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(f, "Expected {}, but got {}", self.expected, self.unexpected)
+    }
+}
+
+impl core::error::Error for InvalidData {}
+
+impl Fields for InvalidData {
+    fn kind(&self) -> &'static str {
+        "invalid_data"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("expected", &self.expected);
+        visit("actual", &self.unexpected);
+    }
+}
+
+impl ErrorCode for InvalidData {
+    fn code(&self) -> u32 {
+        code::INVALID_DATA
+    }
+}
+
+/// Why deserializing failed, kept as structured as the deserializer
+/// allows.
+///
+/// Deserializers do not say which container they were filling, so the
+/// fields are reported in [`DeserGoof::INPUT`] until
+/// [`in_container`](DeserGoof::in_container) names it.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::schema::{DeserGoof, MissingField};
+///
+/// let error = DeserGoof::MissingField(MissingField::new(DeserGoof::INPUT, "port"));
+/// assert_eq!(error.to_string(), "Missing field `port` in the input");
+/// assert_eq!(
+///     error.in_container("Server").to_string(),
+///     "Missing field `port` in Server"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeserGoof {
+    /// What `serde::de::Error::custom` makes.
+    Custom(Goof<'static>),
+    /// A type, value, length or enum variant other than the expected.
+    Invalid(InvalidData),
+    MissingField(MissingField),
+    UnknownField(UnknownField),
+    DuplicateField(DuplicateKey),
+}
+
+impl DeserGoof {
+    /// The container of the fields, until it is named.
+    pub const INPUT: &'static str = "the input";
+
+    /// Name the container of the field that is missing, unknown or
+    /// duplicated, e.g. with `map_err` around a derived `Deserialize`.
+    pub fn in_container(self, container: &'static str) -> Self {
+        match self {
+            DeserGoof::MissingField(error) => {
+                DeserGoof::MissingField(MissingField { container, ..error })
+            }
+            DeserGoof::UnknownField(error) => {
+                DeserGoof::UnknownField(UnknownField { container, ..error })
+            }
+            DeserGoof::DuplicateField(error) => {
+                DeserGoof::DuplicateField(DuplicateKey { container, ..error })
+            }
+            other => other,
+        }
+    }
+}
+
+impl Display for DeserGoof {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        match self {
+            DeserGoof::Custom(error) => Display::fmt(error, f),
+            DeserGoof::Invalid(error) => Display::fmt(error, f),
+            DeserGoof::MissingField(error) => Display::fmt(error, f),
+            DeserGoof::UnknownField(error) => Display::fmt(error, f),
+            DeserGoof::DuplicateField(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl core::error::Error for DeserGoof {}
+
+impl Fields for DeserGoof {
+    fn kind(&self) -> &'static str {
+        match self {
+            DeserGoof::Custom(error) => error.kind(),
+            DeserGoof::Invalid(error) => error.kind(),
+            DeserGoof::MissingField(error) => error.kind(),
+            DeserGoof::UnknownField(error) => error.kind(),
+            DeserGoof::DuplicateField(error) => error.kind(),
+        }
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        match self {
+            DeserGoof::Custom(error) => error.for_each_field(visit),
+            DeserGoof::Invalid(error) => error.for_each_field(visit),
+            DeserGoof::MissingField(error) => error.for_each_field(visit),
+            DeserGoof::UnknownField(error) => error.for_each_field(visit),
+            DeserGoof::DuplicateField(error) => error.for_each_field(visit),
+        }
+    }
+
+    fn help(&self) -> Option<String> {
+        match self {
+            DeserGoof::Custom(error) => error.help(),
+            DeserGoof::Invalid(error) => error.help(),
+            DeserGoof::MissingField(error) => error.help(),
+            DeserGoof::UnknownField(error) => error.help(),
+            DeserGoof::DuplicateField(error) => error.help(),
+        }
+    }
+}
+
+impl ErrorCode for DeserGoof {
+    fn code(&self) -> u32 {
+        match self {
+            DeserGoof::Custom(error) => error.code(),
+            DeserGoof::Invalid(error) => error.code(),
+            DeserGoof::MissingField(error) => error.code(),
+            DeserGoof::UnknownField(error) => error.code(),
+            DeserGoof::DuplicateField(error) => error.code(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use super::{DeserGoof, DuplicateKey, InvalidData, MissingField, UnknownField};
+    use crate::{code, hook, Goof};
+    use ::serde::de::{Error, Expected, Unexpected};
+    use alloc::borrow::Cow;
+    use alloc::format;
+    use alloc::string::{String, ToString};
+    use core::fmt::{Display, Write};
+
+    impl Error for DeserGoof {
+        fn custom<T: Display>(message: T) -> Self {
+            hook::observe("goof", code::GOOF, "de::Error::custom");
+            DeserGoof::Custom(Goof {
+                message: Cow::Owned(message.to_string()),
+                provenance: "de::Error::custom",
+            })
+        }
+
+        fn invalid_type(unexpected: Unexpected<'_>, expected: &dyn Expected) -> Self {
+            DeserGoof::Invalid(InvalidData::observed(
+                expected.to_string(),
+                unexpected.to_string(),
+                "de::Error::invalid_type",
+            ))
+        }
+
+        fn invalid_value(unexpected: Unexpected<'_>, expected: &dyn Expected) -> Self {
+            DeserGoof::Invalid(InvalidData::observed(
+                expected.to_string(),
+                unexpected.to_string(),
+                "de::Error::invalid_value",
+            ))
+        }
+
+        fn invalid_length(len: usize, expected: &dyn Expected) -> Self {
+            DeserGoof::Invalid(InvalidData::observed(
+                expected.to_string(),
+                format!("length {}", len),
+                "de::Error::invalid_length",
+            ))
+        }
+
+        fn unknown_variant(variant: &str, expected: &'static [&'static str]) -> Self {
+            let mut one_of = String::new();
+            for (index, known) in expected.iter().enumerate() {
+                let separator = if index == 0 { "one of" } else { "," };
+                let _ = write!(one_of, "{} `{}`", separator, known);
+            }
+            if one_of.is_empty() {
+                one_of.push_str("no variant");
+            }
+            DeserGoof::Invalid(InvalidData::observed(
+                one_of,
+                format!("variant `{}`", variant),
+                "de::Error::unknown_variant",
+            ))
+        }
+
+        fn unknown_field(field: &str, expected: &'static [&'static str]) -> Self {
+            let provenance = "de::Error::unknown_field";
+            hook::observe("unknown_field", code::UNKNOWN_FIELD, provenance);
+            DeserGoof::UnknownField(UnknownField {
+                container: DeserGoof::INPUT,
+                field: field.into(),
+                expected,
+                provenance,
+            })
+        }
+
+        fn missing_field(field: &'static str) -> Self {
+            let provenance = "de::Error::missing_field";
+            hook::observe("missing_field", code::MISSING_FIELD, provenance);
+            DeserGoof::MissingField(MissingField {
+                container: DeserGoof::INPUT,
+                field,
+                provenance,
+            })
+        }
+
+        fn duplicate_field(field: &'static str) -> Self {
+            let provenance = "de::Error::duplicate_field";
+            hook::observe("duplicate_key", code::DUPLICATE_KEY, provenance);
+            DeserGoof::DuplicateField(DuplicateKey {
+                container: DeserGoof::INPUT,
+                key: field.into(),
+                provenance,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{assert_new_key, DeserGoof, DuplicateKey, InvalidData, UnknownField};
+    use alloc::collections::BTreeSet;
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn messages() {
+        assert_eq!(
+            UnknownField::new("Server", "prot", &[]).to_string(),
+            "Unknown field `prot` in Server"
+        );
+        assert_eq!(
+            DuplicateKey::new("[servers]", "port").to_string(),
+            "Duplicate key `port` in [servers]"
+        );
+    }
+
+    #[test]
+    fn new_keys() {
+        let keys = BTreeSet::from([1, 2]);
+        assert_eq!(assert_new_key("ids", &keys, 3), Ok(3));
+        assert_eq!(
+            assert_new_key("ids", &keys, 2),
+            Err(DuplicateKey::new("ids", "2"))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn deser_goof_names_its_container() {
+        let error = DeserGoof::UnknownField(UnknownField::new(DeserGoof::INPUT, "prot", &["port"]));
+        assert_eq!(
+            error.in_container("Server").to_string(),
+            "Unknown field `prot` in Server, expected one of `port`"
+        );
+        let error = DeserGoof::Invalid(InvalidData::new("u16", "-1"));
+        assert_eq!(error.clone().in_container("Server"), error);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn serde_errors_stay_structured() {
+        use crate::{code, ErrorCode, Fields};
+        use serde::de::value::{StrDeserializer, U64Deserializer};
+        use serde::de::{Error, IntoDeserializer, Unexpected};
+        use serde::Deserialize;
+
+        let str: StrDeserializer<'_, DeserGoof> = "http".into_deserializer();
+        let error = u16::deserialize(str).unwrap_err();
+        assert_eq!(error.code(), code::INVALID_DATA);
+        assert_eq!(error.to_string(), "Expected u16, but got string \"http\"");
+        let u64: U64Deserializer<DeserGoof> = 70_000_u64.into_deserializer();
+        let error = u16::deserialize(u64).unwrap_err();
+        assert_eq!(error.to_string(), "Expected u16, but got integer `70000`");
+
+        let error = DeserGoof::invalid_length(3, &"a pair");
+        assert_eq!(error.to_string(), "Expected a pair, but got length 3");
+        let error = DeserGoof::unknown_variant("Udp", &["Tcp", "Quic"]);
+        assert_eq!(error.kind(), "invalid_data");
+        assert_eq!(
+            error.to_string(),
+            "Expected one of `Tcp`, `Quic`, but got variant `Udp`"
+        );
+        let error = DeserGoof::invalid_value(Unexpected::Signed(-1), &"a port");
+        let mut fields = Vec::new();
+        error.for_each_field(&mut |name, value| fields.push((name, value.to_string())));
+        assert_eq!(
+            fields,
+            [
+                ("expected", "a port".to_string()),
+                ("actual", "integer `-1`".to_string())
+            ]
+        );
+
+        let error = DeserGoof::missing_field("port").in_container("Server");
+        assert_eq!(error.code(), code::MISSING_FIELD);
+        assert_eq!(error.to_string(), "Missing field `port` in Server");
+        let error = DeserGoof::duplicate_field("port");
+        assert_eq!(error.to_string(), "Duplicate key `port` in the input");
+        let error = DeserGoof::custom(format_args!("port {} is taken", 80));
+        assert_eq!(error.kind(), "goof");
+        assert_eq!(error.to_string(), "port 80 is taken");
+    }
+}
@@ -0,0 +1,149 @@
+//! Keeping sensitive values out of error messages.
+
+use core::fmt::{Debug, Display};
+use core::hash::{Hash, Hasher};
+
+/// A value that is compared as usual, but never rendered, so that
+/// asserting on tokens or passwords does not leak them into logs.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::Secret;
+///
+/// let expected = Secret::new("hunter2");
+/// let error = goof::assert_eq(&expected, &Secret::new("*******")).unwrap_err();
+/// assert_eq!(error.to_string(), "Expected [REDACTED], but got [REDACTED]");
+///
+/// // A fingerprint tells different values apart without showing them.
+/// let error = goof::assert_eq(
+///     &Secret::fingerprinted("hunter2"),
+///     &Secret::fingerprinted("*******"),
+/// )
+/// .unwrap_err();
+/// assert!(error.to_string().starts_with("Expected [REDACTED "));
+/// assert!(!error.to_string().contains("hunter2"));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Secret<T> {
+    value: T,
+    fingerprint: Option<u32>,
+}
+
+impl<T> Secret<T> {
+    pub const fn new(value: T) -> Self {
+        Self {
+            value,
+            fingerprint: None,
+        }
+    }
+
+    /// Like [`Secret::new`], but rendered with a short hash of the
+    /// value, to tell whether two redacted values are the same.
+    ///
+    /// The hash is not cryptographic: a guessable secret, such as a
+    /// PIN, can be recovered from it.
+    pub fn fingerprinted(value: T) -> Self
+    where
+        T: Hash,
+    {
+        let mut hasher = Fnv1a::default();
+        value.hash(&mut hasher);
+        Self {
+            value,
+            fingerprint: Some(hasher.finish() as u32),
+        }
+    }
+
+    /// The value itself.  Be careful where it goes.
+    pub fn expose(&self) -> &T {
+        &self.value
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+// Only the value counts, with or without a fingerprint.
+impl<T: PartialEq> PartialEq for Secret<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq> Eq for Secret<T> {}
+
+impl<T: PartialOrd> PartialOrd for Secret<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<T: Ord> Ord for Secret<T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<T: Hash> Hash for Secret<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state)
+    }
+}
+
+impl<T> Display for Secret<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.fingerprint {
+            Some(fingerprint) => write!(f, "[REDACTED {:08x}]", fingerprint),
+            None => f.write_str("[REDACTED]"),
+        }
+    }
+}
+
+impl<T> Debug for Secret<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl<T> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+/// The 64-bit FNV-1a hash, which needs neither `std` nor a seed.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Secret;
+    use alloc::format;
+
+    #[test]
+    fn never_rendered() {
+        let secret = Secret::fingerprinted("hunter2");
+        assert!(!format!("{:?} {:#}", secret, secret).contains("hunter2"));
+        assert_eq!(secret, Secret::new("hunter2"));
+        assert_eq!(secret.expose(), &"hunter2");
+    }
+}
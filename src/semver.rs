@@ -0,0 +1,139 @@
+//! Checking versions against requirements, for plugin loaders and
+//! protocol handshakes.
+//!
+//! Versions and requirements are those of the `semver` crate, so they
+//! follow Cargo, pre-releases and build metadata included.
+
+use crate::{code, hook, ErrorCode, Fields};
+use alloc::format;
+use alloc::string::String;
+use core::fmt::{Debug, Display};
+
+pub use ::semver::{Version, VersionReq};
+
+/// Assert that `found` satisfies `requirement`.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::semver::{assert_version_in, Version};
+///
+/// let requirement = "^1.4".parse().unwrap();
+/// assert!(assert_version_in(Version::new(1, 7, 2), &requirement).is_ok());
+/// assert!(assert_version_in("1.4.0-rc.1".parse().unwrap(), &requirement).is_err());
+/// assert_eq!(
+///     assert_version_in(Version::new(2, 1, 0), &requirement)
+///         .unwrap_err()
+///         .to_string(),
+///     "Version 2.1.0 does not satisfy ^1.4"
+/// );
+/// ```
+pub fn assert_version_in(
+    found: Version,
+    requirement: &VersionReq,
+) -> Result<Version, VersionOutside> {
+    if requirement.matches(&found) {
+        Ok(found)
+    } else {
+        hook::observe(
+            "version_outside",
+            code::VERSION_OUTSIDE,
+            "assert_version_in",
+        );
+        Err(VersionOutside {
+            requirement: requirement.clone(),
+            found,
+            provenance: "assert_version_in",
+        })
+    }
+}
+
+/// A version did not satisfy a requirement.
+#[derive(Clone)]
+pub struct VersionOutside {
+    pub(crate) requirement: VersionReq,
+    pub(crate) found: Version,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl VersionOutside {
+    pub fn requirement(&self) -> &VersionReq {
+        &self.requirement
+    }
+
+    pub fn found(&self) -> &Version {
+        &self.found
+    }
+
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_version_in"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl PartialEq for VersionOutside {
+    fn eq(&self, other: &Self) -> bool {
+        self.requirement == other.requirement && self.found == other.found
+    }
+}
+
+impl Eq for VersionOutside {}
+
+impl Debug for VersionOutside {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("VersionOutside")
+            .field("requirement", &self.requirement)
+            .field("found", &self.found)
+            .finish()
+    }
+}
+
+impl Display for VersionOutside {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(
+            f,
+            "Version {} does not satisfy {}",
+            self.found, self.requirement
+        )
+    }
+}
+
+impl core::error::Error for VersionOutside {}
+
+impl Fields for VersionOutside {
+    fn kind(&self) -> &'static str {
+        "version_outside"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("requirement", &self.requirement);
+        visit("found", &self.found);
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(format!("use a version matching {}", self.requirement))
+    }
+}
+
+impl ErrorCode for VersionOutside {
+    fn code(&self) -> u32 {
+        code::VERSION_OUTSIDE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{assert_version_in, VersionReq};
+
+    #[test]
+    fn pre_releases() {
+        let requirement: VersionReq = ">=1.2.3-rc.1, <2".parse().unwrap();
+        assert!(assert_version_in("1.2.3-rc.2+build.7".parse().unwrap(), &requirement).is_ok());
+        let error = assert_version_in("1.2.3-beta".parse().unwrap(), &requirement).unwrap_err();
+        assert_eq!(error.found().pre.as_str(), "beta");
+        assert_eq!(error.requirement(), &requirement);
+    }
+}
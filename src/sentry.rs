@@ -0,0 +1,104 @@
+//! Sentry events for errors, so that they are grouped by what went
+//! wrong instead of by their message, which differs with every value.
+//!
+//! The event of an error has:
+//!
+//! | field         | value                                       |
+//! |---------------|---------------------------------------------|
+//! | `fingerprint` | `[kind]`, as in [`Fields::kind`]            |
+//! | `exception`   | one, with the kind as its type and the message as its value |
+//! | `message`     | the `Display` of the error                  |
+//! | `tags`        | `kind` and `code`, as in [`ErrorCode`]      |
+//! | `extra`       | one string per payload field, as in [`Fields`] |
+//! | `culprit`     | where the event was made                    |
+
+use crate::{render, ErrorCode, Fields};
+use core::fmt::Display;
+use core::panic::Location;
+use sentry_core::protocol::{Event, Exception, Level, Value};
+use sentry_core::types::Uuid;
+use std::borrow::Cow;
+
+/// An error that can be reported to Sentry, which is every error with
+/// [`Fields`] and an [`ErrorCode`].
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::ToSentryEvent;
+///
+/// let error = goof::assert_eq(&32, &0).unwrap_err();
+/// let event = error.to_sentry_event();
+/// assert_eq!(*event.fingerprint, ["mismatch"]);
+/// assert_eq!(event.exception[0].ty, "mismatch");
+/// assert_eq!(event.message.as_deref(), Some("Expected 32, but got 0"));
+/// assert_eq!(event.extra["expected"], "32");
+/// ```
+pub trait ToSentryEvent: Fields + ErrorCode + Display {
+    /// The event describing `self`, at the `Error` level.
+    #[track_caller]
+    fn to_sentry_event(&self) -> Event<'static> {
+        let message = render(self, false);
+        let mut event = Event {
+            level: Level::Error,
+            fingerprint: Cow::Owned(vec![Cow::Borrowed(self.kind())]),
+            culprit: Some(Location::caller().to_string()),
+            exception: vec![Exception {
+                ty: self.kind().into(),
+                value: Some(message.clone()),
+                ..Exception::default()
+            }]
+            .into(),
+            message: Some(message),
+            ..Event::default()
+        };
+        event.tags.insert("kind".into(), self.kind().into());
+        event.tags.insert("code".into(), self.code().to_string());
+        self.for_each_field(&mut |name, value| {
+            let value = Value::String(render(value, false));
+            event.extra.insert(name.into(), value);
+        });
+        event
+    }
+
+    /// Send the event of `self` to the current Sentry hub, returning
+    /// its id, which is nil if there is no client.
+    #[track_caller]
+    fn capture(&self) -> Uuid {
+        sentry_core::capture_event(self.to_sentry_event())
+    }
+}
+
+impl<E: Fields + ErrorCode + Display + ?Sized> ToSentryEvent for E {}
+
+#[cfg(test)]
+mod tests {
+    use super::ToSentryEvent;
+    use sentry_core::protocol::Level;
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn grouped_by_kind() {
+        let seven = crate::assert_in(&7, &(1..5)).unwrap_err().to_sentry_event();
+        let eight = crate::assert_in(&8, &(1..5)).unwrap_err().to_sentry_event();
+        assert_eq!(seven.fingerprint, eight.fingerprint);
+        assert_ne!(seven.message, eight.message);
+
+        assert_eq!(seven.level, Level::Error);
+        assert_eq!(*seven.fingerprint, ["outside"]);
+        assert_eq!(seven.exception.len(), 1);
+        assert_eq!(seven.exception[0].ty, "outside");
+        assert_eq!(
+            seven.exception[0].value.as_deref(),
+            Some("Value 7 is outside the allowed range (1, 5] (must be at most 5)")
+        );
+        assert_eq!(seven.tags["kind"], "outside");
+        assert_eq!(seven.tags["code"], "2");
+        assert_eq!(seven.extra["start"], "1");
+        assert_eq!(seven.extra["value"], "7");
+        assert_eq!(seven.extra["interval"], "left_open");
+        assert!(seven.culprit.unwrap().starts_with("src/sentry.rs:"));
+
+        assert!(crate::assert_eq(&1, &2).unwrap_err().capture().is_nil());
+    }
+}
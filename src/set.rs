@@ -0,0 +1,301 @@
+//! Assertions about how collections relate to each other as sets.
+//!
+//! These only need `PartialEq` of the elements, so they compare every
+//! pair and are meant for the small collections found in
+//! configuration and requests, not for bulk data.
+
+use crate::{code, hook, ErrorCode, Fields};
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display};
+
+/// How many offending elements `Display` lists before summarising
+/// the rest.
+pub const SHOWN: usize = 5;
+
+/// Assert that every element of `items` is in `set`.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_subset;
+///
+/// let supported = ["gzip", "br", "zstd"];
+/// assert!(assert_subset(["gzip", "br"], &supported).is_ok());
+/// assert_eq!(
+///     assert_subset(["gzip", "lz4", "xz"], &supported)
+///         .unwrap_err()
+///         .to_string(),
+///     "2 elements are missing from the set: lz4, xz"
+/// );
+/// ```
+pub fn assert_subset<T: PartialEq>(
+    items: impl IntoIterator<Item = T>,
+    set: &[T],
+) -> Result<(), NotSubset<T>> {
+    let missing = distinct(items.into_iter().filter(|item| !set.contains(item)));
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        hook::observe("not_subset", code::NOT_SUBSET, "assert_subset");
+        Err(NotSubset {
+            missing,
+            provenance: "assert_subset",
+        })
+    }
+}
+
+/// Assert that `set` contains every element of `items`; the same as
+/// [`assert_subset`] with the arguments the other way round.
+///
+/// # Examples
+/// ```rust
+/// use goof::assert_superset;
+///
+/// let granted = ["read", "write"];
+/// let error = assert_superset(&granted, ["read", "delete"]).unwrap_err();
+/// assert_eq!(error.missing(), ["delete"]);
+/// ```
+pub fn assert_superset<T: PartialEq>(
+    set: &[T],
+    items: impl IntoIterator<Item = T>,
+) -> Result<(), NotSubset<T>> {
+    let missing = distinct(items.into_iter().filter(|item| !set.contains(item)));
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        hook::observe("not_subset", code::NOT_SUBSET, "assert_superset");
+        Err(NotSubset {
+            missing,
+            provenance: "assert_superset",
+        })
+    }
+}
+
+/// Assert that no element of `items` is in `set`.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_disjoint;
+///
+/// let reserved = ["admin", "root"];
+/// assert!(assert_disjoint(["alice"], &reserved).is_ok());
+/// assert_eq!(
+///     assert_disjoint(["root"], &reserved).unwrap_err().to_string(),
+///     "1 element is also in the other set: root"
+/// );
+/// ```
+pub fn assert_disjoint<T: PartialEq>(
+    items: impl IntoIterator<Item = T>,
+    set: &[T],
+) -> Result<(), NotDisjoint<T>> {
+    let common = distinct(items.into_iter().filter(|item| set.contains(item)));
+    if common.is_empty() {
+        Ok(())
+    } else {
+        hook::observe("not_disjoint", code::NOT_DISJOINT, "assert_disjoint");
+        Err(NotDisjoint {
+            common,
+            provenance: "assert_disjoint",
+        })
+    }
+}
+
+fn distinct<T: PartialEq>(items: impl Iterator<Item = T>) -> Vec<T> {
+    let mut distinct = Vec::new();
+    for item in items {
+        if !distinct.contains(&item) {
+            distinct.push(item);
+        }
+    }
+    distinct
+}
+
+/// Writes at most [`SHOWN`] of `items`, and how many were left out.
+fn list<T: Display>(f: &mut core::fmt::Formatter<'_>, items: &[T]) -> core::fmt::Result {
+    for (index, item) in items.iter().take(SHOWN).enumerate() {
+        if index > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{}", item)?;
+    }
+    if items.len() > SHOWN {
+        write!(f, " and {} more", items.len() - SHOWN)?;
+    }
+    Ok(())
+}
+
+fn elements(count: usize) -> &'static str {
+    if count == 1 {
+        "element is"
+    } else {
+        "elements are"
+    }
+}
+
+/// Some elements were not in the set they had to be in.
+#[derive(Clone)]
+pub struct NotSubset<T> {
+    /// Every distinct element that was missing, in order of
+    /// appearance.
+    pub(crate) missing: Vec<T>,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl<T> NotSubset<T> {
+    pub fn missing(&self) -> &[T] {
+        &self.missing
+    }
+
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_subset"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+
+    pub fn into_missing(self) -> Vec<T> {
+        self.missing
+    }
+}
+
+impl<T: PartialEq> PartialEq for NotSubset<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.missing == other.missing
+    }
+}
+
+impl<T: Eq> Eq for NotSubset<T> {}
+
+impl<T: Debug> Debug for NotSubset<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("NotSubset")
+            .field("missing", &self.missing)
+            .finish()
+    }
+}
+
+impl<T: Display> Display for NotSubset<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(
+            f,
+            "{} {} missing from the set: ",
+            self.missing.len(),
+            elements(self.missing.len())
+        )?;
+        list(f, &self.missing)
+    }
+}
+
+impl<T: Debug + Display> core::error::Error for NotSubset<T> {}
+
+impl<T: Display> Fields for NotSubset<T> {
+    fn kind(&self) -> &'static str {
+        "not_subset"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        if let Ok(missing) = crate::join(&self.missing, ", ") {
+            visit("missing", &missing);
+        }
+    }
+}
+
+impl<T> ErrorCode for NotSubset<T> {
+    fn code(&self) -> u32 {
+        code::NOT_SUBSET
+    }
+}
+
+/// Some elements were in a set they had to stay out of.
+#[derive(Clone)]
+pub struct NotDisjoint<T> {
+    /// Every distinct element that was in both, in order of
+    /// appearance.
+    pub(crate) common: Vec<T>,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl<T> NotDisjoint<T> {
+    pub fn common(&self) -> &[T] {
+        &self.common
+    }
+
+    /// The name of the assertion that created this error, e.g.
+    /// `"assert_disjoint"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+
+    pub fn into_common(self) -> Vec<T> {
+        self.common
+    }
+}
+
+impl<T: PartialEq> PartialEq for NotDisjoint<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.common == other.common
+    }
+}
+
+impl<T: Eq> Eq for NotDisjoint<T> {}
+
+impl<T: Debug> Debug for NotDisjoint<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("NotDisjoint")
+            .field("common", &self.common)
+            .finish()
+    }
+}
+
+impl<T: Display> Display for NotDisjoint<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(
+            f,
+            "{} {} also in the other set: ",
+            self.common.len(),
+            elements(self.common.len())
+        )?;
+        list(f, &self.common)
+    }
+}
+
+impl<T: Debug + Display> core::error::Error for NotDisjoint<T> {}
+
+impl<T: Display> Fields for NotDisjoint<T> {
+    fn kind(&self) -> &'static str {
+        "not_disjoint"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        if let Ok(common) = crate::join(&self.common, ", ") {
+            visit("common", &common);
+        }
+    }
+}
+
+impl<T> ErrorCode for NotDisjoint<T> {
+    fn code(&self) -> u32 {
+        code::NOT_DISJOINT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::assert_subset;
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn lists_at_most_shown() {
+        let error = assert_subset(0..10, &[0, 1]).unwrap_err();
+        assert_eq!(error.missing(), [2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(
+            error.to_string(),
+            "8 elements are missing from the set: 2, 3, 4, 5, 6 and 3 more"
+        );
+        assert_eq!(assert_subset([5, 5], &[]).unwrap_err().missing(), [5]);
+    }
+}
@@ -0,0 +1,171 @@
+//! How bad an error is, for linters and compilers that report
+//! problems which do not all stop the build.
+
+use crate::{ErrorCode, Fields};
+use alloc::string::String;
+use core::error::Error;
+use core::fmt::{Debug, Display};
+
+/// How much a problem matters, from the least to the most.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Severity {
+    Hint,
+    Warning,
+    #[default]
+    Error,
+    /// Nothing after this can be trusted, so checking stops.
+    Fatal,
+}
+
+impl Severity {
+    pub const ALL: [Severity; 4] = [
+        Severity::Hint,
+        Severity::Warning,
+        Severity::Error,
+        Severity::Fatal,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Hint => "hint",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+            Severity::Fatal => "fatal",
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An error `E` with a [`Severity`] and, optionally, the name of the
+/// rule that found it, rendered the way compilers do.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::{Diagnostic, Severity};
+///
+/// let error = goof::assert_eq(&4, &2).unwrap_err();
+/// let diagnostic = Diagnostic::new(Severity::Warning, error).with_rule("tab-width");
+/// assert_eq!(diagnostic.to_string(), "warning[tab-width]: Expected 4, but got 2");
+/// assert!(diagnostic.severity() < Severity::Error);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Diagnostic<E> {
+    severity: Severity,
+    error: E,
+    rule: Option<&'static str>,
+}
+
+impl<E> Diagnostic<E> {
+    pub const fn new(severity: Severity, error: E) -> Self {
+        Self {
+            severity,
+            error,
+            rule: None,
+        }
+    }
+
+    /// Name the rule that found the problem, e.g. `"unused-key"`.
+    pub fn with_rule(self, rule: &'static str) -> Self {
+        Self {
+            rule: Some(rule),
+            ..self
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn rule(&self) -> Option<&'static str> {
+        self.rule
+    }
+
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+}
+
+impl<E: Debug> Debug for Diagnostic<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Diagnostic")
+            .field("severity", &self.severity)
+            .field("error", &self.error)
+            .field("rule", &self.rule)
+            .finish()
+    }
+}
+
+impl<E: Display> Display for Diagnostic<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.rule {
+            Some(rule) => write!(f, "{}[{}]: ", self.severity, rule)?,
+            None => write!(f, "{}: ", self.severity)?,
+        }
+        Display::fmt(&self.error, f)
+    }
+}
+
+impl<E: Error> Error for Diagnostic<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+}
+
+impl<E: Fields> Fields for Diagnostic<E> {
+    fn kind(&self) -> &'static str {
+        self.error.kind()
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        self.error.for_each_field(visit);
+        visit("severity", &self.severity);
+        if let Some(rule) = &self.rule {
+            visit("rule", rule);
+        }
+    }
+
+    fn help(&self) -> Option<String> {
+        self.error.help()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn as_explain(&self) -> Option<&dyn crate::Explain> {
+        self.error.as_explain()
+    }
+}
+
+impl<E: ErrorCode> ErrorCode for Diagnostic<E> {
+    fn code(&self) -> u32 {
+        self.error.code()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Diagnostic, Severity};
+    use crate::Fields;
+    use alloc::vec::Vec;
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn fields_include_severity() {
+        let diagnostic = Diagnostic::new(Severity::Hint, crate::Cancelled::new()).with_rule("x");
+        let mut names = Vec::new();
+        diagnostic.for_each_field(&mut |name, _| names.push(name));
+        assert_eq!(names, ["severity", "rule"]);
+        assert_eq!(diagnostic.to_string(), "hint[x]: Cancelled");
+    }
+}
@@ -0,0 +1,217 @@
+//! Source locations, for errors found while parsing text.
+
+use crate::Fields;
+use alloc::string::String;
+use core::error::Error;
+use core::fmt::{Debug, Display};
+
+/// A range of bytes in some source text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Span {
+    /// Byte offset of the start.
+    pub offset: usize,
+    /// Length in bytes.
+    pub len: usize,
+}
+
+impl Span {
+    pub const fn new(offset: usize, len: usize) -> Self {
+        Self { offset, len }
+    }
+
+    /// The line and column at which the span starts in `source`.
+    ///
+    /// An offset past the end of `source` is clamped to its end, and
+    /// one in the middle of a character counts as that character.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use goof::{Position, Span};
+    ///
+    /// let source = "[package]\nname = 12\n";
+    /// assert_eq!(Span::new(17, 2).position_in(source), Position { line: 2, column: 8 });
+    /// ```
+    pub fn position_in(&self, source: &str) -> Position {
+        let mut position = Position { line: 1, column: 1 };
+        for (index, c) in source.char_indices() {
+            if index >= self.offset {
+                break;
+            }
+            if c == '\n' {
+                position.line += 1;
+                position.column = 1;
+            } else if index + c.len_utf8() <= self.offset {
+                position.column += 1;
+            }
+        }
+        position
+    }
+}
+
+/// A human-friendly location in source text.  Both numbers start at
+/// one, and columns count characters, not bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// An error `E` at a [`Span`] of some source text.
+///
+/// The wrapper adds the location to `Display` and to the [`Fields`],
+/// and is otherwise transparent.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::{Span, Spanned};
+///
+/// let source = "[package]\nname = 12\n";
+/// let error = Spanned::new(goof::assert_eq(&"\"goof\"", &"12").unwrap_err(), Span::new(17, 2))
+///     .located_in(source);
+/// assert_eq!(error.to_string(), "Expected \"goof\", but got 12 at line 2, column 8");
+/// ```
+pub struct Spanned<E> {
+    error: E,
+    span: Span,
+    position: Option<Position>,
+}
+
+impl<E> Spanned<E> {
+    /// Put `error` at `span`, without knowing the line and column.
+    pub const fn new(error: E, span: Span) -> Self {
+        Self {
+            error,
+            span,
+            position: None,
+        }
+    }
+
+    /// Work out the line and column of the span in `source`, the text
+    /// that the span refers to.
+    pub fn located_in(mut self, source: &str) -> Self {
+        self.position = Some(self.span.position_in(source));
+        self
+    }
+
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// The line and column, if the error was [`Spanned::located_in`]
+    /// its source.
+    pub fn position(&self) -> Option<Position> {
+        self.position
+    }
+
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+}
+
+impl<E: Clone> Clone for Spanned<E> {
+    fn clone(&self) -> Self {
+        Self {
+            error: self.error.clone(),
+            span: self.span,
+            position: self.position,
+        }
+    }
+}
+
+impl<E: PartialEq> PartialEq for Spanned<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.error == other.error && self.span == other.span && self.position == other.position
+    }
+}
+
+impl<E: Eq> Eq for Spanned<E> {}
+
+impl<E: Debug> Debug for Spanned<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Spanned")
+            .field("error", &self.error)
+            .field("span", &self.span)
+            .field("position", &self.position)
+            .finish()
+    }
+}
+
+impl<E: Display> Display for Spanned<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.error, f)?;
+        if cfg!(feature = "compact") {
+            return write!(f, " offset={} len={}", self.span.offset, self.span.len);
+        }
+        match self.position {
+            Some(position) => write!(f, " at {}", position),
+            None => write!(f, " at offset {}", self.span.offset),
+        }
+    }
+}
+
+impl<E: Error> Error for Spanned<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+}
+
+impl<E: Fields> Fields for Spanned<E> {
+    fn kind(&self) -> &'static str {
+        self.error.kind()
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        self.error.for_each_field(visit);
+        visit("offset", &self.span.offset);
+        visit("len", &self.span.len);
+        if let Some(position) = &self.position {
+            visit("line", &position.line);
+            visit("column", &position.column);
+        }
+    }
+
+    fn help(&self) -> Option<String> {
+        self.error.help()
+    }
+
+    fn severity(&self) -> crate::Severity {
+        self.error.severity()
+    }
+
+    fn as_explain(&self) -> Option<&dyn crate::Explain> {
+        self.error.as_explain()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Position, Span, Spanned};
+
+    #[test]
+    fn positions() {
+        let source = "ab\ncé\nd";
+        let at = |offset| Span::new(offset, 0).position_in(source);
+        assert_eq!(at(0), Position { line: 1, column: 1 });
+        assert_eq!(at(3), Position { line: 2, column: 1 });
+        assert_eq!(at(6), Position { line: 2, column: 3 });
+        assert_eq!(at(100), Position { line: 3, column: 2 });
+    }
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn without_source_shows_offset() {
+        let error = Spanned::new(crate::assert_eq(&',', &']').unwrap_err(), Span::new(7, 1));
+        assert_eq!(error.to_string(), "Expected ,, but got ] at offset 7");
+    }
+}
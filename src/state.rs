@@ -0,0 +1,181 @@
+//! Errors for state machines.
+
+use crate::{code, hook, ErrorCode, Fields};
+use core::fmt::{Debug, Display};
+
+/// Assert that a state machine may go from `from` to `to`, where
+/// `allowed` are the states reachable from `from`.
+///
+/// Returns the new state.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_transition;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum State {
+///     Running,
+///     Paused,
+///     Stopped,
+///     Finished,
+/// }
+///
+/// impl std::fmt::Display for State {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         std::fmt::Debug::fmt(self, f)
+///     }
+/// }
+///
+/// let allowed = [State::Running, State::Stopped];
+/// assert_eq!(
+///     assert_transition(State::Paused, State::Running, &allowed),
+///     Ok(State::Running)
+/// );
+/// assert_eq!(
+///     assert_transition(State::Paused, State::Finished, &allowed)
+///         .unwrap_err()
+///         .to_string(),
+///     "cannot go from Paused to Finished (allowed: Running, Stopped)"
+/// );
+/// ```
+pub fn assert_transition<S: PartialEq>(
+    from: S,
+    to: S,
+    allowed: &[S],
+) -> Result<S, InvalidTransition<'_, S>> {
+    if allowed.contains(&to) {
+        Ok(to)
+    } else {
+        hook::observe(
+            "invalid_transition",
+            code::INVALID_TRANSITION,
+            "assert_transition",
+        );
+        Err(InvalidTransition {
+            from,
+            to,
+            allowed,
+            provenance: "assert_transition",
+        })
+    }
+}
+
+/// A state machine was asked to go to a state it cannot reach from
+/// the one it is in.
+#[derive(Clone, Copy)]
+pub struct InvalidTransition<'a, S> {
+    pub(crate) from: S,
+    pub(crate) to: S,
+    /// Every state reachable from `from`.
+    pub(crate) allowed: &'a [S],
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl<'a, S> InvalidTransition<'a, S> {
+    /// For state machines that already know the transition is invalid.
+    pub fn new(from: S, to: S, allowed: &'a [S]) -> Self {
+        hook::observe(
+            "invalid_transition",
+            code::INVALID_TRANSITION,
+            "InvalidTransition::new",
+        );
+        Self {
+            from,
+            to,
+            allowed,
+            provenance: "InvalidTransition::new",
+        }
+    }
+
+    pub fn from(&self) -> &S {
+        &self.from
+    }
+
+    pub fn to(&self) -> &S {
+        &self.to
+    }
+
+    pub fn allowed(&self) -> &'a [S] {
+        self.allowed
+    }
+
+    /// The name of the function that created this error, e.g.
+    /// `"assert_transition"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl<S: PartialEq> PartialEq for InvalidTransition<'_, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.from == other.from && self.to == other.to && self.allowed == other.allowed
+    }
+}
+
+impl<S: Eq> Eq for InvalidTransition<'_, S> {}
+
+impl<S: Debug> Debug for InvalidTransition<'_, S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("InvalidTransition")
+            .field("from", &self.from)
+            .field("to", &self.to)
+            .field("allowed", &self.allowed)
+            .finish()
+    }
+}
+
+impl<S: Display> Display for InvalidTransition<'_, S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(f, "cannot go from {} to {} ", self.from, self.to)?;
+        if self.allowed.is_empty() {
+            return write!(f, "(no way out of {})", self.from);
+        }
+        f.write_str("(allowed: ")?;
+        for (index, state) in self.allowed.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            Display::fmt(state, f)?;
+        }
+        f.write_str(")")
+    }
+}
+
+impl<S: Debug + Display> core::error::Error for InvalidTransition<'_, S> {}
+
+impl<S: Display> Fields for InvalidTransition<'_, S> {
+    fn kind(&self) -> &'static str {
+        "invalid_transition"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("from", &self.from);
+        visit("to", &self.to);
+        if let Ok(allowed) = crate::join(self.allowed, ", ") {
+            visit("allowed", &allowed);
+        }
+    }
+}
+
+impl<S> ErrorCode for InvalidTransition<'_, S> {
+    fn code(&self) -> u32 {
+        code::INVALID_TRANSITION
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InvalidTransition;
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn terminal_state() {
+        assert_eq!(
+            InvalidTransition::new("finished", "running", &[]).to_string(),
+            "cannot go from finished to running (no way out of finished)"
+        );
+    }
+}
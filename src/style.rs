@@ -0,0 +1,226 @@
+//! Choosing how errors are phrased where they are shown, rather than
+//! where they are created.
+
+use crate::envelope::write_escaped;
+use crate::{render, write_compact, ErrorCode, Fields};
+use core::fmt::{Display, Formatter, Write};
+
+/// A way of turning any error into text.
+///
+/// Libraries return goofs as they are; the application picks a style
+/// once, at the boundary where errors are printed or logged.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::{Compact, JsonLike, Stylable, Terse, Verbose};
+///
+/// let error = goof::assert_eq(&32, &0).unwrap_err();
+/// assert_eq!(error.styled(&Terse).to_string(), "Expected 32, but got 0");
+/// assert_eq!(error.styled(&Compact).to_string(), "#1 expected=32 actual=0");
+/// assert_eq!(
+///     error.styled(&Verbose).to_string(),
+///     "Expected 32, but got 0\n  kind: mismatch (code 1)\n  expected: 32\n  actual: 0\n  help: use 32 instead"
+/// );
+/// assert_eq!(
+///     error.styled(&JsonLike).to_string(),
+///     r#"{"type":"mismatch","code":1,"message":"Expected 32, but got 0","expected":"32","actual":"0"}"#
+/// );
+/// ```
+pub trait MessageStyle {
+    fn fmt_error(&self, f: &mut Formatter<'_>, error: &dyn Stylable) -> core::fmt::Result;
+}
+
+/// Just the message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Terse;
+
+/// The message, followed by the kind, the code, every field and the
+/// help, one per indented line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Verbose;
+
+/// The code and the raw fields, e.g. `#1 expected=32 actual=0`, as
+/// every error renders with the `compact` feature, for logs that are
+/// kept short and read with the codes at hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Compact;
+
+/// A single-line JSON object with the kind, the code, the message and
+/// every field as a string, with the keys of [`crate::ToJson`].  The
+/// `json` feature keeps field types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct JsonLike;
+
+impl MessageStyle for Terse {
+    fn fmt_error(&self, f: &mut Formatter<'_>, error: &dyn Stylable) -> core::fmt::Result {
+        Display::fmt(error, f)
+    }
+}
+
+impl MessageStyle for Verbose {
+    fn fmt_error(&self, f: &mut Formatter<'_>, error: &dyn Stylable) -> core::fmt::Result {
+        Display::fmt(error, f)?;
+        write!(f, "\n  kind: {} (code {})", error.kind(), error.code())?;
+        let mut result = Ok(());
+        error.for_each_field(&mut |name, value| {
+            if result.is_ok() {
+                result = write!(f, "\n  {}: {}", name, value);
+            }
+        });
+        result?;
+        match error.help() {
+            Some(help) => write!(f, "\n  help: {}", help),
+            None => Ok(()),
+        }
+    }
+}
+
+impl MessageStyle for Compact {
+    fn fmt_error(&self, f: &mut Formatter<'_>, error: &dyn Stylable) -> core::fmt::Result {
+        write_compact(f, error)
+    }
+}
+
+impl MessageStyle for JsonLike {
+    fn fmt_error(&self, f: &mut Formatter<'_>, error: &dyn Stylable) -> core::fmt::Result {
+        write_object(f, error, |f, value| write_escaped(f, value))
+    }
+}
+
+/// Write `error` as a flat JSON object with its `type`, `code`,
+/// `message` and every field, each field value through `value`.
+///
+/// A field named like one of the first three keys is written as e.g.
+/// `field_message`, unless it only repeats that key.
+pub(crate) fn write_object(
+    f: &mut Formatter<'_>,
+    error: &(impl Fields + ErrorCode + Display + ?Sized),
+    value: fn(&mut Formatter<'_>, &str) -> core::fmt::Result,
+) -> core::fmt::Result {
+    let kind = error.kind();
+    let code = error.code();
+    let message = render(error, false);
+    f.write_str(r#"{"type":"#)?;
+    write_escaped(f, kind)?;
+    write!(f, r#","code":{},"message":"#, code)?;
+    write_escaped(f, &message)?;
+    let mut result = Ok(());
+    error.for_each_field(&mut |name, field| {
+        if result.is_err() {
+            return;
+        }
+        let field = render(field, false);
+        let prefix = match name {
+            "type" if field == kind => return,
+            "code" if field == render(&code, false) => return,
+            "message" if field == message => return,
+            "type" | "code" | "message" => "field_",
+            _ => "",
+        };
+        result = f
+            .write_char(',')
+            .and_then(|_| write_escaped(f, &render(&format_args!("{}{}", prefix, name), false)))
+            .and_then(|_| f.write_char(':'))
+            .and_then(|_| value(f, &field));
+    });
+    result?;
+    f.write_char('}')
+}
+
+/// An error that any [`MessageStyle`] can render.
+pub trait Stylable: Fields + ErrorCode + Display {
+    /// Render with `style` instead of the plain `Display`.
+    fn styled<'a>(&'a self, style: &'a dyn MessageStyle) -> Styled<'a, Self>
+    where
+        Self: Sized,
+    {
+        Styled { error: self, style }
+    }
+}
+
+impl<E: Fields + ErrorCode + Display + ?Sized> Stylable for E {}
+
+/// `Display` adapter returned by [`Stylable::styled`].
+pub struct Styled<'a, E> {
+    error: &'a E,
+    style: &'a dyn MessageStyle,
+}
+
+impl<E: Stylable> Display for Styled<'_, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.style.fmt_error(f, self.error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Compact, JsonLike, Stylable, Verbose};
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn help_and_escaping() {
+        let error = crate::assert_known_enum(&["a\"b"], "c").unwrap_err();
+        assert_eq!(
+            error.styled(&Verbose).to_string(),
+            "The value c is not known, because it's not one of [a\"b]\n  \
+             kind: unknown (code 3)\n  value: c\n  knowns: a\"b\n  help: use one of [a\"b]"
+        );
+        assert!(error
+            .styled(&JsonLike)
+            .to_string()
+            .ends_with(r#""value":"c","knowns":"a\"b"}"#));
+    }
+
+    #[test]
+    fn compact_is_the_code_and_fields() {
+        let error = crate::assert_in(&7, &(1..5)).unwrap_err();
+        assert_eq!(
+            error.styled(&Compact).to_string(),
+            "#2 start=1 end=5 value=7 interval=left_open"
+        );
+    }
+
+    /// The keys of a JSON object in order, repeats included.
+    struct Keys(Vec<String>);
+
+    impl<'de> serde::Deserialize<'de> for Keys {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct Visitor;
+
+            impl<'de> serde::de::Visitor<'de> for Visitor {
+                type Value = Keys;
+
+                fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    f.write_str("an object")
+                }
+
+                fn visit_map<A: serde::de::MapAccess<'de>>(
+                    self,
+                    mut map: A,
+                ) -> Result<Keys, A::Error> {
+                    let mut keys = Vec::new();
+                    while let Some((key, _)) = map.next_entry::<String, serde_json::Value>()? {
+                        keys.push(key);
+                    }
+                    Ok(Keys(keys))
+                }
+            }
+
+            deserializer.deserialize_map(Visitor)
+        }
+    }
+
+    #[test]
+    fn json_like_keys_are_unique() {
+        let json = crate::Goof::from("boom").styled(&JsonLike).to_string();
+        let Keys(keys) = serde_json::from_str(&json).unwrap();
+        assert_eq!(keys[..3], ["type", "code", "message"]);
+        let mut unique = keys.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), keys.len(), "{}", json);
+    }
+}
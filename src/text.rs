@@ -0,0 +1,138 @@
+//! Errors for decoding text.
+
+use crate::{code, hook, ErrorCode, Fields};
+use alloc::string::FromUtf8Error;
+use core::fmt::{Debug, Display};
+use core::str::Utf8Error;
+
+/// Assert that `bytes` are UTF-8, returning them as a `str`.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_utf8;
+///
+/// assert_eq!(assert_utf8(b"caf\xc3\xa9"), Ok("café"));
+/// let error = assert_utf8(b"caf\xe9!").unwrap_err();
+/// assert_eq!(error.offset(), 3);
+/// assert_eq!(error.to_string(), "Invalid UTF-8 at byte 3");
+/// ```
+pub fn assert_utf8(bytes: &[u8]) -> Result<&str, BadText> {
+    core::str::from_utf8(bytes).map_err(|error| BadText::observed(error, "assert_utf8"))
+}
+
+/// Bytes that had to be UTF-8 were not.
+///
+/// Converts from the errors of `str::from_utf8` and
+/// `String::from_utf8`, so `?` works on them.
+#[derive(Clone, Copy)]
+pub struct BadText {
+    pub(crate) error: Utf8Error,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl BadText {
+    fn observed(error: Utf8Error, provenance: &'static str) -> Self {
+        hook::observe("bad_text", code::BAD_TEXT, provenance);
+        Self { error, provenance }
+    }
+
+    /// The offset of the first byte that is not valid UTF-8; everything
+    /// before it is.
+    pub fn offset(&self) -> usize {
+        self.error.valid_up_to()
+    }
+
+    /// How many bytes are invalid, or `None` if the input ended in the
+    /// middle of a character.
+    pub fn invalid_len(&self) -> Option<usize> {
+        self.error.error_len()
+    }
+
+    pub fn utf8_error(&self) -> Utf8Error {
+        self.error
+    }
+
+    /// The name of the function that created this error, e.g.
+    /// `"assert_utf8"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl From<Utf8Error> for BadText {
+    fn from(error: Utf8Error) -> Self {
+        Self::observed(error, "From<Utf8Error>")
+    }
+}
+
+impl From<FromUtf8Error> for BadText {
+    fn from(error: FromUtf8Error) -> Self {
+        Self::observed(error.utf8_error(), "From<FromUtf8Error>")
+    }
+}
+
+impl PartialEq for BadText {
+    fn eq(&self, other: &Self) -> bool {
+        self.error == other.error
+    }
+}
+
+impl Eq for BadText {}
+
+impl Debug for BadText {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BadText")
+            .field("offset", &self.offset())
+            .field("invalid_len", &self.invalid_len())
+            .finish()
+    }
+}
+
+impl Display for BadText {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        match self.invalid_len() {
+            Some(_) => write!(f, "Invalid UTF-8 at byte {}", self.offset()),
+            None => write!(f, "Incomplete UTF-8 character at byte {}", self.offset()),
+        }
+    }
+}
+
+impl core::error::Error for BadText {}
+
+impl Fields for BadText {
+    fn kind(&self) -> &'static str {
+        "bad_text"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("offset", &self.offset());
+        if let Some(invalid_len) = self.invalid_len() {
+            visit("invalid_len", &invalid_len);
+        }
+    }
+}
+
+impl ErrorCode for BadText {
+    fn code(&self) -> u32 {
+        code::BAD_TEXT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BadText;
+    use alloc::string::String;
+    use alloc::vec;
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn conversions() {
+        let error = BadText::from(String::from_utf8(vec![b'a', 0xc3]).unwrap_err());
+        assert_eq!(error.invalid_len(), None);
+        assert_eq!(error.to_string(), "Incomplete UTF-8 character at byte 1");
+        assert_eq!(error.provenance(), "From<FromUtf8Error>");
+    }
+}
@@ -0,0 +1,148 @@
+//! Errors about time running out.
+
+use crate::{code, hook, ErrorCode, Fields};
+use core::fmt::{Debug, Display};
+use core::time::Duration;
+
+/// Something took longer than it was allowed to.
+#[derive(Clone, Copy)]
+pub struct Timeout {
+    pub(crate) elapsed: Duration,
+    pub(crate) limit: Duration,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl Timeout {
+    /// For code that measures time itself, e.g. without `std`.
+    pub fn new(elapsed: Duration, limit: Duration) -> Self {
+        hook::observe("timeout", code::TIMEOUT, "Timeout::new");
+        Self {
+            elapsed,
+            limit,
+            provenance: "Timeout::new",
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn limit(&self) -> Duration {
+        self.limit
+    }
+
+    /// The name of the function that created this error, e.g.
+    /// `"assert_within"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl PartialEq for Timeout {
+    fn eq(&self, other: &Self) -> bool {
+        self.elapsed == other.elapsed && self.limit == other.limit
+    }
+}
+
+impl Eq for Timeout {}
+
+impl Debug for Timeout {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Timeout")
+            .field("elapsed", &self.elapsed)
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
+
+impl Display for Timeout {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        write!(
+            f,
+            "Took {:?}, which is over the limit of {:?}",
+            self.elapsed, self.limit
+        )
+    }
+}
+
+impl core::error::Error for Timeout {}
+
+/// `Display` of a `Duration`, which only has a `Debug`.
+pub(crate) struct Human(pub(crate) Duration);
+
+impl Display for Human {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl Fields for Timeout {
+    fn kind(&self) -> &'static str {
+        "timeout"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        visit("elapsed", &Human(self.elapsed));
+        visit("limit", &Human(self.limit));
+    }
+}
+
+impl ErrorCode for Timeout {
+    fn code(&self) -> u32 {
+        code::TIMEOUT
+    }
+}
+
+/// Run `f` and fail if it took longer than `limit`.
+///
+/// `f` always runs to completion; this only reports the overrun
+/// afterwards, which is what test harnesses and watchdogs want.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use std::time::Duration;
+///
+/// assert_eq!(goof::assert_within(Duration::from_secs(60), || 42), Ok(42));
+///
+/// let error = goof::assert_within(Duration::ZERO, || {
+///     std::thread::sleep(Duration::from_millis(1))
+/// })
+/// .unwrap_err();
+/// assert!(error.elapsed() >= Duration::from_millis(1));
+/// assert!(error.to_string().ends_with("over the limit of 0ns"));
+/// ```
+#[cfg(feature = "std")]
+pub fn assert_within<T>(limit: Duration, f: impl FnOnce() -> T) -> Result<T, Timeout> {
+    let start = std::time::Instant::now();
+    let value = f();
+    let elapsed = start.elapsed();
+    if elapsed <= limit {
+        Ok(value)
+    } else {
+        hook::observe("timeout", code::TIMEOUT, "assert_within");
+        Err(Timeout {
+            elapsed,
+            limit,
+            provenance: "assert_within",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Timeout;
+    use core::time::Duration;
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn durations_are_readable() {
+        let error = Timeout::new(Duration::from_millis(1500), Duration::from_secs(1));
+        assert_eq!(
+            error.to_string(),
+            "Took 1.5s, which is over the limit of 1s"
+        );
+    }
+}
@@ -0,0 +1,434 @@
+//! Errors for lexers and parsers.
+
+use crate::{code, hook, render, ErrorCode, Fields, Truncation};
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display};
+
+/// Assert that `found` is one of the tokens acceptable at this point
+/// of the input.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::assert_one_of;
+///
+/// assert_eq!(assert_one_of(&[',', '}'], '}'), Ok('}'));
+/// assert_eq!(
+///     assert_one_of(&[',', '}'], ']').unwrap_err().to_string(),
+///     "expected `,` or `}`, found `]`"
+/// );
+/// ```
+pub fn assert_one_of<T: PartialEq>(expected: &[T], found: T) -> Result<T, ExpectedOneOf<'_, T>> {
+    if expected.contains(&found) {
+        Ok(found)
+    } else {
+        hook::observe("expected_one_of", code::EXPECTED_ONE_OF, "assert_one_of");
+        Err(ExpectedOneOf {
+            expected,
+            found,
+            provenance: "assert_one_of",
+        })
+    }
+}
+
+/// A token that is not acceptable at this point of the input.
+///
+/// Unlike [`crate::Unknown`], which is about a value outside of an
+/// enumeration, this is about what the grammar allows next, and it is
+/// phrased like a compiler diagnostic.
+#[derive(Clone, Copy)]
+pub struct ExpectedOneOf<'a, T> {
+    /// Every token that would have been accepted.
+    pub(crate) expected: &'a [T],
+    pub(crate) found: T,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl<'a, T> ExpectedOneOf<'a, T> {
+    /// Report `found` where one of `expected` had to be, for parsers
+    /// that already know that it is not.
+    pub fn new(expected: &'a [T], found: T) -> Self {
+        hook::observe(
+            "expected_one_of",
+            code::EXPECTED_ONE_OF,
+            "ExpectedOneOf::new",
+        );
+        Self {
+            expected,
+            found,
+            provenance: "ExpectedOneOf::new",
+        }
+    }
+
+    pub fn expected(&self) -> &'a [T] {
+        self.expected
+    }
+
+    pub fn found(&self) -> &T {
+        &self.found
+    }
+
+    /// The name of the function that created this error, e.g.
+    /// `"assert_one_of"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl<T: PartialEq> PartialEq for ExpectedOneOf<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.expected == other.expected && self.found == other.found
+    }
+}
+
+impl<T: Eq> Eq for ExpectedOneOf<'_, T> {}
+
+impl<T: Debug> Debug for ExpectedOneOf<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ExpectedOneOf")
+            .field("expected", &self.expected)
+            .field("found", &self.found)
+            .finish()
+    }
+}
+
+impl<T: Display> Display for ExpectedOneOf<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        f.write_str("expected ")?;
+        match self.expected {
+            [] => f.write_str("nothing")?,
+            [only] => write!(f, "`{}`", only)?,
+            [init @ .., last] => {
+                for (index, token) in init.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "`{}`", token)?;
+                }
+                write!(f, " or `{}`", last)?;
+            }
+        }
+        write!(f, ", found `{}`", self.found)
+    }
+}
+
+impl<T: Debug + Display> core::error::Error for ExpectedOneOf<'_, T> {}
+
+impl<T: Display> Fields for ExpectedOneOf<'_, T> {
+    fn kind(&self) -> &'static str {
+        "expected_one_of"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        if let Ok(expected) = crate::join(self.expected, ", ") {
+            visit("expected", &expected);
+        }
+        visit("found", &self.found);
+    }
+
+    fn help(&self) -> Option<String> {
+        match self.expected {
+            [only] => Some(format!("insert `{}`", only)),
+            _ => None,
+        }
+    }
+}
+
+impl<T> ErrorCode for ExpectedOneOf<'_, T> {
+    fn code(&self) -> u32 {
+        code::EXPECTED_ONE_OF
+    }
+}
+
+/// Input that a parser could not make sense of, for parser combinators
+/// that report their errors as goofs instead of their own type.
+///
+/// It keeps the input that was left where parsing failed, and what was
+/// expected there, innermost first: the failed parser, then the
+/// contexts it was in.  With the `nom` feature it is a
+/// `nom::error::ParseError`, and with the `winnow` feature a
+/// `winnow::error::ParserError`.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::ParseGoof;
+///
+/// let error = ParseGoof::new("]}", "digit").context("array");
+/// assert_eq!(error.to_string(), "expected digit in array, found `]}`");
+/// assert_eq!(ParseGoof::new("", "`}`").to_string(), "expected `}`, found end of input");
+/// ```
+#[derive(Clone)]
+pub struct ParseGoof<I> {
+    /// What was left of the input.
+    pub(crate) input: I,
+    pub(crate) expected: Vec<Cow<'static, str>>,
+    /// The name of the function that created this error
+    pub(crate) provenance: &'static str,
+}
+
+impl<I> ParseGoof<I> {
+    /// Report that `expected` had to be at the start of `input`.
+    pub fn new(input: I, expected: impl Into<Cow<'static, str>>) -> Self {
+        Self::from_parser(input, vec![expected.into()], "ParseGoof::new")
+    }
+
+    pub(crate) fn from_parser(
+        input: I,
+        expected: Vec<Cow<'static, str>>,
+        provenance: &'static str,
+    ) -> Self {
+        hook::observe("parse", code::PARSE, provenance);
+        Self {
+            input,
+            expected,
+            provenance,
+        }
+    }
+
+    /// Note that the parser that failed was parsing `context`, e.g. an
+    /// `"array"`.
+    pub fn context(mut self, context: impl Into<Cow<'static, str>>) -> Self {
+        self.expected.push(context.into());
+        self
+    }
+
+    pub fn input(&self) -> &I {
+        &self.input
+    }
+
+    /// What was expected, then the contexts it was expected in.
+    pub fn expected(&self) -> &[Cow<'static, str>] {
+        &self.expected
+    }
+
+    /// The name of the function that created this error, e.g.
+    /// `"ParseGoof::new"`.
+    pub fn provenance(&self) -> &'static str {
+        self.provenance
+    }
+}
+
+impl<I: PartialEq> PartialEq for ParseGoof<I> {
+    fn eq(&self, other: &Self) -> bool {
+        self.input == other.input && self.expected == other.expected
+    }
+}
+
+impl<I: Eq> Eq for ParseGoof<I> {}
+
+impl<I: Debug> Debug for ParseGoof<I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ParseGoof")
+            .field("input", &self.input)
+            .field("expected", &self.expected)
+            .finish()
+    }
+}
+
+impl<I: Display> Display for ParseGoof<I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        compact!(self, f);
+        match self.expected.split_first() {
+            None => f.write_str("unexpected input")?,
+            Some((expected, contexts)) => {
+                write!(f, "expected {}", expected)?;
+                for context in contexts {
+                    write!(f, " in {}", context)?;
+                }
+            }
+        }
+        if render(&self.input, false).is_empty() {
+            f.write_str(", found end of input")
+        } else {
+            f.write_str(", found `")?;
+            Truncation::current().write_value(f, &self.input)?;
+            f.write_str("`")
+        }
+    }
+}
+
+impl<I: Debug + Display> core::error::Error for ParseGoof<I> {}
+
+impl<I: Display> Fields for ParseGoof<I> {
+    fn kind(&self) -> &'static str {
+        "parse"
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        if !self.expected.is_empty() {
+            if let Ok(expected) = crate::join(&self.expected, " in ") {
+                visit("expected", &expected);
+            }
+        }
+        visit("found", &self.input);
+    }
+}
+
+impl<I> ErrorCode for ParseGoof<I> {
+    fn code(&self) -> u32 {
+        code::PARSE
+    }
+}
+
+#[cfg(feature = "nom")]
+mod nom {
+    use super::ParseGoof;
+    use ::nom::error::{ContextError, ErrorKind, FromExternalError, ParseError};
+    use alloc::borrow::Cow;
+    use alloc::format;
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    impl<I> ParseError<I> for ParseGoof<I> {
+        fn from_error_kind(input: I, kind: ErrorKind) -> Self {
+            let expected = kind.description().to_string();
+            Self::from_parser(input, vec![Cow::Owned(expected)], "nom")
+        }
+
+        /// Keeps `other` as it is, since the kinds of the parsers
+        /// around the one that failed, e.g. `Many1`, say nothing about
+        /// what was expected.  Name them with `nom::error::context`.
+        fn append(_: I, _: ErrorKind, other: Self) -> Self {
+            other
+        }
+
+        fn from_char(input: I, expected: char) -> Self {
+            Self::from_parser(input, vec![Cow::Owned(format!("`{}`", expected))], "nom")
+        }
+    }
+
+    impl<I> ContextError<I> for ParseGoof<I> {
+        fn add_context(_: I, context: &'static str, other: Self) -> Self {
+            other.context(context)
+        }
+    }
+
+    impl<I, E> FromExternalError<I, E> for ParseGoof<I> {
+        fn from_external_error(input: I, kind: ErrorKind, _: E) -> Self {
+            Self::from_error_kind(input, kind)
+        }
+    }
+}
+
+#[cfg(feature = "winnow")]
+mod winnow {
+    use super::ParseGoof;
+    use ::winnow::error::{AddContext, FromExternalError, ParserError, StrContext};
+    use ::winnow::stream::Stream;
+    use alloc::borrow::Cow;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+
+    /// Nothing is expected until a context says what, e.g. with
+    /// `Parser::context(StrContext::Expected(...))`.
+    impl<I: Stream + Clone> ParserError<I> for ParseGoof<I> {
+        type Inner = Self;
+
+        fn from_input(input: &I) -> Self {
+            Self::from_parser(input.clone(), Vec::new(), "winnow")
+        }
+
+        fn into_inner(self) -> Result<Self::Inner, Self> {
+            Ok(self)
+        }
+    }
+
+    impl<I: Stream> AddContext<I, &'static str> for ParseGoof<I> {
+        fn add_context(self, _: &I, _: &I::Checkpoint, context: &'static str) -> Self {
+            self.context(context)
+        }
+    }
+
+    impl<I: Stream> AddContext<I, StrContext> for ParseGoof<I> {
+        fn add_context(self, _: &I, _: &I::Checkpoint, context: StrContext) -> Self {
+            match context {
+                StrContext::Label(label) => self.context(label),
+                StrContext::Expected(expected) => self.context(Cow::Owned(expected.to_string())),
+                _ => self,
+            }
+        }
+    }
+
+    impl<I: Stream + Clone, E> FromExternalError<I, E> for ParseGoof<I> {
+        fn from_external_error(input: &I, _: E) -> Self {
+            Self::from_input(input)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExpectedOneOf, ParseGoof};
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn lists_alternatives() {
+        let render = |expected: &[&str]| ExpectedOneOf::new(expected, "]").to_string();
+        assert_eq!(render(&[]), "expected nothing, found `]`");
+        assert_eq!(render(&[")"]), "expected `)`, found `]`");
+        assert_eq!(
+            render(&["a", "b", "c"]),
+            "expected `a`, `b` or `c`, found `]`"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn expected_in_context() {
+        let error = ParseGoof::new("]", "`,`").context("list").context("call");
+        assert_eq!(error.to_string(), "expected `,` in list in call, found `]`");
+        assert_eq!(error.expected(), ["`,`", "list", "call"]);
+        let error = ParseGoof::from_parser("", Vec::new(), "fuzz_check");
+        assert_eq!(error.to_string(), "unexpected input, found end of input");
+    }
+
+    #[cfg(feature = "nom")]
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn nom_parse_error() {
+        use nom::character::complete::{char, digit1};
+        use nom::error::context;
+        use nom::sequence::delimited;
+        use nom::Parser;
+
+        let mut list = context(
+            "list",
+            delimited(char::<_, ParseGoof<&str>>('['), digit1, char(']')),
+        );
+        assert_eq!(list.parse("[12]"), Ok(("", "12")));
+        let nom::Err::Error(error) = list.parse("[12}").unwrap_err() else {
+            unreachable!()
+        };
+        assert_eq!(error.input(), &"}");
+        assert_eq!(error.to_string(), "expected `]` in list, found `}`");
+        let nom::Err::Error(error) = list.parse("[x]").unwrap_err() else {
+            unreachable!()
+        };
+        assert_eq!(error.to_string(), "expected Digit in list, found `x]`");
+    }
+
+    #[cfg(feature = "winnow")]
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn winnow_parser_error() {
+        use winnow::error::{StrContext, StrContextValue};
+        use winnow::token::take_while;
+        use winnow::Parser;
+
+        let mut list = ('[', take_while(1.., |c: char| c.is_ascii_digit()), ']')
+            .take()
+            .context(StrContext::Expected(StrContextValue::CharLiteral(']')))
+            .context(StrContext::Label("list"));
+        let mut input = "[12}";
+        let error: ParseGoof<&str> = list.parse_next(&mut input).unwrap_err();
+        assert_eq!(error.to_string(), "expected `]` in list, found `}`");
+        assert_eq!(error.provenance(), "winnow");
+    }
+}
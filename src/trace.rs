@@ -0,0 +1,160 @@
+//! `tracing` events for errors, so that subscribers index their payload
+//! instead of parsing their message.
+//!
+//! `tracing` needs the names of the fields of an event up front, so the
+//! common payload fields below are recorded as fields of their own, and
+//! any other ones together as `fields`, in `name=value` form:
+//!
+//! | field      | value                                          |
+//! |------------|------------------------------------------------|
+//! | `kind`     | [`Fields::kind`]                               |
+//! | `code`     | [`ErrorCode::code`]                            |
+//! | `expected`, `actual`, `start`, `end`, `value`, `interval` | the payload field of that name, if any |
+//! | `fields`   | the rest of the payload, if any                |
+//! | `caller`   | where [`TraceGoofExt::traced`] was called      |
+//! | `message`  | the `Display` of the error                     |
+
+use crate::{render, ErrorCode, Fields};
+use alloc::string::String;
+use core::fmt::{Display, Write};
+use core::panic::Location;
+
+/// The payload fields recorded as fields of their own.
+const KNOWN: [&str; 6] = ["expected", "actual", "start", "end", "value", "interval"];
+
+/// Emits the error of a `Result` as a `tracing::error!` event.
+///
+/// # Examples
+/// ```rust
+/// use goof::TraceGoofExt;
+///
+/// // kind="outside" code=2 start=1 end=5 value=7 interval="left_open"
+/// let port = goof::assert_in(&7, &(1..5)).traced();
+/// assert!(port.is_err());
+/// ```
+pub trait TraceGoofExt<T> {
+    /// Emit the error, if there is one, and return `self` unchanged.
+    #[track_caller]
+    fn traced(self) -> Self;
+}
+
+impl<T, E: Fields + ErrorCode + Display> TraceGoofExt<T> for Result<T, E> {
+    #[track_caller]
+    fn traced(self) -> Self {
+        if let Err(error) = &self {
+            trace(error, Location::caller());
+        }
+        self
+    }
+}
+
+fn trace<E: Fields + ErrorCode + Display + ?Sized>(error: &E, caller: &Location<'_>) {
+    let mut known: [Option<String>; KNOWN.len()] = Default::default();
+    let mut rest = String::new();
+    error.for_each_field(&mut |name, value| {
+        let value = render(value, false);
+        match KNOWN.iter().position(|known| *known == name) {
+            Some(at) => known[at] = Some(value),
+            None => {
+                if !rest.is_empty() {
+                    rest.push(' ');
+                }
+                // Quote the values that would not read back as one.
+                if value.is_empty() || value.contains(|c: char| c.is_whitespace() || c == '"') {
+                    let _ = write!(rest, "{}={:?}", name, value);
+                } else {
+                    let _ = write!(rest, "{}={}", name, value);
+                }
+            }
+        }
+    });
+    let [expected, actual, start, end, value, interval] = known;
+    ::tracing::error!(
+        kind = error.kind(),
+        code = error.code(),
+        expected,
+        actual,
+        start,
+        end,
+        value,
+        interval,
+        fields = (!rest.is_empty()).then_some(rest.as_str()),
+        caller = %caller,
+        "{}",
+        error
+    );
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::TraceGoofExt;
+    use std::collections::BTreeMap;
+    use std::fmt::Debug;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Keeps the fields of the last event, as their `Debug`.
+    #[derive(Clone, Default)]
+    struct Last(Arc<Mutex<BTreeMap<&'static str, String>>>);
+
+    impl Visit for Last {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            let value = format!("{:?}", value);
+            self.0.lock().unwrap().insert(field.name(), value);
+        }
+    }
+
+    impl Subscriber for Last {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            self.0.lock().unwrap().clear();
+            event.record(&mut self.clone());
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn structured_fields() {
+        let last = Last::default();
+        tracing::subscriber::with_default(last.clone(), || {
+            assert!(crate::assert_in(&7, &(1..5)).traced().is_err());
+            let event = last.0.lock().unwrap().clone();
+            assert_eq!(event["kind"], r#""outside""#);
+            assert_eq!(event["code"], "2");
+            assert_eq!(event["start"], r#""1""#);
+            assert_eq!(event["value"], r#""7""#);
+            assert_eq!(event["interval"], r#""left_open""#);
+            assert!(!event.contains_key("expected"));
+            assert!(!event.contains_key("fields"));
+            assert!(event["caller"].starts_with("src/trace.rs:"));
+            assert_eq!(
+                event["message"],
+                "Value 7 is outside the allowed range (1, 5] (must be at most 5)"
+            );
+
+            let _ = crate::assert_capacity(3_usize, 2).traced();
+            let event = last.0.lock().unwrap().clone();
+            assert!(event["fields"].contains("limit=2"), "{:?}", event);
+
+            assert_eq!(crate::assert_eq(&1, &1).traced(), Ok(1));
+            assert_eq!(last.0.lock().unwrap()["kind"], r#""exceeded""#);
+        });
+    }
+}
@@ -0,0 +1,259 @@
+//! Telling errors that may go away on their own from those that will
+//! not.
+
+use crate::arith::{ArithGoof, DivisionByZero, Overflow, Underflow};
+use crate::config::ConfigGoof;
+use crate::http::WithStatus;
+use crate::retry::RetriesExhausted;
+use crate::schema::{DeserGoof, DuplicateKey, InvalidData, MissingField, UnknownField};
+use crate::{
+    At, Caused, Duplicate, ErrorCode, ExpectedOneOf, FailedPredicate, Fields, LengthMismatch,
+    LengthOutside, MagicMismatch, Mismatch, NotDisjoint, NotMultipleOf, NotPositive, NotSubset,
+    Outside, PatternMismatch, SliceMismatch, Spanned, Timeout, Unknown, UnsortedAt, Zero,
+};
+use alloc::string::String;
+use core::error::Error;
+use core::fmt::{Debug, Display};
+
+/// Whether trying again could succeed.
+///
+/// Validation errors are permanent: the same input fails the same
+/// way.  Timeouts and some I/O errors are transient.
+///
+/// # Examples
+/// ```rust
+/// use goof::Transience;
+///
+/// let error = goof::assert_eq(&1, &2).unwrap_err();
+/// assert!(!error.is_transient());
+/// assert!(error.transient(true).is_transient());
+/// ```
+pub trait Transience {
+    /// Whether the same operation could succeed if tried again, as
+    /// [`crate::retry::retry_transient`] asks.  `false` unless the
+    /// kind of error says otherwise.
+    fn is_transient(&self) -> bool {
+        false
+    }
+
+    /// Override whether this error is transient.
+    fn transient(self, transient: bool) -> WithTransience<Self>
+    where
+        Self: Sized,
+    {
+        WithTransience {
+            error: self,
+            transient,
+        }
+    }
+}
+
+impl<T: Copy + Eq> Transience for Mismatch<T> {}
+impl<T: PartialOrd + Copy> Transience for Outside<T> {}
+impl<T: Eq> Transience for Unknown<'_, T> {}
+impl Transience for PatternMismatch {}
+impl<T> Transience for FailedPredicate<T> {}
+impl Transience for LengthMismatch {}
+impl Transience for LengthOutside {}
+impl<T> Transience for UnsortedAt<T> {}
+impl<T> Transience for Duplicate<T> {}
+impl<T> Transience for NotSubset<T> {}
+impl<T> Transience for NotDisjoint<T> {}
+impl<T> Transience for SliceMismatch<T> {}
+impl Transience for MagicMismatch {}
+impl<T> Transience for ExpectedOneOf<'_, T> {}
+impl<I> Transience for crate::ParseGoof<I> {}
+impl Transience for MissingField {}
+impl Transience for UnknownField {}
+impl Transience for DuplicateKey {}
+impl Transience for InvalidData {}
+impl Transience for DeserGoof {}
+impl Transience for ConfigGoof {}
+impl<T> Transience for Overflow<T> {}
+impl<T> Transience for Underflow<T> {}
+impl<T> Transience for DivisionByZero<T> {}
+impl<T> Transience for ArithGoof<T> {}
+impl<T> Transience for NotPositive<T> {}
+impl Transience for Zero {}
+impl<T> Transience for NotMultipleOf<T> {}
+impl<S> Transience for crate::InvalidTransition<'_, S> {}
+impl<P> Transience for crate::InsufficientPermission<P> {}
+impl<T> Transience for crate::Exceeded<T> {}
+impl Transience for crate::ArityMismatch {}
+impl Transience for crate::Misaligned {}
+impl Transience for crate::EndiannessMismatch {}
+impl<T> Transience for crate::UnknownBits<T> {}
+impl Transience for crate::TooShort {}
+impl Transience for crate::BadText {}
+impl Transience for crate::Goof<'_> {}
+impl<const N: usize> Transience for crate::InlineGoof<N> {}
+impl<E> Transience for crate::MultiError<E> {}
+impl<T> Transience for crate::EmptyRange<T> {}
+impl<T> Transience for crate::NotComparable<T> {}
+impl<T> Transience for crate::TooLarge<T> {}
+impl<T> Transience for crate::TooSmall<T> {}
+impl<T: PartialOrd + Copy> Transience for crate::RangeGoof<T> {}
+impl<T: PartialOrd + Copy> Transience for crate::BoundGoof<T> {}
+
+impl Transience for Timeout {
+    fn is_transient(&self) -> bool {
+        true
+    }
+}
+
+impl Transience for crate::RateLimited {
+    fn is_transient(&self) -> bool {
+        true
+    }
+}
+
+impl Transience for crate::Cancelled {}
+
+// Already retried as often as the policy allowed.
+impl<E> Transience for RetriesExhausted<E> {}
+
+#[cfg(feature = "semver")]
+impl Transience for crate::semver::VersionOutside {}
+
+#[cfg(feature = "std")]
+impl Transience for crate::Panicked {}
+
+#[cfg(feature = "std")]
+impl Transience for crate::env::MissingEnv {}
+
+#[cfg(feature = "std")]
+impl<E> Transience for crate::env::InvalidEnv<E> {}
+
+#[cfg(feature = "std")]
+impl<E> Transience for crate::env::EnvGoof<E> {}
+
+#[cfg(feature = "std")]
+impl Transience for crate::IoGoof {
+    fn is_transient(&self) -> bool {
+        use std::io::ErrorKind;
+
+        matches!(
+            self.io().kind(),
+            ErrorKind::Interrupted
+                | ErrorKind::WouldBlock
+                | ErrorKind::TimedOut
+                | ErrorKind::ConnectionReset
+                | ErrorKind::ConnectionAborted
+                | ErrorKind::ConnectionRefused
+                | ErrorKind::NotConnected
+                | ErrorKind::BrokenPipe
+                | ErrorKind::AddrInUse
+        )
+    }
+}
+
+impl<E: Transience, S: ?Sized> Transience for Caused<E, S> {
+    fn is_transient(&self) -> bool {
+        self.error().is_transient()
+    }
+}
+
+impl<E: Transience> Transience for Spanned<E> {
+    fn is_transient(&self) -> bool {
+        self.error().is_transient()
+    }
+}
+
+impl<E: Transience> Transience for At<E> {
+    fn is_transient(&self) -> bool {
+        self.error().is_transient()
+    }
+}
+
+impl<E: Transience> Transience for crate::Diagnostic<E> {
+    fn is_transient(&self) -> bool {
+        self.error().is_transient()
+    }
+}
+
+impl<E: Transience> Transience for WithStatus<E> {
+    fn is_transient(&self) -> bool {
+        self.error().is_transient()
+    }
+}
+
+/// An error with an overridden [`Transience`], see
+/// [`Transience::transient`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WithTransience<E> {
+    error: E,
+    transient: bool,
+}
+
+impl<E> WithTransience<E> {
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+}
+
+impl<E> Transience for WithTransience<E> {
+    fn is_transient(&self) -> bool {
+        self.transient
+    }
+}
+
+impl<E: Display> Display for WithTransience<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.error, f)
+    }
+}
+
+impl<E: Error> Error for WithTransience<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+}
+
+impl<E: Fields> Fields for WithTransience<E> {
+    fn kind(&self) -> &'static str {
+        self.error.kind()
+    }
+
+    fn for_each_field(&self, visit: &mut dyn FnMut(&'static str, &dyn Display)) {
+        self.error.for_each_field(visit)
+    }
+
+    fn help(&self) -> Option<String> {
+        self.error.help()
+    }
+
+    fn severity(&self) -> crate::Severity {
+        self.error.severity()
+    }
+
+    fn as_explain(&self) -> Option<&dyn crate::Explain> {
+        self.error.as_explain()
+    }
+}
+
+impl<E: ErrorCode> ErrorCode for WithTransience<E> {
+    fn code(&self) -> u32 {
+        self.error.code()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::Transience;
+    use crate::IoGoof;
+    use std::io::{Error, ErrorKind};
+
+    #[test]
+    fn io_depends_on_kind() {
+        assert!(IoGoof::from(Error::from(ErrorKind::TimedOut)).is_transient());
+        assert!(!IoGoof::from(Error::from(ErrorKind::NotFound)).is_transient());
+        assert!(
+            crate::Caused::new(crate::Timeout::new(Default::default(), Default::default()))
+                .is_transient()
+        );
+    }
+}
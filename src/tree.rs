@@ -0,0 +1,238 @@
+//! Rendering errors that contain other errors as a tree.
+
+use crate::{render, Fields};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+
+/// One of the errors in an [`Aggregate`], which may be an aggregate
+/// itself.
+#[derive(Clone, Copy)]
+pub struct Child<'a> {
+    pub(crate) error: &'a (dyn Error + 'static),
+    pub(crate) aggregate: Option<&'a dyn Aggregate>,
+}
+
+impl<'a> Child<'a> {
+    /// A child that is drawn as a subtree if it is an aggregate, as
+    /// told by [`Fields::as_aggregate`].
+    pub fn new<E: Error + Fields + 'static>(error: &'a E) -> Self {
+        Self {
+            error,
+            aggregate: error.as_aggregate(),
+        }
+    }
+
+    /// A child that is drawn as a leaf, whatever it is.
+    pub fn leaf(error: &'a (dyn Error + 'static)) -> Self {
+        Self {
+            error,
+            aggregate: None,
+        }
+    }
+
+    pub fn error(&self) -> &'a (dyn Error + 'static) {
+        self.error
+    }
+
+    pub fn as_aggregate(&self) -> Option<&'a dyn Aggregate> {
+        self.aggregate
+    }
+}
+
+impl core::fmt::Debug for Child<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Child")
+            .field("error", &self.error)
+            .field("aggregate", &self.aggregate.is_some())
+            .finish()
+    }
+}
+
+/// An error made of other errors, such as every problem found in a
+/// configuration.
+pub trait Aggregate {
+    /// A one-line summary, without the errors themselves, e.g.
+    /// `"2 problems in app.toml"`.
+    fn fmt_summary(&self, f: &mut Formatter<'_>) -> core::fmt::Result;
+
+    /// Call `visit` with every error, in order, and with where it was
+    /// found, if that is known.
+    fn for_each_child<'a>(&'a self, visit: &mut dyn FnMut(Option<&dyn Display>, Child<'a>));
+
+    /// Render as a tree, nesting the aggregates that are among the
+    /// errors.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # if cfg!(feature = "compact") { return; }
+    /// use goof::config::ConfigGoof;
+    /// use goof::Aggregate;
+    ///
+    /// let mut plugins = ConfigGoof::new("plugins.toml");
+    /// plugins.key_in_range("jobs", 0_u32, &(1..16));
+    /// plugins.require_key::<&str>("name", None);
+    ///
+    /// let mut app = ConfigGoof::new("app.toml");
+    /// app.key_one_of("level", "loud", &["debug", "info"]);
+    /// app.push("plugins", plugins.finish().unwrap_err());
+    /// let error = app.finish().unwrap_err();
+    ///
+    /// assert_eq!(
+    ///     error.tree().to_string(),
+    ///     "2 problems in app.toml\n\
+    ///      ├─ level: The value loud is not known, because it's not one of [debug, info]\n\
+    ///      └─ plugins: 2 problems in plugins.toml\n\
+    ///      \x20  ├─ jobs: Value 0 is outside the allowed range (1, 16] (must be greater than 1)\n\
+    ///      \x20  └─ name: Missing field `name` in plugins.toml"
+    /// );
+    /// assert!(error.tree().ascii().to_string().contains("\n`- plugins: "));
+    /// ```
+    fn tree(&self) -> Tree<'_>
+    where
+        Self: Sized,
+    {
+        Tree {
+            root: self,
+            glyphs: Glyphs::UNICODE,
+        }
+    }
+}
+
+/// The pieces a [`Tree`] is drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Glyphs {
+    /// Before every child but the last.
+    pub branch: &'static str,
+    /// Before the last child.
+    pub last: &'static str,
+    /// Below a child that has siblings after it.
+    pub pipe: &'static str,
+    /// Below the last child.
+    pub space: &'static str,
+}
+
+impl Glyphs {
+    pub const UNICODE: Glyphs = Glyphs {
+        branch: "├─ ",
+        last: "└─ ",
+        pipe: "│  ",
+        space: "   ",
+    };
+
+    /// For terminals and logs that mangle box-drawing characters.
+    pub const ASCII: Glyphs = Glyphs {
+        branch: "+- ",
+        last: "`- ",
+        pipe: "|  ",
+        space: "   ",
+    };
+}
+
+/// `Display` adapter returned by [`Aggregate::tree`].
+pub struct Tree<'a> {
+    root: &'a dyn Aggregate,
+    glyphs: Glyphs,
+}
+
+impl Tree<'_> {
+    pub fn ascii(self) -> Self {
+        self.glyphs(Glyphs::ASCII)
+    }
+
+    pub fn glyphs(self, glyphs: Glyphs) -> Self {
+        Self { glyphs, ..self }
+    }
+}
+
+impl Display for Tree<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.root.fmt_summary(f)?;
+        write_children(f, self.root, "", self.glyphs)
+    }
+}
+
+fn write_children(
+    f: &mut Formatter<'_>,
+    aggregate: &dyn Aggregate,
+    prefix: &str,
+    glyphs: Glyphs,
+) -> core::fmt::Result {
+    let mut children = Vec::new();
+    aggregate.for_each_child(&mut |label, child| {
+        children.push((label.map(|label| render(label, false)), child))
+    });
+    for (index, (label, child)) in children.iter().enumerate() {
+        let last = index + 1 == children.len();
+        let (glyph, below) = if last {
+            (glyphs.last, glyphs.space)
+        } else {
+            (glyphs.branch, glyphs.pipe)
+        };
+        write!(f, "\n{}{}", prefix, glyph)?;
+        if let Some(label) = label {
+            write!(f, "{}: ", label)?;
+        }
+        let mut nested = String::from(prefix);
+        nested.push_str(below);
+        match child.aggregate {
+            Some(inner) => {
+                inner.fmt_summary(f)?;
+                write_children(f, inner, &nested, glyphs)?;
+            }
+            None => {
+                for (line_index, line) in render(child.error, false).split('\n').enumerate() {
+                    if line_index > 0 {
+                        write!(f, "\n{}", nested)?;
+                    }
+                    f.write_str(line)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Aggregate;
+    use crate::config::ConfigGoof;
+    use alloc::string::ToString;
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn multi_line_leaves_stay_in_their_branch() {
+        let mut config = ConfigGoof::new("app.toml");
+        config.push("banner", crate::Goof::from("first\nsecond"));
+        config.push("motd", crate::Goof::from("third"));
+        assert_eq!(
+            config.tree().ascii().to_string(),
+            "2 problems in app.toml\n+- banner: first\n|  second\n`- motd: third"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn nested_multi_errors_are_subtrees() {
+        let file = |name: &str, fields: &[&str]| {
+            let errors: crate::MultiError<_> = fields
+                .iter()
+                .map(|field| crate::At::new(crate::Goof::from("missing")).field(*field))
+                .collect();
+            crate::At::new(errors).field(name)
+        };
+        let error: crate::MultiError<_> = [file("a.toml", &["x", "y"]), file("b.toml", &["z"])]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            error.tree().ascii().to_string(),
+            "2 errors\n\
+             +- a.toml: 2 errors\n\
+             |  +- x: missing\n\
+             |  `- y: missing\n\
+             `- b.toml: 1 error\n\
+             \x20  `- z: missing"
+        );
+    }
+}
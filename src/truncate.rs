@@ -0,0 +1,237 @@
+//! Keeping messages readable when values are huge.
+
+use core::fmt::{Display, Formatter, Write};
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// Both limits of the current [`Truncation`] in one word, so that no
+/// thread sees the width of one setting with the knowns of another.
+static CURRENT: AtomicU64 = AtomicU64::new(Truncation::DEFAULT.pack());
+
+/// How much of long values and long lists of knowns the `Display` of
+/// an error shows.
+///
+/// # Examples
+/// ```rust
+/// # if cfg!(feature = "compact") { return; }
+/// use goof::{Truncate, Truncation};
+///
+/// let knowns: Vec<u32> = (0..10_000).collect();
+/// let error = goof::assert_known_enum(&knowns, 20_000).unwrap_err();
+/// assert!(error.to_string().ends_with(", 15, …and 9984 more]"));
+///
+/// let terse = Truncation { max_width: 80, max_knowns: 2 };
+/// assert_eq!(
+///     error.truncated(terse).to_string(),
+///     "The value 20000 is not known, because it's not one of [0, 1, …and 9998 more]"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Truncation {
+    /// The most characters of a single value to show.
+    pub max_width: usize,
+    /// The most elements of a list of knowns to show.
+    pub max_knowns: usize,
+}
+
+impl Truncation {
+    /// What errors use unless [`Truncation::set_default`] was called.
+    pub const DEFAULT: Truncation = Truncation {
+        max_width: 120,
+        max_knowns: 16,
+    };
+
+    /// Show everything.
+    pub const UNLIMITED: Truncation = Truncation {
+        max_width: usize::MAX,
+        max_knowns: usize::MAX,
+    };
+
+    /// What the `Display` of every error uses from now on.  Limits of
+    /// `u32::MAX` or more are stored as [`usize::MAX`], i.e. unlimited.
+    pub fn set_default(self) {
+        CURRENT.store(self.pack(), Ordering::Relaxed);
+    }
+
+    /// What the `Display` of errors currently uses.
+    pub fn current() -> Self {
+        Self::unpack(CURRENT.load(Ordering::Relaxed))
+    }
+
+    /// The width in the high half and the knowns in the low half.
+    const fn pack(self) -> u64 {
+        const fn half(limit: usize) -> u64 {
+            if limit >= u32::MAX as usize {
+                u32::MAX as u64
+            } else {
+                limit as u64
+            }
+        }
+        half(self.max_width) << 32 | half(self.max_knowns)
+    }
+
+    fn unpack(packed: u64) -> Self {
+        let half = |limit: u64| match limit as u32 {
+            u32::MAX => usize::MAX,
+            limit => limit as usize,
+        };
+        Self {
+            max_width: half(packed >> 32),
+            max_knowns: half(packed),
+        }
+    }
+
+    /// Write `value`, cut off after `max_width` characters.
+    pub(crate) fn write_value(
+        self,
+        f: &mut Formatter<'_>,
+        value: &(impl Display + ?Sized),
+    ) -> core::fmt::Result {
+        let mut limited = Limited {
+            f,
+            left: self.max_width,
+            cut: false,
+        };
+        let result = write!(limited, "{}", value);
+        if limited.cut {
+            f.write_str("…")
+        } else {
+            result
+        }
+    }
+
+    /// Write the first `max_knowns` of `items`, separated by `", "`.
+    pub(crate) fn write_list<T: Display>(
+        self,
+        f: &mut Formatter<'_>,
+        items: &[T],
+    ) -> core::fmt::Result {
+        for (index, item) in items.iter().take(self.max_knowns).enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            self.write_value(f, item)?;
+        }
+        if let Some(more) = items
+            .len()
+            .checked_sub(self.max_knowns)
+            .filter(|more| *more > 0)
+        {
+            if self.max_knowns > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "…and {} more", more)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for Truncation {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// `Display` of a list shortened by [`Truncation::write_list`].
+pub(crate) struct List<'a, T>(pub(crate) Truncation, pub(crate) &'a [T]);
+
+impl<T: Display> Display for List<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.0.write_list(f, self.1)
+    }
+}
+
+/// Passes at most `left` characters through, then fails.
+struct Limited<'a, 'b> {
+    f: &'a mut Formatter<'b>,
+    left: usize,
+    cut: bool,
+}
+
+impl Write for Limited<'_, '_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        match s.char_indices().nth(self.left) {
+            None => {
+                self.left -= s.chars().count();
+                self.f.write_str(s)
+            }
+            Some((end, _)) => {
+                self.f.write_str(&s[..end])?;
+                self.left = 0;
+                self.cut = true;
+                Err(core::fmt::Error)
+            }
+        }
+    }
+}
+
+/// An error whose `Display` can be truncated differently from the
+/// [`Truncation::current`] default.
+pub trait Truncate {
+    fn fmt_truncated(&self, f: &mut Formatter<'_>, truncation: Truncation) -> core::fmt::Result;
+
+    /// Render with `truncation` instead of the default.
+    fn truncated(&self, truncation: Truncation) -> Truncated<'_, Self> {
+        Truncated {
+            error: self,
+            truncation,
+        }
+    }
+}
+
+/// `Display` adapter returned by [`Truncate::truncated`].
+pub struct Truncated<'a, E: ?Sized> {
+    error: &'a E,
+    truncation: Truncation,
+}
+
+impl<E: Truncate + ?Sized> Display for Truncated<'_, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.error.fmt_truncated(f, self.truncation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Truncate, Truncation};
+    use alloc::string::{String, ToString};
+
+    #[test]
+    #[cfg_attr(feature = "compact", ignore = "compares full messages")]
+    fn long_values() {
+        let long = "é".repeat(10);
+        let error = crate::assert_eq(&long.as_str(), &"").unwrap_err();
+        let narrow = Truncation {
+            max_width: 3,
+            ..Truncation::UNLIMITED
+        };
+        assert_eq!(
+            error.truncated(narrow).to_string(),
+            "Expected ééé…, but got "
+        );
+        assert_eq!(
+            error.truncated(Truncation::UNLIMITED).to_string(),
+            error.to_string()
+        );
+        let huge = String::from("x").repeat(1000);
+        let error = crate::assert_eq(&huge.as_str(), &"").unwrap_err();
+        assert_eq!(error.to_string().chars().count(), 140);
+    }
+
+    #[test]
+    fn limits_are_stored_together() {
+        for truncation in [Truncation::DEFAULT, Truncation::UNLIMITED] {
+            assert_eq!(Truncation::unpack(truncation.pack()), truncation);
+        }
+        let huge = Truncation {
+            max_width: 7,
+            max_knowns: u32::MAX as usize,
+        };
+        assert_eq!(
+            Truncation::unpack(huge.pack()),
+            Truncation {
+                max_knowns: usize::MAX,
+                ..huge
+            }
+        );
+    }
+}
@@ -0,0 +1,59 @@
+//! Conversion into a JavaScript `Error`, so that a library compiled to
+//! WebAssembly can throw its errors across `wasm-bindgen` without
+//! losing their payload.
+//!
+//! The message of the `Error` is the `Display` of the error, and its
+//! `cause` is a plain object with the `kind`, the `code` and one
+//! property per payload field, as in [`Fields`]:
+//!
+//! ```js
+//! try { check(32, 0) } catch (e) {
+//!   e.message  // "Expected 32, but got 0"
+//!   e.cause    // { kind: "mismatch", code: 1, expected: "32", actual: "0" }
+//! }
+//! ```
+//!
+//! Field values are strings, since [`Fields`] only exposes them as
+//! `Display`; the `code` is a number.
+
+use crate::{render, ErrorCode, Fields};
+use core::fmt::Display;
+use js_sys::wasm_bindgen::JsValue;
+use js_sys::{Error, Object, Reflect};
+
+/// An error that can be thrown into JavaScript as an `Error`, which is
+/// every error with [`Fields`] and an [`ErrorCode`].
+///
+/// # Examples
+/// ```rust,no_run
+/// use goof::ToJsError;
+/// use js_sys::wasm_bindgen::JsValue;
+///
+/// fn check(expected: u32, actual: u32) -> Result<u32, JsValue> {
+///     goof::assert_eq(&expected, &actual).map_err(|error| error.to_js_error().into())
+/// }
+/// ```
+pub trait ToJsError: Fields + ErrorCode + Display {
+    /// The JavaScript `Error` with the message of `self`, and its
+    /// payload as the `cause`.
+    fn to_js_error(&self) -> Error {
+        let error = Error::new(&render(self, false));
+        error.set_cause(&self.to_js_cause());
+        error
+    }
+
+    /// The plain object set as the `cause` by
+    /// [`to_js_error`](ToJsError::to_js_error).
+    fn to_js_cause(&self) -> JsValue {
+        let cause: JsValue = Object::new().into();
+        // `Reflect.set` only fails on a non-object target.
+        let _ = Reflect::set(&cause, &"kind".into(), &self.kind().into());
+        let _ = Reflect::set(&cause, &"code".into(), &self.code().into());
+        self.for_each_field(&mut |name, value| {
+            let _ = Reflect::set(&cause, &name.into(), &render(value, false).into());
+        });
+        cause
+    }
+}
+
+impl<E: Fields + ErrorCode + Display + ?Sized> ToJsError for E {}
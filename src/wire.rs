@@ -0,0 +1,537 @@
+//! A compact, versioned binary encoding of the built-in errors, for
+//! shipping them from devices that cannot afford strings.
+//!
+//! An encoded error is a version byte, the [`crate::code`] of its kind
+//! and its payload.  Codes and integer values are zigzag varints, and
+//! intervals, operators and flags a single byte each.  Nothing is
+//! allocated, so the buffers can live on the stack or in a
+//! `heapless::Vec`.
+//!
+//! Every built-in error whose payload is integers has an encoding.
+//! The ones that carry text, durations, borrowed slices or other
+//! errors, e.g. [`crate::Goof`], [`crate::Timeout`],
+//! [`crate::NotSubset`] or [`crate::MultiError`], do not.
+
+use crate::arith::{DivisionByZero, Op, Overflow, Underflow};
+use crate::code;
+use crate::{
+    Duplicate, EmptyRange, Exceeded, Interval, LengthMismatch, Misaligned, Mismatch, NotMultipleOf,
+    NotPositive, Outside, TooLarge, TooShort, TooSmall, Unknown, UnknownBits, UnsortedAt, Zero,
+};
+use core::fmt::Display;
+
+/// The version written by this release; decoding any other version
+/// fails.
+pub const WIRE_VERSION: u8 = 1;
+
+/// Why encoding or decoding failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WireError {
+    /// The buffer to encode into is too small.
+    BufferTooSmall,
+    /// The input ended in the middle of an error.
+    Truncated,
+    /// The input was written by an incompatible release.
+    Version(Mismatch<u8>),
+    /// The input holds a different kind of error.
+    Kind(Mismatch<u32>),
+    /// A value does not fit in the type being decoded.
+    Overflow,
+    /// A byte is not one of the values its field can take.
+    Malformed,
+}
+
+impl Display for WireError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            WireError::BufferTooSmall => f.write_str("The buffer is too small"),
+            WireError::Truncated => f.write_str("The input is truncated"),
+            WireError::Version(mismatch) => write!(f, "Unsupported version: {}", mismatch),
+            WireError::Kind(mismatch) => write!(f, "Unexpected kind: {}", mismatch),
+            WireError::Overflow => f.write_str("A value does not fit its type"),
+            WireError::Malformed => f.write_str("A field holds an invalid byte"),
+        }
+    }
+}
+
+impl core::error::Error for WireError {}
+
+/// An integer payload value.  Every value of every implementing type
+/// is encoded exactly.
+pub trait WireValue: Copy {
+    fn to_wire(self) -> i128;
+    fn from_wire(value: i128) -> Option<Self>;
+}
+
+macro_rules! wire_value {
+    ($($t:ty),*) => {
+        $(impl WireValue for $t {
+            fn to_wire(self) -> i128 {
+                self as i128
+            }
+
+            fn from_wire(value: i128) -> Option<Self> {
+                <$t>::try_from(value).ok()
+            }
+        })*
+    };
+}
+
+wire_value!(u8, u16, u32, u64, usize, i8, i16, i32, i64, i128, isize);
+
+/// An error with a binary encoding.
+///
+/// # Examples
+/// ```rust
+/// use goof::wire::Wire;
+/// use goof::Mismatch;
+///
+/// let error = goof::assert_eq(&32_u16, &0).unwrap_err();
+/// let mut buffer = [0; 16];
+/// let len = error.encode(&mut buffer).unwrap();
+/// assert_eq!(&buffer[..len], &[1, 2, 64, 0]);
+/// assert_eq!(Mismatch::<u16>::decode(&buffer[..len]), Ok((error, len)));
+/// ```
+pub trait Wire: Sized {
+    /// Encode `self` at the start of `buffer`, returning the number of
+    /// bytes written.
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, WireError>;
+
+    /// Decode an error from the start of `bytes`, returning it and the
+    /// number of bytes read.
+    fn decode(bytes: &[u8]) -> Result<(Self, usize), WireError>;
+}
+
+struct Writer<'a> {
+    buffer: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> Writer<'a> {
+    fn new(buffer: &'a mut [u8], code: u32) -> Result<Self, WireError> {
+        let mut writer = Writer { buffer, len: 0 };
+        writer.byte(WIRE_VERSION)?;
+        writer.value(code)?;
+        Ok(writer)
+    }
+
+    fn byte(&mut self, byte: u8) -> Result<(), WireError> {
+        *self
+            .buffer
+            .get_mut(self.len)
+            .ok_or(WireError::BufferTooSmall)? = byte;
+        self.len += 1;
+        Ok(())
+    }
+
+    fn value(&mut self, value: impl WireValue) -> Result<(), WireError> {
+        let value = value.to_wire();
+        let mut zigzag = ((value << 1) ^ (value >> 127)) as u128;
+        loop {
+            let byte = (zigzag & 0x7f) as u8;
+            zigzag >>= 7;
+            if zigzag == 0 {
+                return self.byte(byte);
+            }
+            self.byte(byte | 0x80)?;
+        }
+    }
+
+    fn interval(&mut self, interval: Interval) -> Result<(), WireError> {
+        self.byte(interval as u8)
+    }
+
+    fn op(&mut self, op: Op) -> Result<(), WireError> {
+        self.byte(op as u8)
+    }
+
+    fn flag(&mut self, flag: bool) -> Result<(), WireError> {
+        self.byte(u8::from(flag))
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    len: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8], code: u32) -> Result<Self, WireError> {
+        let mut reader = Reader { bytes, len: 0 };
+        // Not `assert_eq`, so that probing a buffer does not count as a
+        // failed assertion.
+        let version = reader.byte()?;
+        if version != WIRE_VERSION {
+            return Err(WireError::Version(Mismatch {
+                expected: WIRE_VERSION,
+                actual: version,
+                provenance: "decode",
+            }));
+        }
+        let kind = reader.value()?;
+        if kind != code {
+            return Err(WireError::Kind(Mismatch {
+                expected: code,
+                actual: kind,
+                provenance: "decode",
+            }));
+        }
+        Ok(reader)
+    }
+
+    fn byte(&mut self) -> Result<u8, WireError> {
+        let byte = *self.bytes.get(self.len).ok_or(WireError::Truncated)?;
+        self.len += 1;
+        Ok(byte)
+    }
+
+    fn value<T: WireValue>(&mut self) -> Result<T, WireError> {
+        let mut zigzag = 0_u128;
+        for shift in (0..128).step_by(7) {
+            let byte = self.byte()?;
+            let bits = u128::from(byte & 0x7f);
+            if bits.leading_zeros() < shift {
+                return Err(WireError::Overflow);
+            }
+            zigzag |= bits << shift;
+            if byte & 0x80 == 0 {
+                let value = (zigzag >> 1) as i128 ^ -((zigzag & 1) as i128);
+                return T::from_wire(value).ok_or(WireError::Overflow);
+            }
+        }
+        Err(WireError::Overflow)
+    }
+
+    fn interval(&mut self) -> Result<Interval, WireError> {
+        match self.byte()? {
+            0 => Ok(Interval::Closed),
+            1 => Ok(Interval::LeftOpen),
+            2 => Ok(Interval::HalfOpen),
+            3 => Ok(Interval::Open),
+            _ => Err(WireError::Malformed),
+        }
+    }
+
+    fn op(&mut self) -> Result<Op, WireError> {
+        match self.byte()? {
+            0 => Ok(Op::Add),
+            1 => Ok(Op::Sub),
+            2 => Ok(Op::Mul),
+            3 => Ok(Op::Div),
+            _ => Err(WireError::Malformed),
+        }
+    }
+
+    fn flag(&mut self) -> Result<bool, WireError> {
+        match self.byte()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(WireError::Malformed),
+        }
+    }
+}
+
+/// `Wire` for errors whose payload is only integers, encoded in the
+/// order of the fields given.
+macro_rules! wire {
+    ($(impl<$($generic:ident: $bound:path),*> $error:ty = $code:path { $($field:ident),* })*) => {
+        $(impl<$($generic: WireValue + $bound),*> Wire for $error {
+            fn encode(&self, buffer: &mut [u8]) -> Result<usize, WireError> {
+                let mut writer = Writer::new(buffer, $code)?;
+                $(writer.value(self.$field)?;)*
+                Ok(writer.len)
+            }
+
+            fn decode(bytes: &[u8]) -> Result<(Self, usize), WireError> {
+                let mut reader = Reader::new(bytes, $code)?;
+                let error = Self {
+                    $($field: reader.value()?,)*
+                    provenance: "decode",
+                };
+                Ok((error, reader.len))
+            }
+        })*
+    };
+}
+
+wire! {
+    impl<T: Eq> Mismatch<T> = code::MISMATCH { expected, actual }
+    impl<> LengthMismatch = code::LENGTH_MISMATCH { expected, actual }
+    impl<T: Eq> UnsortedAt<T> = code::UNSORTED { index, prev, next }
+    impl<T: Eq> Duplicate<T> = code::DUPLICATE { value, first_index, second_index }
+    impl<T: Eq> DivisionByZero<T> = code::DIVISION_BY_ZERO { dividend }
+    impl<T: Eq> NotPositive<T> = code::NOT_POSITIVE { value }
+    impl<T: Eq> NotMultipleOf<T> = code::NOT_MULTIPLE_OF { value, factor }
+    impl<T: Eq> Exceeded<T> = code::EXCEEDED { requested, limit, over }
+    impl<> Misaligned = code::MISALIGNED { required, actual_offset }
+    impl<T: Eq> UnknownBits<T> = code::UNKNOWN_BITS { known_mask, value, unknown }
+    impl<> TooShort = code::TOO_SHORT { needed, available }
+}
+
+impl<T: WireValue + PartialOrd> Wire for Outside<T> {
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, WireError> {
+        let mut writer = Writer::new(buffer, code::OUTSIDE)?;
+        writer.value(self.range.start)?;
+        writer.value(self.range.end)?;
+        writer.interval(self.interval)?;
+        writer.value(self.value)?;
+        Ok(writer.len)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<(Self, usize), WireError> {
+        let mut reader = Reader::new(bytes, code::OUTSIDE)?;
+        let error = Outside {
+            range: reader.value()?..reader.value()?,
+            interval: reader.interval()?,
+            value: reader.value()?,
+            provenance: "decode",
+        };
+        Ok((error, reader.len))
+    }
+}
+
+impl<T: WireValue> Wire for EmptyRange<T> {
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, WireError> {
+        let mut writer = Writer::new(buffer, code::EMPTY_RANGE)?;
+        writer.value(self.range.start)?;
+        writer.value(self.range.end)?;
+        writer.interval(self.interval)?;
+        Ok(writer.len)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<(Self, usize), WireError> {
+        let mut reader = Reader::new(bytes, code::EMPTY_RANGE)?;
+        let error = EmptyRange {
+            range: reader.value()?..reader.value()?,
+            interval: reader.interval()?,
+            provenance: "decode",
+        };
+        Ok((error, reader.len))
+    }
+}
+
+/// Only the value is encoded; the knowns are decoded as `None`.
+impl<T: WireValue + Eq> Wire for Unknown<'_, T> {
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, WireError> {
+        let mut writer = Writer::new(buffer, code::UNKNOWN)?;
+        writer.value(self.value)?;
+        Ok(writer.len)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<(Self, usize), WireError> {
+        let mut reader = Reader::new(bytes, code::UNKNOWN)?;
+        let error = Unknown {
+            knowns: None,
+            value: reader.value()?,
+            provenance: "decode",
+        };
+        Ok((error, reader.len))
+    }
+}
+
+impl Wire for Zero {
+    fn encode(&self, buffer: &mut [u8]) -> Result<usize, WireError> {
+        Ok(Writer::new(buffer, code::ZERO)?.len)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<(Self, usize), WireError> {
+        let reader = Reader::new(bytes, code::ZERO)?;
+        let error = Zero {
+            provenance: "decode",
+        };
+        Ok((error, reader.len))
+    }
+}
+
+macro_rules! wire_bound {
+    ($($error:ident = $code:path { $bound:ident }),*) => {
+        $(impl<T: WireValue> Wire for $error<T> {
+            fn encode(&self, buffer: &mut [u8]) -> Result<usize, WireError> {
+                let mut writer = Writer::new(buffer, $code)?;
+                writer.value(self.$bound)?;
+                writer.value(self.actual)?;
+                writer.flag(self.strict)?;
+                Ok(writer.len)
+            }
+
+            fn decode(bytes: &[u8]) -> Result<(Self, usize), WireError> {
+                let mut reader = Reader::new(bytes, $code)?;
+                let error = $error {
+                    $bound: reader.value()?,
+                    actual: reader.value()?,
+                    strict: reader.flag()?,
+                    provenance: "decode",
+                };
+                Ok((error, reader.len))
+            }
+        })*
+    };
+}
+
+wire_bound!(
+    TooLarge = code::TOO_LARGE { max },
+    TooSmall = code::TOO_SMALL { min }
+);
+
+macro_rules! wire_out_of_range {
+    ($($error:ident = $code:path),*) => {
+        $(impl<T: WireValue> Wire for $error<T> {
+            fn encode(&self, buffer: &mut [u8]) -> Result<usize, WireError> {
+                let mut writer = Writer::new(buffer, $code)?;
+                writer.value(*self.lhs())?;
+                writer.op(self.op())?;
+                writer.value(*self.rhs())?;
+                Ok(writer.len)
+            }
+
+            fn decode(bytes: &[u8]) -> Result<(Self, usize), WireError> {
+                let mut reader = Reader::new(bytes, $code)?;
+                let error = $error::new(reader.value()?, reader.op()?, reader.value()?, "decode");
+                Ok((error, reader.len))
+            }
+        })*
+    };
+}
+
+wire_out_of_range!(Overflow = code::OVERFLOW, Underflow = code::UNDERFLOW);
+
+#[cfg(test)]
+mod tests {
+    use super::{Wire, WireError};
+    use crate::arith::{checked_add, checked_div, checked_sub, ArithGoof};
+    use crate::{BoundGoof, Interval, Mismatch, Outside, Unknown};
+    use core::fmt::Debug;
+
+    fn round_trip<E: Wire + PartialEq + Debug>(error: E) {
+        let mut buffer = [0; 64];
+        let len = error.encode(&mut buffer).unwrap();
+        assert_eq!(E::decode(&buffer[..len]), Ok((error, len)));
+        assert_eq!(E::decode(&buffer[..len - 1]), Err(WireError::Truncated));
+    }
+
+    #[test]
+    fn round_trips_and_rejects() {
+        let mut buffer = [0; 64];
+
+        let outside = crate::assert_in(&i128::MIN, &(-1..i128::MAX)).unwrap_err();
+        let len = outside.encode(&mut buffer).unwrap();
+        assert_eq!(Outside::decode(&buffer[..len]), Ok((outside.clone(), len)));
+        assert_eq!(
+            Outside::<i128>::decode(&buffer[..len - 1]),
+            Err(WireError::Truncated)
+        );
+        assert_eq!(
+            Outside::<i8>::decode(&buffer[..len]),
+            Err(WireError::Overflow)
+        );
+        assert!(matches!(
+            Mismatch::<i128>::decode(&buffer[..len]),
+            Err(WireError::Kind(_))
+        ));
+        assert_eq!(
+            outside.encode(&mut buffer[..4]),
+            Err(WireError::BufferTooSmall)
+        );
+
+        let unknown = crate::assert_known(&[1_u64], u64::MAX).unwrap_err();
+        let len = unknown.encode(&mut buffer).unwrap();
+        assert_eq!(
+            Unknown::<u64>::decode(&buffer[..len]).map(|(u, _)| u.value),
+            Ok(u64::MAX)
+        );
+
+        buffer[0] = 2;
+        assert!(matches!(
+            Unknown::<u64>::decode(&buffer),
+            Err(WireError::Version(_))
+        ));
+    }
+
+    #[test]
+    fn every_interval_survives() {
+        for interval in [
+            Interval::Closed,
+            Interval::LeftOpen,
+            Interval::HalfOpen,
+            Interval::Open,
+        ] {
+            round_trip(crate::assert_in_with(&9_u8, &(1..5), interval).unwrap_err());
+            let (start, end) = (5_i16, -5);
+            round_trip(crate::assert_range(&(start..end), interval).unwrap_err());
+        }
+
+        let mut buffer = [0; 8];
+        let outside = crate::assert_in(&7_u8, &(1..5)).unwrap_err();
+        let len = outside.encode(&mut buffer).unwrap();
+        buffer[len - 2] = 4;
+        assert_eq!(
+            Outside::<u8>::decode(&buffer[..len]),
+            Err(WireError::Malformed)
+        );
+    }
+
+    #[test]
+    fn every_integer_kind_round_trips() {
+        round_trip(crate::assert_eq(&-1_i32, &2).unwrap_err());
+        round_trip(crate::assert_len([1, 2], 3).unwrap_err());
+        round_trip(crate::assert_sorted(&[3_u16, 1]).unwrap_err());
+        round_trip(crate::assert_unique(&[4_i64, 2, 4]).unwrap_err());
+        round_trip(crate::assert_positive(-3_i8).unwrap_err());
+        round_trip(crate::assert_nonzero(0_u32).unwrap_err());
+        round_trip(crate::assert_multiple_of(7_u8, 3).unwrap_err());
+        round_trip(crate::assert_capacity(9_usize, 4).unwrap_err());
+        round_trip(crate::assert_aligned(3_usize, 8).unwrap_err());
+        round_trip(crate::assert_known_bits(0b1010_u8, 0b0111).unwrap_err());
+        round_trip(crate::assert_remaining(&[0; 2], 5).unwrap_err());
+        for error in [
+            crate::assert_le(&5_i32, &4).unwrap_err(),
+            crate::assert_lt(&4_i32, &4).unwrap_err(),
+            crate::assert_ge(&3_i32, &4).unwrap_err(),
+            crate::assert_gt(&4_i32, &4).unwrap_err(),
+        ] {
+            match error {
+                BoundGoof::TooLarge(error) => round_trip(error),
+                BoundGoof::TooSmall(error) => round_trip(error),
+                BoundGoof::NotComparable(_) => unreachable!(),
+            }
+        }
+        for error in [
+            checked_add(i8::MAX, 1).unwrap_err(),
+            checked_sub(i8::MIN, 1).unwrap_err(),
+            checked_div(i8::MIN, 0).unwrap_err(),
+        ] {
+            match error {
+                ArithGoof::Overflow(error) => round_trip(error),
+                ArithGoof::Underflow(error) => round_trip(error),
+                ArithGoof::DivisionByZero(error) => round_trip(error),
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn decoding_is_not_observed() {
+        use std::sync::Mutex;
+        use std::thread::{self, ThreadId};
+
+        let _lock = crate::hook::HOOK_LOCK
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        static SEEN: Mutex<Vec<ThreadId>> = Mutex::new(Vec::new());
+        let mut buffer = [0; 64];
+        let len = crate::assert_in(&0_u8, &(1..5))
+            .unwrap_err()
+            .encode(&mut buffer)
+            .unwrap();
+
+        crate::set_hook(|_| SEEN.lock().unwrap().push(thread::current().id()));
+        assert!(Outside::<u8>::decode(&buffer[..len]).is_ok());
+        assert!(Mismatch::<u8>::decode(&buffer[..len]).is_err());
+        buffer[0] = 0;
+        assert!(Outside::<u8>::decode(&buffer[..len]).is_err());
+        crate::take_hook();
+
+        // Other tests may fail assertions concurrently, on their threads.
+        assert!(!SEEN.lock().unwrap().contains(&thread::current().id()));
+    }
+}